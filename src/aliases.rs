@@ -0,0 +1,163 @@
+//! The names the community calls moves by, e.g. Ryu's `hadoken` or Zangief's `SPD`, mapped to
+//! the wiki's identifiers so every bot doesn't need to maintain its own alias map.
+
+use crate::character::{self, CharacterId};
+use crate::framedata::{CharacterFrameData, FrameData, Move, SF6FrameDataError};
+use crate::text::normalize;
+
+/// A community name for a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveAlias {
+    /// The character the alias is for, `None` if it means the same thing for everyone
+    pub character_id: Option<&'static CharacterId>,
+    pub alias: &'static str,
+    /// The wiki identifier the alias stands for. Punches and kicks match any strength, so `236P`
+    /// finds `236LP`, and a move whose identifier contains it also matches, so `SA3` finds
+    /// `236236K(SA3)`.
+    pub identifier: &'static str,
+}
+
+impl MoveAlias {
+    pub const fn shared(alias: &'static str, identifier: &'static str) -> Self {
+        MoveAlias {
+            character_id: None,
+            alias,
+            identifier,
+        }
+    }
+
+    pub const fn character(character_id: &'static CharacterId, alias: &'static str, identifier: &'static str) -> Self {
+        MoveAlias {
+            character_id: Some(character_id),
+            alias,
+            identifier,
+        }
+    }
+}
+
+/// Every alias the crate knows. Character aliases take precedence over shared ones.
+pub static ALIASES: [MoveAlias; 44] = [
+    MoveAlias::shared("throw", "LPLK"),
+    MoveAlias::shared("drive parry", "MPMK"),
+    MoveAlias::shared("parry", "MPMK"),
+    MoveAlias::shared("drive impact", "HPHK"),
+    MoveAlias::shared("di", "HPHK"),
+    MoveAlias::shared("drive reversal", "6HPHK"),
+    MoveAlias::shared("level 1", "SA1"),
+    MoveAlias::shared("lvl 1", "SA1"),
+    MoveAlias::shared("super 1", "SA1"),
+    MoveAlias::shared("level 2", "SA2"),
+    MoveAlias::shared("lvl 2", "SA2"),
+    MoveAlias::shared("super 2", "SA2"),
+    MoveAlias::shared("level 3", "SA3"),
+    MoveAlias::shared("lvl 3", "SA3"),
+    MoveAlias::shared("super 3", "SA3"),
+    MoveAlias::shared("ca", "SA3"),
+    MoveAlias::shared("critical art", "SA3"),
+    MoveAlias::character(&character::RYU, "hadoken", "236P"),
+    MoveAlias::character(&character::RYU, "fireball", "236P"),
+    MoveAlias::character(&character::RYU, "shoryuken", "623P"),
+    MoveAlias::character(&character::RYU, "dp", "623P"),
+    MoveAlias::character(&character::RYU, "tatsu", "214K"),
+    MoveAlias::character(&character::RYU, "hashogeki", "214P"),
+    MoveAlias::character(&character::RYU, "denjin", "22P"),
+    MoveAlias::character(&character::KEN, "hadoken", "236P"),
+    MoveAlias::character(&character::KEN, "fireball", "236P"),
+    MoveAlias::character(&character::KEN, "shoryuken", "623P"),
+    MoveAlias::character(&character::KEN, "dp", "623P"),
+    MoveAlias::character(&character::KEN, "tatsu", "214K"),
+    MoveAlias::character(&character::KEN, "dragonlash", "623K"),
+    MoveAlias::character(&character::KEN, "jinrai", "236K"),
+    MoveAlias::character(&character::ZANGIEF, "spd", "360P"),
+    MoveAlias::character(&character::ZANGIEF, "screw piledriver", "360P"),
+    MoveAlias::character(&character::ZANGIEF, "borscht", "360K"),
+    MoveAlias::character(&character::ZANGIEF, "lariat", "PPP"),
+    MoveAlias::character(&character::GUILE, "sonic boom", "[4]6P"),
+    MoveAlias::character(&character::GUILE, "boom", "[4]6P"),
+    MoveAlias::character(&character::GUILE, "flash kick", "[2]8K"),
+    MoveAlias::character(&character::GUILE, "flash", "[2]8K"),
+    MoveAlias::character(&character::LUKE, "sand blast", "236P"),
+    MoveAlias::character(&character::LUKE, "flash knuckle", "214P"),
+    MoveAlias::character(&character::LUKE, "rising uppercut", "623P"),
+    MoveAlias::character(&character::LUKE, "dp", "623P"),
+    MoveAlias::character(&character::JURI, "fuha", "214K"),
+];
+
+/// The wiki identifier `alias` stands for on `character_id`, ignoring case, spaces and
+/// punctuation, so `Level-3` and `lvl3` both work
+pub fn resolve_alias(character_id: &CharacterId, alias: &str) -> Option<&'static str> {
+    let alias = normalize(alias);
+    let matching = |a: &&MoveAlias| normalize(a.alias) == alias;
+    ALIASES.iter().filter(|a| a.character_id == Some(character_id)).find(matching)
+        .or_else(|| ALIASES.iter().filter(|a| a.character_id.is_none()).find(matching))
+        .map(|a| a.identifier)
+}
+
+impl CharacterFrameData {
+    /// Finds a move by one of its community names, see [`ALIASES`]. A query that isn't an alias
+    /// is matched against identifiers like [`FrameData::find_move`], so this can stand in for it.
+    pub fn find_move_by_alias(&self, alias: &str) -> Result<&Move, SF6FrameDataError> {
+        let Some(identifier) = resolve_alias(&self.character_id, alias) else {
+            return self.moves.iter().find(|m| m.identifier.eq_ignore_ascii_case(alias.trim())).ok_or(SF6FrameDataError::UnknownMove);
+        };
+        self.moves.iter().find(|m| m.identifier.eq_ignore_ascii_case(identifier))
+            .or_else(|| self.moves.iter().find(|m| matches_any_strength(&m.identifier.to_ascii_uppercase(), identifier)))
+            .or_else(|| self.moves.iter().find(|m| m.identifier.to_ascii_uppercase().contains(identifier)))
+            .ok_or(SF6FrameDataError::UnknownMove)
+    }
+}
+
+impl FrameData {
+    /// Finds a move of the character matching `character_query` by one of its community names,
+    /// see [`CharacterFrameData::find_move_by_alias`]
+    pub fn find_move_by_alias(&self, character_query: &str, alias: &str) -> Result<&Move, SF6FrameDataError> {
+        self.find_character_frame_data_query(character_query)?.find_move_by_alias(alias)
+    }
+}
+
+/// Whether `identifier` is `pattern` with a strength (`L`, `M` or `H`) before any of its `P` or
+/// `K` buttons, e.g. `236LP` for `236P`
+fn matches_any_strength(identifier: &str, pattern: &str) -> bool {
+    let mut identifier = identifier.chars().peekable();
+    for button in pattern.chars() {
+        if matches!(button, 'P' | 'K') {
+            identifier.next_if(|c| matches!(c, 'L' | 'M' | 'H'));
+        }
+        if identifier.next() != Some(button) {
+            return false;
+        }
+    }
+    identifier.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn finds_moves_by_alias() {
+        let with_identifier = |identifier: &str| Move {
+            identifier: identifier.to_string(),
+            ..Move::default()
+        };
+        let data = FrameData {
+            character_frame_data: vec![
+                testing::character_frame_data(&character::RYU, ["5LP", "236P", "623LP", "623HP", "[4]6MK", "236236K(SA3)"].map(with_identifier).to_vec()),
+                testing::character_frame_data(&character::ZANGIEF, ["360P", "360K"].map(with_identifier).to_vec()),
+            ],
+        };
+        assert_eq!(data.find_move_by_alias("ryu", "Hadoken").unwrap().identifier, "236P");
+        assert_eq!(data.find_move_by_alias("ryu", "Level-3").unwrap().identifier, "236236K(SA3)");
+        assert_eq!(data.find_move_by_alias("ryu", "CA").unwrap().identifier, "236236K(SA3)");
+        assert_eq!(data.find_move_by_alias("ryu", "shoryuken").unwrap().identifier, "623LP");
+        assert_eq!(data.find_move_by_alias("ryu", "5lp").unwrap().identifier, "5LP");
+        assert_eq!(data.find_move_by_alias("gief", "SPD").unwrap().identifier, "360P");
+        assert!(data.find_move_by_alias("gief", "hadoken").is_err());
+        assert_eq!(resolve_alias(&character::KEN, "dp"), Some("623P"));
+        assert_eq!(resolve_alias(&character::KEN, "DI"), Some("HPHK"));
+        assert!(matches_any_strength("[4]6MK", "[4]6K"));
+        assert!(matches_any_strength("LPLK", "LPLK") && matches_any_strength("6HPHK", "6HPHK"));
+        assert!(!matches_any_strength("236PP", "236P") && !matches_any_strength("236LK", "236P"));
+    }
+}
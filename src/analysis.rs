@@ -0,0 +1,13 @@
+//! Roster-wide analyses built on top of scraped frame data, the tables players put together by
+//! hand for matchup preparation.
+
+pub mod reversals;
+pub mod grabs;
+pub mod mixups;
+pub mod drive_impact;
+pub mod projectiles;
+pub mod advantage;
+pub mod punish;
+pub mod combo;
+pub mod reactions;
+pub mod compare;
@@ -0,0 +1,140 @@
+use std::ops::RangeInclusive;
+
+use crate::character::CharacterId;
+use crate::framedata::{CharacterFrameData, FrameData, Move};
+use crate::parsed::Advantage;
+
+/// The furthest minus a move can be on block and still not be punishable by a 4 frame normal
+pub const SAFE_ON_BLOCK: i32 = -3;
+
+impl Move {
+    /// This move's advantage on block in frames, `None` when the wiki has no number for it
+    pub fn block_frames(&self) -> Option<i32> {
+        Advantage::parse(&self.block_advantage).as_ref().and_then(Advantage::frames)
+    }
+
+    /// This move's advantage on hit in frames, knockdowns included
+    pub fn hit_frames(&self) -> Option<i32> {
+        Advantage::parse(&self.hit_advantage).as_ref().and_then(Advantage::frames)
+    }
+
+    /// This move's advantage once a knocked down opponent gets up, e.g. `38` for `KD +38`, `None`
+    /// when the move doesn't knock down
+    pub fn knockdown_frames(&self) -> Option<i32> {
+        match Advantage::parse(&self.hit_advantage)? {
+            Advantage::Knockdown(frames) => Some(frames),
+            _ => None,
+        }
+    }
+
+    /// [`Move::hit_frames`] for moves that leave the opponent standing
+    fn standing_hit_frames(&self) -> Option<i32> {
+        match Advantage::parse(&self.hit_advantage)? {
+            Advantage::Frames(frames) => Some(frames),
+            _ => None,
+        }
+    }
+}
+
+impl CharacterFrameData {
+    /// This character's moves whose block advantage is within `min..=max`, e.g. `(-3, -1)` for
+    /// moves that are minus but safe
+    pub fn moves_with_block_advantage_between(&self, min: i32, max: i32) -> Vec<&Move> {
+        self.moves_where(Move::block_frames, min..=max)
+    }
+
+    /// This character's moves that are at least `frames` plus on block
+    pub fn moves_plus_at_least(&self, frames: i32) -> Vec<&Move> {
+        self.moves_where(Move::block_frames, frames..=i32::MAX)
+    }
+
+    /// This character's moves whose hit advantage is within `min..=max`. Knockdowns are left out,
+    /// since `KD +30` isn't comparable to being `+30` next to a standing opponent, see
+    /// [`CharacterFrameData::moves_with_knockdown_advantage_between`].
+    pub fn moves_with_hit_advantage_between(&self, min: i32, max: i32) -> Vec<&Move> {
+        self.moves_where(Move::standing_hit_frames, min..=max)
+    }
+
+    /// This character's knockdowns whose advantage once the opponent gets up is within `min..=max`
+    pub fn moves_with_knockdown_advantage_between(&self, min: i32, max: i32) -> Vec<&Move> {
+        self.moves_where(Move::knockdown_frames, min..=max)
+    }
+
+    /// This character's moves that can be punished on block, see [`SAFE_ON_BLOCK`]
+    pub fn punishable_moves(&self) -> Vec<&Move> {
+        self.moves_where(Move::block_frames, i32::MIN..=SAFE_ON_BLOCK - 1)
+    }
+
+    fn moves_where(&self, frames: fn(&Move) -> Option<i32>, range: RangeInclusive<i32>) -> Vec<&Move> {
+        self.moves.iter()
+            .filter(|m| frames(m).is_some_and(|f| range.contains(&f)))
+            .collect()
+    }
+}
+
+impl FrameData {
+    /// Every move across the roster whose block advantage is within `min..=max`
+    pub fn moves_with_block_advantage_between(&self, min: i32, max: i32) -> Vec<(&CharacterId, &Move)> {
+        self.roster_moves(|c| c.moves_with_block_advantage_between(min, max))
+    }
+
+    /// Every move across the roster that is at least `frames` plus on block
+    pub fn moves_plus_at_least(&self, frames: i32) -> Vec<(&CharacterId, &Move)> {
+        self.roster_moves(|c| c.moves_plus_at_least(frames))
+    }
+
+    /// Every move across the roster whose hit advantage is within `min..=max`, knockdowns left out
+    pub fn moves_with_hit_advantage_between(&self, min: i32, max: i32) -> Vec<(&CharacterId, &Move)> {
+        self.roster_moves(|c| c.moves_with_hit_advantage_between(min, max))
+    }
+
+    /// Every knockdown across the roster whose advantage once the opponent gets up is within
+    /// `min..=max`
+    pub fn moves_with_knockdown_advantage_between(&self, min: i32, max: i32) -> Vec<(&CharacterId, &Move)> {
+        self.roster_moves(|c| c.moves_with_knockdown_advantage_between(min, max))
+    }
+
+    fn roster_moves<'a>(&'a self, moves: impl Fn(&'a CharacterFrameData) -> Vec<&'a Move>) -> Vec<(&'a CharacterId, &'a Move)> {
+        self.character_frame_data.iter()
+            .flat_map(|c| moves(c).into_iter().map(move |m| (&c.character_id, m)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn on_block(identifier: &str, block: &str, hit: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            block_advantage: block.to_string(),
+            hit_advantage: hit.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn buckets_by_advantage() {
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            on_block("5MP", "+1", "+5"),
+            on_block("2MK", "-3", "+1"),
+            on_block("623HP", "-30", "KD +30"),
+            on_block("5HP", "+2", "+30"),
+            on_block("SA1", "-", "HKD +22"),
+        ]);
+        let ids = |moves: Vec<&Move>| moves.iter().map(|m| m.identifier.clone()).collect::<Vec<String>>();
+        assert_eq!(ids(ryu.moves_with_block_advantage_between(-4, -1)), vec!["2MK"]);
+        assert_eq!(ids(ryu.moves_plus_at_least(2)), vec!["5HP"]);
+        assert_eq!(ids(ryu.punishable_moves()), vec!["623HP"]);
+        assert_eq!(ids(ryu.moves_with_hit_advantage_between(30, 40)), vec!["5HP"]);
+        assert_eq!(ids(ryu.moves_with_hit_advantage_between(0, 10)), vec!["5MP", "2MK"]);
+        assert_eq!(ids(ryu.moves_with_knockdown_advantage_between(20, 40)), vec!["623HP", "SA1"]);
+
+        let data = FrameData { character_frame_data: vec![ryu] };
+        assert_eq!(data.moves_plus_at_least(1).len(), 2);
+        assert_eq!(data.moves_with_knockdown_advantage_between(30, 30).len(), 1);
+    }
+}
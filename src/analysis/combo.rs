@@ -0,0 +1,179 @@
+use regex::Regex;
+
+use crate::format::signed_number;
+use crate::framedata::Move;
+use crate::LazyLock;
+
+pub mod validation;
+
+pub use validation::{parse_combo, validate_combo, ComboError, ComboProblem, ComboStep, Connection};
+
+/// The damage scaling of each hit of a combo by position, in percent. Every hit after the last
+/// uses the last value.
+pub const HIT_SCALING: [u32; 10] = [100, 100, 80, 70, 60, 50, 40, 30, 20, 10];
+/// The least a hit can be scaled to, whatever the starter or immediate scaling
+pub const MIN_SCALING: u32 = 10;
+/// Counter hits and Punish Counters deal this much (percent) of the starter's damage
+pub const COUNTER_DAMAGE: u32 = 120;
+/// Hits after a Drive Rush starter are scaled down by this much (percent) more
+pub const DRIVE_RUSH_SCALING: u32 = 15;
+
+/// How the first hit of a combo connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Starter {
+    Normal,
+    Counter,
+    PunishCounter,
+    /// Out of a raw Drive Rush, which adds [`DRIVE_RUSH_SCALING`] to every following hit
+    DriveRush,
+}
+
+/// One hit of a [`ComboResult`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboHit {
+    /// The `identifier` of the move the hit belongs to
+    pub identifier: String,
+    /// The hit's damage before scaling
+    pub base: u32,
+    /// The scaling applied, in percent
+    pub scaling: u32,
+    pub damage: u32,
+}
+
+/// The outcome of a combo, see [`combo_damage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboResult {
+    pub hits: Vec<ComboHit>,
+    pub damage: u32,
+    /// The attacker's Drive gauge gain, from each move's `drive_gain`
+    pub drive_gain: i32,
+    /// The attacker's Super gauge gain, from each move's `super_gain_hit`
+    pub super_gain: i32,
+}
+
+static SCALING_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(\d+)%\s*(starter|immediate)").unwrap());
+
+/// Computes the damage of `moves` comboed in order, with SF6's scaling: [`HIT_SCALING`] by hit,
+/// plus the starter and immediate scaling the wiki lists in each move's `damage_scaling`, never
+/// below [`MIN_SCALING`] or a Super Art's minimum (30%, 40% and 50% for levels 1, 2 and 3). Moves
+/// listing several hits in `damage`, e.g. `300,500` or `400x2`, count as that many hits; moves
+/// without a damage value count as one hit dealing nothing.
+pub fn combo_damage(moves: &[&Move], starter: Starter) -> ComboResult {
+    let starter_scaling = moves.first().map(|m| listed_scaling(m, "starter")).unwrap_or_default()
+        + if starter == Starter::DriveRush { DRIVE_RUSH_SCALING } else { 0 };
+    let mut immediate_scaling = 0;
+    let mut hits = Vec::new();
+    for move_data in moves {
+        immediate_scaling += listed_scaling(move_data, "immediate");
+        let minimum = super_minimum(move_data);
+        for base in hit_damage(&move_data.damage) {
+            let position = hits.len();
+            let mut scaling = HIT_SCALING[position.min(HIT_SCALING.len() - 1)];
+            if position > 0 {
+                scaling = scaling.saturating_sub(starter_scaling);
+            }
+            scaling = scaling.saturating_sub(immediate_scaling).max(MIN_SCALING).max(minimum);
+            let mut damage = base * scaling / 100;
+            if position == 0 && matches!(starter, Starter::Counter | Starter::PunishCounter) {
+                damage = damage * COUNTER_DAMAGE / 100;
+            }
+            hits.push(ComboHit {
+                identifier: move_data.identifier.clone(),
+                base,
+                scaling,
+                damage,
+            });
+        }
+    }
+    ComboResult {
+        damage: hits.iter().map(|hit| hit.damage).sum(),
+        hits,
+        drive_gain: moves.iter().filter_map(|m| signed_number(&m.drive_gain)).sum(),
+        super_gain: moves.iter().filter_map(|m| signed_number(&m.super_gain_hit)).sum(),
+    }
+}
+
+/// The damage of each of a move's hits, e.g. `[300, 500]` for `300,500`
+fn hit_damage(damage: &str) -> Vec<u32> {
+    let mut hits = Vec::new();
+    for part in damage.split([',', '+']) {
+        let (value, count) = part.split_once(['x', '×']).unwrap_or((part, "1"));
+        let Some(value) = signed_number(value).and_then(|v| u32::try_from(v).ok()) else {
+            continue;
+        };
+        let count = signed_number(count).and_then(|c| usize::try_from(c).ok()).unwrap_or(1);
+        hits.extend(std::iter::repeat_n(value, count));
+    }
+    if hits.is_empty() {
+        hits.push(0);
+    }
+    hits
+}
+
+/// The starter or immediate scaling listed in a move's `damage_scaling`, e.g. `20% Starter`
+fn listed_scaling(move_data: &Move, kind: &str) -> u32 {
+    SCALING_REGEX.captures_iter(&move_data.damage_scaling)
+        .filter(|caps| caps[2].eq_ignore_ascii_case(kind))
+        .filter_map(|caps| caps[1].parse::<u32>().ok())
+        .sum()
+}
+
+/// The least a Super Art's hits are scaled to, `0` for other moves
+fn super_minimum(move_data: &Move) -> u32 {
+    if !move_data.is_super() {
+        return 0;
+    }
+    match move_data.super_art_level() {
+        Some(3) => 50,
+        Some(2) => 40,
+        _ => 30,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_damage(identifier: &str, damage: &str, scaling: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            damage: damage.to_string(),
+            damage_scaling: scaling.to_string(),
+            drive_gain: String::from("250"),
+            super_gain_hit: String::from("300"),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn scales_combo_damage() {
+        let light = with_damage("2LP", "300", "20% Starter");
+        let medium = with_damage("5MP", "600", "-");
+        let special = with_damage("214K", "400x2", "");
+        let super_art = with_damage("236236K(SA3)", "4000", "");
+
+        let result = combo_damage(&[&medium, &medium, &special], Starter::Normal);
+        assert_eq!(result.hits.iter().map(|h| h.scaling).collect::<Vec<u32>>(), vec![100, 100, 80, 70]);
+        assert_eq!(result.damage, 600 + 600 + 320 + 280);
+        assert_eq!((result.drive_gain, result.super_gain), (750, 900));
+
+        let result = combo_damage(&[&light, &light, &medium], Starter::Normal);
+        assert_eq!(result.hits.iter().map(|h| h.scaling).collect::<Vec<u32>>(), vec![100, 80, 60]);
+
+        let result = combo_damage(&[&medium, &medium, &medium, &medium, &medium, &medium, &medium, &medium, &medium, &super_art], Starter::PunishCounter);
+        assert_eq!(result.hits[0].damage, 720);
+        assert_eq!(result.hits[8].scaling, 20);
+        assert_eq!(result.hits[9].scaling, 50);
+        assert_eq!(super_minimum(&with_damage("236236P (SA2)", "2500", "")), 40);
+        assert_eq!(super_minimum(&with_damage("Cannon Revenge (SA1)", "2000", "")), 30);
+        assert_eq!(super_minimum(&with_damage("214214K (CA)", "4500", "")), 50);
+
+        let drive_rush = combo_damage(&[&medium, &medium], Starter::DriveRush);
+        assert_eq!(drive_rush.hits[1].scaling, 85);
+        assert_eq!(hit_damage("300,500"), vec![300, 500]);
+        assert_eq!(hit_damage("-"), vec![0]);
+    }
+}
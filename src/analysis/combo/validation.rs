@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use regex::Regex;
+
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::fuzzy::normalize_input;
+use crate::parsed::{Advantage, CancelType};
+use crate::tags::MoveCategory;
+use crate::LazyLock;
+
+static CONNECTOR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\s*(,|>|\bxx\b)\s*").unwrap());
+static DRIVE_RUSH_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^drc?\b\s*").unwrap());
+
+/// How a step of a combo follows the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connection {
+    /// After the previous move recovers, written `,`
+    Link,
+    /// Out of the previous move before it recovers, written `>` or `xx`
+    Cancel,
+    /// Out of the previous move into a Drive Rush and then the move, written `DR` or `DRC`
+    DriveRushCancel,
+}
+
+/// One move of a combo, see [`parse_combo`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboStep {
+    /// The move as written, e.g. `2MK` or `cr.mk`
+    pub input: String,
+    /// `None` for the first step
+    pub connection: Option<Connection>,
+}
+
+/// Why a step of a combo can't follow the one before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComboProblem {
+    /// The character has no move matching the step
+    UnknownMove,
+    /// The move's startup is slower than the advantage the previous move leaves
+    LinkTooSlow { startup: i32, advantage: i32 },
+    /// The previous move knocks down without launching, so nothing links after it
+    Knockdown,
+    /// The previous move can't be canceled into this kind of move
+    NotCancelable { required: CancelType },
+    /// The previous move can cancel into Super Arts, but not one of this level
+    SuperArtLevel { level: u8 },
+    /// Nothing can be canceled into this move, like a throw
+    Uncancelable,
+    /// The opponent has been juggled too much for the move to hit them
+    JuggleLimit { count: i32, limit: i32 },
+}
+
+impl Display for ComboProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComboProblem::UnknownMove => write!(f, "no such move"),
+            ComboProblem::LinkTooSlow { startup, advantage } => write!(f, "{} frames of startup don't link from {:+} on hit", startup, advantage),
+            ComboProblem::Knockdown => write!(f, "the previous move knocks down"),
+            ComboProblem::NotCancelable { required } => write!(f, "the previous move has no {} cancel", required.name()),
+            ComboProblem::SuperArtLevel { level } => write!(f, "the previous move has no level {} Super Art cancel", level),
+            ComboProblem::Uncancelable => write!(f, "nothing cancels into it"),
+            ComboProblem::JuggleLimit { count, limit } => write!(f, "juggle count {} is over its limit of {}", count, limit),
+        }
+    }
+}
+
+/// The first impossible step of a combo, see [`validate_combo`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboError {
+    /// Index of the step, `0` for the first
+    pub step: usize,
+    pub input: String,
+    pub problem: ComboProblem,
+}
+
+impl Display for ComboError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Step {} ({}): {}", self.step + 1, self.input, self.problem)
+    }
+}
+
+impl Error for ComboError {}
+
+/// Reads combo notation like `2MK > DR 5HP, 5HP xx 236HP` into steps. `,` links, `>` and `xx`
+/// cancel, and `DR` or `DRC` before a move Drive Rush cancels into it. `~` is left alone, as it's
+/// part of target combo identifiers like `5MP~5HP`.
+pub fn parse_combo(notation: &str) -> Vec<ComboStep> {
+    let mut steps = Vec::new();
+    let mut connection = None;
+    let mut drive_rush = false;
+    let mut rest = notation.trim();
+    loop {
+        let (segment, next) = match CONNECTOR_REGEX.captures(rest) {
+            Some(caps) => {
+                let whole = caps.get(0).unwrap();
+                let link = &caps[1] == ",";
+                (&rest[..whole.start()], Some((link, &rest[whole.end()..])))
+            }
+            None => (rest, None),
+        };
+        let input = DRIVE_RUSH_REGEX.replace(segment.trim(), "");
+        drive_rush |= input.len() < segment.trim().len();
+        if !input.is_empty() {
+            steps.push(ComboStep {
+                input: input.to_string(),
+                connection: match connection {
+                    _ if steps.is_empty() => None,
+                    _ if drive_rush => Some(Connection::DriveRushCancel),
+                    connection => connection,
+                },
+            });
+            drive_rush = false;
+        }
+        let Some((link, next)) = next else {
+            return steps;
+        };
+        connection = Some(if link { Connection::Link } else { Connection::Cancel });
+        rest = next;
+    }
+}
+
+/// Checks that each step of a combo can follow the one before it, returning the moves in order,
+/// ready for [`combo_damage`](super::combo_damage), or the first step that can't:
+/// - a link needs startup no slower than the previous move's advantage on hit
+/// - a cancel needs the previous move's `cancel` to list the kind of move canceled into, chains
+///   for normals, specials for specials and supers for Super Arts
+/// - a Drive Rush cancel needs a move that can cancel into specials, and startup no slower than
+///   its `dr_cancel_hit` advantage
+/// - once a move with a `juggle_start` knocks the opponent down, every hit after it has to be
+///   within its `juggle_limit`, each adding its `juggle_increase`
+///
+/// Checks the wiki has no numbers for are skipped, so a combo is only rejected on data that
+/// rules it out.
+pub fn validate_combo<'a>(character: &'a CharacterFrameData, steps: &[ComboStep]) -> Result<Vec<&'a Move>, ComboError> {
+    let mut moves: Vec<&Move> = Vec::new();
+    let mut juggle: Option<i32> = None;
+    for (step, combo_step) in steps.iter().enumerate() {
+        let error = |problem| ComboError { step, input: combo_step.input.clone(), problem };
+        let move_data = find_move(character, &combo_step.input).ok_or_else(|| error(ComboProblem::UnknownMove))?;
+        if let (Some(previous), Some(connection)) = (moves.last(), combo_step.connection) {
+            check_connection(previous, move_data, connection, juggle.is_some()).map_err(error)?;
+        }
+        juggle = match juggle {
+            Some(count) => {
+                if let Some(limit) = signed_number(&move_data.juggle_limit).filter(|limit| count > *limit) {
+                    return Err(error(ComboProblem::JuggleLimit { count, limit }));
+                }
+                Some(count + signed_number(&move_data.juggle_increase).unwrap_or_default())
+            }
+            None if launches(move_data) => signed_number(&move_data.juggle_start),
+            None => None,
+        };
+        moves.push(move_data);
+    }
+    Ok(moves)
+}
+
+pub(crate) fn find_move<'a>(character: &'a CharacterFrameData, input: &str) -> Option<&'a Move> {
+    character.find_move_by_alias(input).ok()
+        .or_else(|| character.find_move_by_alias(&normalize_input(input)?).ok())
+}
+
+fn check_connection(previous: &Move, next: &Move, connection: Connection, juggling: bool) -> Result<(), ComboProblem> {
+    let startup = signed_number(&next.startup).filter(|startup| *startup > 0);
+    let cancels = previous.cancel_options;
+    match connection {
+        Connection::Link if juggling => Ok(()),
+        Connection::Link => match (Advantage::parse(&previous.hit_advantage), startup) {
+            (Some(Advantage::Knockdown(_)), _) => Err(ComboProblem::Knockdown),
+            (Some(Advantage::Frames(advantage)), Some(startup)) if startup > advantage => Err(ComboProblem::LinkTooSlow { startup, advantage }),
+            _ => Ok(()),
+        },
+        Connection::Cancel => {
+            let required = match next.category() {
+                MoveCategory::Normal | MoveCategory::CommandNormal => CancelType::Chain,
+                MoveCategory::TargetCombo => CancelType::TargetCombo,
+                MoveCategory::Special | MoveCategory::OverDriveSpecial => CancelType::Special,
+                MoveCategory::SuperArt => CancelType::Super,
+                MoveCategory::Throw | MoveCategory::DriveSystem | MoveCategory::Taunt => return Err(ComboProblem::Uncancelable),
+            };
+            // Specials that cancel into supers are often only marked as cancelable into specials
+            if required == CancelType::Super && previous.category() == MoveCategory::Special && cancels.special {
+                return Ok(());
+            }
+            match next.super_art_level() {
+                Some(level) if required == CancelType::Super && cancels.contains(required) && !cancels.allows_super(level) => Err(ComboProblem::SuperArtLevel { level }),
+                _ if cancels.contains(required) => Ok(()),
+                _ => Err(ComboProblem::NotCancelable { required }),
+            }
+        }
+        Connection::DriveRushCancel => {
+            if !cancels.special && !cancels.drive_rush {
+                return Err(ComboProblem::NotCancelable { required: CancelType::DriveRush });
+            }
+            match (signed_number(&previous.dr_cancel_hit), startup) {
+                (Some(advantage), Some(startup)) if !juggling && startup > advantage => Err(ComboProblem::LinkTooSlow { startup, advantage }),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+/// Whether a move puts the opponent in a juggle state on hit, rather than just knocking them down
+fn launches(move_data: &Move) -> bool {
+    matches!(Advantage::parse(&move_data.hit_advantage), Some(Advantage::Knockdown(_))) && signed_number(&move_data.juggle_start).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::parsed::CancelOptions;
+    use crate::testing;
+
+    fn with_frames(identifier: &str, startup: &str, hit: &str, cancel: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            hit_advantage: hit.to_string(),
+            cancel: cancel.to_string(),
+            cancel_options: CancelOptions::parse(cancel),
+            dr_cancel_hit: String::from("+12"),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn validates_combos() {
+        let steps = parse_combo("2MK > DR 5HP, 5HP xx 236HP");
+        assert_eq!(steps.iter().map(|s| (s.input.as_str(), s.connection)).collect::<Vec<_>>(), vec![
+            ("2MK", None),
+            ("5HP", Some(Connection::DriveRushCancel)),
+            ("5HP", Some(Connection::Link)),
+            ("236HP", Some(Connection::Cancel)),
+        ]);
+        assert_eq!(parse_combo("2LP, DRC 5HP")[1].connection, Some(Connection::DriveRushCancel));
+
+        let launcher = Move { juggle_start: String::from("1"), juggle_increase: String::from("2"), ..with_frames("236HK", "20", "KD +30", "SA") };
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            with_frames("2MK", "8", "+1", "Sp SA"),
+            with_frames("5HP", "10", "+4", "Sp SA"),
+            with_frames("2LP", "4", "+4", "Chn Sp SA"),
+            with_frames("2HK", "8", "KD +30", "-"),
+            with_frames("236HP", "14", "-2", "SA"),
+            launcher.clone(),
+            Move { juggle_limit: String::from("2"), ..with_frames("623HP", "6", "KD +20", "SA") },
+            with_frames("5HK", "12", "+2", "SA3"),
+            with_frames("SA1", "7", "KD +20", "-"),
+            with_frames("SA3", "10", "KD +30", "-"),
+        ]);
+        let moves = validate_combo(&ryu, &parse_combo("cr.mk > DR 5HP xx 236HP")).unwrap();
+        assert_eq!(moves.iter().map(|m| m.identifier.as_str()).collect::<Vec<&str>>(), vec!["2MK", "5HP", "236HP"]);
+
+        let error = validate_combo(&ryu, &parse_combo("2LP, 2LP, 5HP")).unwrap_err();
+        assert_eq!((error.step, error.problem.clone()), (2, ComboProblem::LinkTooSlow { startup: 10, advantage: 4 }));
+        assert_eq!(error.to_string(), "Step 3 (5HP): 10 frames of startup don't link from +4 on hit");
+        assert_eq!(validate_combo(&ryu, &parse_combo("2HK, 2LP")).unwrap_err().problem, ComboProblem::Knockdown);
+        assert_eq!(validate_combo(&ryu, &parse_combo("2MK > 5HP")).unwrap_err().problem, ComboProblem::NotCancelable { required: CancelType::Chain });
+        assert_eq!(validate_combo(&ryu, &parse_combo("2MK > 5LP")).unwrap_err().problem, ComboProblem::UnknownMove);
+        assert!(validate_combo(&ryu, &parse_combo("236HK, 623HP")).is_ok());
+        assert_eq!(validate_combo(&ryu, &parse_combo("5HK xx SA3")).map(|m| m.len()), Ok(2));
+        let error = validate_combo(&ryu, &parse_combo("5HK xx SA1")).unwrap_err();
+        assert_eq!(error.problem, ComboProblem::SuperArtLevel { level: 1 });
+        assert_eq!(error.to_string(), "Step 2 (SA1): the previous move has no level 1 Super Art cancel");
+        assert_eq!(
+            validate_combo(&ryu, &parse_combo("236HK, 236HK, 623HP")).unwrap_err().problem,
+            ComboProblem::JuggleLimit { count: 3, limit: 2 },
+        );
+    }
+}
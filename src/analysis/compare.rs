@@ -0,0 +1,151 @@
+use crate::analysis::combo::{combo_damage, Starter};
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::tags::MoveCategory;
+
+/// The startup of the fastest buttons in the game, which few characters have
+pub const FASTEST_BUTTON: i32 = 3;
+
+/// One side's numbers in a [`CharacterComparison`], `None` where the wiki has no value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterSummary {
+    pub character_id: CharacterId,
+    pub vitality: Option<f64>,
+    pub forward_walk_speed: Option<f64>,
+    /// Total frames of a forward dash
+    pub forward_dash: Option<f64>,
+    /// The identifier and startup of the fastest normal
+    pub fastest_button: Option<(String, i32)>,
+    /// Whether any normal has [`FASTEST_BUTTON`] startup or better
+    pub has_3f_button: bool,
+    /// The identifier and damage of the hardest hitting move, Super Arts included
+    pub highest_damage: Option<(String, u32)>,
+    /// The identifier and startup of the fastest reversal that costs no Drive or Super gauge
+    pub meterless_reversal: Option<(String, i32)>,
+}
+
+/// Which character a stat favors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    A,
+    B,
+    Even,
+}
+
+/// A head-to-head report for pick and counterpick tools, see [`compare_characters`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterComparison {
+    pub a: CharacterSummary,
+    pub b: CharacterSummary,
+}
+
+impl CharacterComparison {
+    /// Which side each stat favors, by name: `vitality`, `forward_walk_speed`, `forward_dash`,
+    /// `fastest_button`, `highest_damage` and `meterless_reversal`. Stats neither side has a
+    /// value for are left out; a side without one loses to a side with one.
+    pub fn edges(&self) -> Vec<(&'static str, Edge)> {
+        let (a, b) = (&self.a, &self.b);
+        let startup = |value: &Option<(String, i32)>| value.as_ref().map(|(_, startup)| -(*startup as f64));
+        let stats = [
+            ("vitality", a.vitality, b.vitality),
+            ("forward_walk_speed", a.forward_walk_speed, b.forward_walk_speed),
+            ("forward_dash", a.forward_dash.map(|f| -f), b.forward_dash.map(|f| -f)),
+            ("fastest_button", startup(&a.fastest_button), startup(&b.fastest_button)),
+            ("highest_damage", a.highest_damage.as_ref().map(|(_, d)| *d as f64), b.highest_damage.as_ref().map(|(_, d)| *d as f64)),
+            ("meterless_reversal", startup(&a.meterless_reversal), startup(&b.meterless_reversal)),
+        ];
+        stats.into_iter()
+            .filter_map(|(stat, a, b)| {
+                let edge = match (a, b) {
+                    (None, None) => return None,
+                    (Some(_), None) => Edge::A,
+                    (None, Some(_)) => Edge::B,
+                    (Some(a), Some(b)) if a > b => Edge::A,
+                    (Some(a), Some(b)) if a < b => Edge::B,
+                    _ => Edge::Even,
+                };
+                Some((stat, edge))
+            })
+            .collect()
+    }
+}
+
+/// Compares two characters' speed, damage, meterless reversals and fastest buttons
+pub fn compare_characters(a: &CharacterFrameData, b: &CharacterFrameData) -> CharacterComparison {
+    CharacterComparison { a: summarize(a), b: summarize(b) }
+}
+
+fn summarize(character: &CharacterFrameData) -> CharacterSummary {
+    let stat = |field: &str| character.stats.as_ref().and_then(|stats| stats.number(field));
+    let startup = |m: &Move| signed_number(&m.startup).filter(|startup| *startup > 0);
+    let fastest_button = character.moves.iter()
+        .filter(|m| m.category().is_normal())
+        .filter_map(|m| Some((m.identifier.clone(), startup(m)?)))
+        .min_by_key(|(_, startup)| *startup);
+    let highest_damage = character.moves.iter()
+        .map(|m| (m.identifier.clone(), combo_damage(&[m], Starter::Normal).damage))
+        .filter(|(_, damage)| *damage > 0)
+        .max_by_key(|(_, damage)| *damage);
+    let meterless_reversal = character.moves.iter()
+        .filter(|m| m.is_reversal() && !m.is_super())
+        .filter(|m| !matches!(m.category(), MoveCategory::OverDriveSpecial | MoveCategory::SuperArt | MoveCategory::DriveSystem))
+        .filter_map(|m| Some((m.identifier.clone(), startup(m)?)))
+        .min_by_key(|(_, startup)| *startup);
+    CharacterSummary {
+        character_id: character.character_id.clone(),
+        vitality: stat("vitality"),
+        forward_walk_speed: stat("forward_walk_speed"),
+        forward_dash: stat("forward_dash"),
+        has_3f_button: fastest_button.as_ref().is_some_and(|(_, startup)| *startup <= FASTEST_BUTTON),
+        fastest_button,
+        highest_damage,
+        meterless_reversal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::CharacterStats;
+    use crate::testing;
+
+    fn with(identifier: &str, startup: &str, damage: &str, invuln: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            damage: damage.to_string(),
+            invuln: invuln.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn compares_characters() {
+        let stats = |vitality: &str| Some(CharacterStats { vitality: vitality.to_string(), forward_dash: String::from("19"), ..Default::default() });
+        let mut ryu = testing::character_frame_data(&character::RYU, vec![
+            with("5LP", "4", "300", ""),
+            with("623HP", "6", "1200", "1-9 Strike & Throw"),
+            with("236236P(SA2)", "7", "3000", "1-8 Full"),
+        ]);
+        ryu.stats = stats("10000");
+        let mut ken = testing::character_frame_data(&character::KEN, vec![with("2LP", "3", "200", ""), with("236236K(SA3)", "10", "4000", "1-10 Full")]);
+        ken.stats = stats("10000");
+        let comparison = compare_characters(&ryu, &ken);
+        assert_eq!(comparison.a.meterless_reversal, Some((String::from("623HP"), 6)));
+        assert_eq!(comparison.b.meterless_reversal, None);
+        assert!(!comparison.a.has_3f_button && comparison.b.has_3f_button);
+        assert_eq!(comparison.b.highest_damage, Some((String::from("236236K(SA3)"), 4000)));
+        assert_eq!(comparison.edges(), vec![
+            ("vitality", Edge::Even),
+            ("forward_dash", Edge::Even),
+            ("fastest_button", Edge::B),
+            ("highest_damage", Edge::B),
+            ("meterless_reversal", Edge::A),
+        ]);
+    }
+}
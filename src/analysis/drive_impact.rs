@@ -0,0 +1,116 @@
+use crate::framedata::{is_placeholder, CharacterFrameData, Move};
+
+/// Why a move beats or trades favorably with Drive Impact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CounterDiReason {
+    /// The move's notes say it breaks armor
+    ArmorBreak,
+    /// The move hits more than once, breaking Drive Impact's armor before it becomes active
+    MultiHit,
+    /// A Super Art, which beats Drive Impact through invincibility or armor break
+    Super,
+    /// Invincible from frame 1, see [`Move::is_reversal`]
+    Reversal,
+}
+
+impl Move {
+    /// Every reason this move beats Drive Impact, empty if it doesn't
+    pub fn counter_di_reasons(&self) -> Vec<CounterDiReason> {
+        let notes = self.notes.to_ascii_lowercase();
+        let mut reasons = Vec::new();
+        if notes.contains("armor break") || self.armor.to_ascii_lowercase().contains("break") {
+            reasons.push(CounterDiReason::ArmorBreak);
+        }
+        if self.is_multi_hit() {
+            reasons.push(CounterDiReason::MultiHit);
+        }
+        if self.is_super() {
+            reasons.push(CounterDiReason::Super);
+        }
+        if self.is_reversal() {
+            reasons.push(CounterDiReason::Reversal);
+        }
+        reasons
+    }
+
+    fn is_multi_hit(&self) -> bool {
+        let damage = self.damage.trim();
+        !is_placeholder(damage) && (damage.contains('x') || damage.contains('*') || damage.contains(','))
+            || self.notes.to_ascii_lowercase().contains("multi-hit")
+    }
+
+    /// Whether this move is a Super Art
+    pub(crate) fn is_super(&self) -> bool {
+        let input = self.input.replace(' ', "");
+        input.contains("236236") || input.contains("214214")
+            || self.identifier_tokens().any(|token| matches!(token.as_str(), "SA" | "SA1" | "SA2" | "SA3" | "CA"))
+    }
+
+    /// The Super Art level from the identifier's `SA1`-`SA3` label, a Critical Art (`CA`)
+    /// counting as level 3
+    pub(crate) fn super_art_level(&self) -> Option<u8> {
+        self.identifier_tokens().find_map(|token| match token.as_str() {
+            "SA1" => Some(1),
+            "SA2" => Some(2),
+            "SA3" | "CA" => Some(3),
+            _ => None,
+        })
+    }
+
+    /// The uppercase words of the identifier, e.g. `236236P` and `SA1` for `236236P (SA1)`, so
+    /// labels are matched whole instead of inside names like `CASTLE`
+    pub(crate) fn identifier_tokens(&self) -> impl Iterator<Item = String> + '_ {
+        self.identifier.split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_ascii_uppercase)
+    }
+}
+
+impl CharacterFrameData {
+    /// This character's moves that beat or trade favorably with Drive Impact, with the reasons
+    /// why, as a ready-made matchup sheet query
+    pub fn counter_di_options(&self) -> Vec<(&Move, Vec<CounterDiReason>)> {
+        self.moves.iter()
+            .map(|m| (m, m.counter_di_reasons()))
+            .filter(|(_, reasons)| !reasons.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_counter_di_reasons() {
+        let tatsu = Move {
+            input: String::from("214K"),
+            damage: String::from("300x2"),
+            ..Move::default()
+        };
+        let super_art = Move {
+            input: String::from("236236P"),
+            invuln: String::from("1-9 Full"),
+            ..Move::default()
+        };
+        let jab = Move {
+            input: String::from("5LP"),
+            damage: String::from("300"),
+            ..Move::default()
+        };
+        assert_eq!(tatsu.counter_di_reasons(), vec![CounterDiReason::MultiHit]);
+        assert_eq!(super_art.counter_di_reasons(), vec![CounterDiReason::Super, CounterDiReason::Reversal]);
+        assert!(jab.counter_di_reasons().is_empty());
+    }
+
+    #[test]
+    fn recognises_supers_by_label() {
+        let with = |identifier: &str| Move { identifier: identifier.to_string(), ..Move::default() };
+        assert!(with("SA2").is_super());
+        assert!(with("214214K (CA)").is_super());
+        assert!(!with("Sakura Rush").is_super());
+        assert!(!with("5HP(Hold)").is_super());
+        assert!(!with("Cannon Spike (CASTLE)").is_super());
+    }
+}
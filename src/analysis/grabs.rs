@@ -0,0 +1,78 @@
+use regex::Regex;
+
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{FrameData, Move};
+use crate::tags::Tag;
+use crate::LazyLock;
+
+static RANGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)range\D{0,12}?(\d+(?:\.\d+)?)").unwrap());
+
+/// One row of the roster-wide command grab table, see [`FrameData::command_grabs`]
+#[derive(Debug, Clone)]
+pub struct CommandGrab<'a> {
+    pub character_id: &'a CharacterId,
+    pub move_data: &'a Move,
+    pub startup: Option<i32>,
+    pub damage: Option<i32>,
+    /// The grab's range when the wiki's notes mention it
+    pub range: Option<f32>,
+}
+
+impl Move {
+    /// Whether this move is an unblockable grab other than the universal `LPLK` throws
+    pub fn is_command_grab(&self) -> bool {
+        self.has_tag(Tag::Throw) && !self.identifier.to_ascii_uppercase().contains("LPLK")
+    }
+}
+
+impl FrameData {
+    /// Every command grab across the roster, fastest first, for grappler matchup study
+    pub fn command_grabs(&self) -> Vec<CommandGrab<'_>> {
+        let mut grabs = self.character_frame_data.iter()
+            .flat_map(|c| c.moves.iter().map(move |m| (&c.character_id, m)))
+            .filter(|(_, m)| m.is_command_grab())
+            .map(|(character_id, move_data)| CommandGrab {
+                character_id,
+                move_data,
+                startup: signed_number(&move_data.startup),
+                damage: signed_number(&move_data.damage),
+                range: RANGE_REGEX.captures(&move_data.notes)
+                    .and_then(|caps| caps.get(1))
+                    .and_then(|m| m.as_str().parse().ok()),
+            })
+            .collect::<Vec<CommandGrab>>();
+        grabs.sort_by(|a, b| (a.startup.unwrap_or(i32::MAX), &a.character_id.id).cmp(&(b.startup.unwrap_or(i32::MAX), &b.character_id.id)));
+        grabs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn excludes_universal_throws() {
+        let spd = Move {
+            identifier: String::from("360P"),
+            guard: String::from("T"),
+            startup: String::from("5"),
+            notes: String::from("Throw range 1.2; whiffs on airborne opponents"),
+            ..Move::default()
+        };
+        let throw = Move {
+            identifier: String::from("LPLK"),
+            guard: String::from("T"),
+            ..Move::default()
+        };
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::ZANGIEF, vec![spd, throw])],
+        };
+        let grabs = data.command_grabs();
+        assert_eq!(grabs.len(), 1);
+        assert_eq!(grabs[0].startup, Some(5));
+        assert_eq!(grabs[0].range, Some(1.2));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::tags::Tag;
+
+/// Which way a mixup tool has to be blocked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MixupKind {
+    Overhead,
+    Low,
+}
+
+/// One of a character's overheads or lows, see [`CharacterFrameData::mixup_tools`]
+#[derive(Debug, Clone)]
+pub struct MixupTool<'a> {
+    pub move_data: &'a Move,
+    pub kind: MixupKind,
+    pub startup: Option<i32>,
+    pub on_hit: Option<i32>,
+    pub on_block: Option<i32>,
+}
+
+impl CharacterFrameData {
+    /// This character's overheads and lows, fastest first, for generating reaction drills
+    pub fn mixup_tools(&self) -> Vec<MixupTool<'_>> {
+        let mut tools = self.moves.iter()
+            .filter_map(|m| {
+                let kind = if m.has_tag(Tag::Overhead) {
+                    MixupKind::Overhead
+                } else if m.has_tag(Tag::Low) {
+                    MixupKind::Low
+                } else {
+                    return None;
+                };
+                Some(MixupTool {
+                    move_data: m,
+                    kind,
+                    startup: signed_number(&m.startup),
+                    on_hit: signed_number(&m.hit_advantage),
+                    on_block: signed_number(&m.block_advantage),
+                })
+            })
+            .collect::<Vec<MixupTool>>();
+        tools.sort_by_key(|t| t.startup.unwrap_or(i32::MAX));
+        tools
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn lists_overheads_and_lows() {
+        let overhead = Move {
+            identifier: String::from("6MP"),
+            guard: String::from("H"),
+            startup: String::from("20"),
+            hit_advantage: String::from("+1"),
+            block_advantage: String::from("-2"),
+            ..Move::default()
+        };
+        let low = Move {
+            identifier: String::from("2MK"),
+            guard: String::from("L"),
+            startup: String::from("8"),
+            ..Move::default()
+        };
+        let mid = Move {
+            identifier: String::from("5MP"),
+            guard: String::from("LH"),
+            ..Move::default()
+        };
+        let data = testing::character_frame_data(&character::RYU, vec![overhead, low, mid]);
+        let tools = data.mixup_tools();
+        assert_eq!(tools.iter().map(|t| t.kind).collect::<Vec<_>>(), vec![MixupKind::Low, MixupKind::Overhead]);
+        assert_eq!(tools[1].on_block, Some(-2));
+    }
+}
@@ -0,0 +1,81 @@
+use regex::Regex;
+
+use crate::framedata::Move;
+use crate::tags::Tag;
+use crate::LazyLock;
+
+static DURABILITY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(?:durability|projectile strength)\D{0,12}?(\d+)").unwrap());
+
+/// How many projectile hits a fireball absorbs before disappearing. Supers beat everything short of
+/// another super.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Durability(pub u8);
+
+impl Durability {
+    /// Level 1/2/3 Super Art projectiles
+    pub const SUPER: Durability = Durability(5);
+
+    /// The durability the notes state, otherwise the usual level for the move's kind: supers beat
+    /// everything, EX (double button) projectiles take two hits, anything else one. `None` when the
+    /// move isn't a projectile.
+    pub fn of(move_data: &Move) -> Option<Durability> {
+        if !move_data.has_tag(Tag::Projectile) {
+            return None;
+        }
+        if let Some(stated) = DURABILITY_REGEX.captures(&move_data.notes)
+            .and_then(|caps| caps.get(1))
+            .and_then(|m| m.as_str().parse().ok()) {
+            return Some(Durability(stated));
+        }
+        let input = move_data.input.to_ascii_uppercase();
+        Some(if move_data.is_super() {
+            Durability::SUPER
+        } else if input.ends_with("PP") || input.ends_with("KK") {
+            Durability(2)
+        } else {
+            Durability(1)
+        })
+    }
+}
+
+/// The result of two projectiles meeting, see [`projectile_vs_projectile`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClashOutcome {
+    /// The first projectile passes through with this much durability left
+    FirstWins(Durability),
+    /// The second projectile passes through with this much durability left
+    SecondWins(Durability),
+    /// Both projectiles disappear
+    Cancel,
+}
+
+/// Resolves a fireball clash between `a` and `b`. `None` if either move isn't a projectile.
+pub fn projectile_vs_projectile(a: &Move, b: &Move) -> Option<ClashOutcome> {
+    let (Durability(a), Durability(b)) = (Durability::of(a)?, Durability::of(b)?);
+    Some(match a.cmp(&b) {
+        std::cmp::Ordering::Greater => ClashOutcome::FirstWins(Durability(a - b)),
+        std::cmp::Ordering::Less => ClashOutcome::SecondWins(Durability(b - a)),
+        std::cmp::Ordering::Equal => ClashOutcome::Cancel,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_clashes() {
+        let fireball = |input: &str| Move {
+            input: String::from(input),
+            projectile_speed: String::from("0.05"),
+            ..Move::default()
+        };
+        let (hadoken, ex_hadoken, shinku) = (fireball("236P"), fireball("236PP"), fireball("236236P"));
+        assert_eq!(projectile_vs_projectile(&hadoken, &hadoken), Some(ClashOutcome::Cancel));
+        assert_eq!(projectile_vs_projectile(&ex_hadoken, &hadoken), Some(ClashOutcome::FirstWins(Durability(1))));
+        assert_eq!(projectile_vs_projectile(&hadoken, &shinku), Some(ClashOutcome::SecondWins(Durability(4))));
+        assert_eq!(projectile_vs_projectile(&hadoken, &Move::default()), None);
+    }
+}
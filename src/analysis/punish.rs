@@ -0,0 +1,132 @@
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::parsed::Advantage;
+use crate::tags::MoveCategory;
+
+/// The fewest frames a Drive Rush takes before a normal can come out of it
+pub const DRIVE_RUSH_FRAMES: i32 = 11;
+
+/// Roughly how far apart the characters are once the attacker's move is blocked. The wiki has no
+/// range data, so this only rules out the kinds of moves that don't reach: throws outside
+/// [`Range::Close`], light normals at [`Range::Mid`] and every normal without a Drive Rush at
+/// [`Range::Far`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Range {
+    Close,
+    Mid,
+    Far,
+}
+
+/// How a punish is performed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PunishMethod {
+    /// The move on its own
+    Raw,
+    /// A Drive Rush into the move, for normals that don't reach on their own
+    DriveRush,
+}
+
+/// A move that punishes a blocked move, see [`find_punishes`]
+#[derive(Debug, Clone)]
+pub struct PunishOption<'a> {
+    pub move_data: &'a Move,
+    pub method: PunishMethod,
+    /// Frames until the punish hits, including the Drive Rush if there is one
+    pub startup: i32,
+    /// Frames to spare, `0` for a frame-perfect punish
+    pub margin: i32,
+    /// The move's [`Move::punish_advantage`], parsed
+    pub punish_advantage: Option<Advantage>,
+}
+
+/// The moves of `defender` that punish `attacker_move` on block, fastest first. A move punishes
+/// when its startup is no more than how minus `attacker_move` is on block. Normals that don't
+/// reach at `range_hint` are offered through a Drive Rush instead if it still gets there in time.
+/// Nothing is returned for moves without a numeric block advantage or that aren't minus.
+pub fn find_punishes<'a>(attacker_move: &Move, defender: &'a CharacterFrameData, range_hint: Option<Range>) -> Vec<PunishOption<'a>> {
+    let Some(gap) = attacker_move.block_frames().map(|frames| -frames).filter(|gap| *gap > 0) else {
+        return Vec::new();
+    };
+    let range = range_hint.unwrap_or(Range::Close);
+    let mut punishes = defender.moves.iter()
+        .filter_map(|move_data| {
+            let startup = signed_number(&move_data.startup).filter(|startup| *startup > 0)?;
+            let category = move_data.category();
+            let method = match category {
+                MoveCategory::Throw if range != Range::Close => return None,
+                _ if category.is_normal() && (range == Range::Far || (range == Range::Mid && is_light(move_data))) => PunishMethod::DriveRush,
+                _ => PunishMethod::Raw,
+            };
+            let startup = match method {
+                PunishMethod::Raw => startup,
+                PunishMethod::DriveRush => startup + DRIVE_RUSH_FRAMES,
+            };
+            (startup <= gap).then(|| PunishOption {
+                move_data,
+                method,
+                startup,
+                margin: gap - startup,
+                punish_advantage: Advantage::parse(&move_data.punish_advantage),
+            })
+        })
+        .collect::<Vec<PunishOption>>();
+    punishes.sort_by_key(|p| (p.startup, p.method));
+    punishes
+}
+
+fn is_light(move_data: &Move) -> bool {
+    let identifier = move_data.identifier.to_ascii_uppercase();
+    identifier.ends_with("LP") || identifier.ends_with("LK")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with_startup(identifier: &str, startup: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            punish_advantage: String::from("+8"),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn finds_punishes() {
+        let ken = testing::character_frame_data(&character::KEN, vec![with_startup("5LP", "4"), with_startup("5HP", "10"), with_startup("623HP", "5"), with_startup("2HK", "9")]);
+        let dp = Move { block_advantage: String::from("-30"), ..Move::default() };
+        let punishes = find_punishes(&dp, &ken, None);
+        let summary = punishes.iter().map(|p| (p.move_data.identifier.as_str(), p.method, p.margin)).collect::<Vec<_>>();
+        assert_eq!(summary, vec![
+            ("5LP", PunishMethod::Raw, 26),
+            ("623HP", PunishMethod::Raw, 25),
+            ("2HK", PunishMethod::Raw, 21),
+            ("5HP", PunishMethod::Raw, 20),
+        ]);
+        assert_eq!(punishes[0].punish_advantage, Some(Advantage::Frames(8)));
+
+        let far = find_punishes(&dp, &ken, Some(Range::Far));
+        assert_eq!(far.iter().map(|p| (p.move_data.identifier.as_str(), p.startup)).collect::<Vec<_>>(), vec![
+            ("623HP", 5), ("5LP", 15), ("2HK", 20), ("5HP", 21),
+        ]);
+
+        let minus_four = Move { block_advantage: String::from("-4"), ..Move::default() };
+        assert_eq!(find_punishes(&minus_four, &ken, None).len(), 1);
+        assert!(find_punishes(&minus_four, &ken, Some(Range::Mid)).is_empty());
+        assert!(find_punishes(&Move { block_advantage: String::from("+2"), ..Move::default() }, &ken, None).is_empty());
+    }
+
+    #[test]
+    fn skips_normals_too_slow_to_punish() {
+        let ken = testing::character_frame_data(&character::KEN, vec![with_startup("5LP", "4"), with_startup("5HP", "10")]);
+        let minus_eight = Move { block_advantage: String::from("-8"), ..Move::default() };
+        let punishes = find_punishes(&minus_eight, &ken, None);
+        assert_eq!(punishes.iter().map(|p| (p.move_data.identifier.as_str(), p.method)).collect::<Vec<_>>(), vec![("5LP", PunishMethod::Raw)]);
+        assert!(find_punishes(&minus_eight, &ken, Some(Range::Far)).is_empty());
+    }
+}
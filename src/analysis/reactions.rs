@@ -0,0 +1,145 @@
+use crate::analysis::combo::validation::{find_move, parse_combo, ComboError, ComboProblem, Connection};
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::tags::{MoveArchetype, Tag};
+
+/// Frames a player watching for something needs to react to it, the slow end of the usual 15 to
+/// 20 so answers hold up mid-match
+pub const REACTION_FRAMES: i32 = 20;
+/// Frames before a jump leaves the ground, during which it can still be hit
+pub const PREJUMP_FRAMES: i32 = 4;
+
+/// A move that can be seen coming, see [`CharacterFrameData::moves_slower_than`]
+#[derive(Debug, Clone)]
+pub struct ReactableMove<'a> {
+    pub move_data: &'a Move,
+    pub startup: i32,
+    /// Frames left to answer the move once reacted to, negative if it can't be reacted to in time
+    pub window: i32,
+}
+
+/// The space between two moves of a blockstring, see [`string_gaps`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringGap {
+    /// Index of the move after the gap
+    pub step: usize,
+    pub identifier: String,
+    /// Frames the defender can act before the move hits, `0` or less for a true blockstring.
+    /// `None` for cancels and where the wiki has no numbers.
+    pub gap: Option<i32>,
+}
+
+impl StringGap {
+    /// Whether the gap is long enough to Drive Impact into on reaction
+    pub fn di_reactable(&self, reaction_frames: i32) -> bool {
+        self.gap.is_some_and(|gap| gap >= reaction_frames)
+    }
+}
+
+impl CharacterFrameData {
+    /// This character's moves with more than `frames` of startup, slowest first, with how much
+    /// time is left after reacting within [`REACTION_FRAMES`]
+    pub fn moves_slower_than(&self, frames: i32) -> Vec<ReactableMove<'_>> {
+        self.reactable(|_| true, frames, REACTION_FRAMES)
+    }
+
+    /// This character's overheads slow enough to block on reaction within `reaction_frames`
+    pub fn reactable_overheads(&self, reaction_frames: i32) -> Vec<ReactableMove<'_>> {
+        self.reactable(|m| m.has_tag(Tag::Overhead), reaction_frames, reaction_frames)
+    }
+
+    /// This character's projectiles slow enough to jump over on reaction within
+    /// `reaction_frames`, allowing for [`PREJUMP_FRAMES`]
+    pub fn jumpable_projectiles(&self, reaction_frames: i32) -> Vec<ReactableMove<'_>> {
+        self.reactable(|m| m.has_tag(Tag::Projectile), reaction_frames + PREJUMP_FRAMES, reaction_frames + PREJUMP_FRAMES)
+    }
+
+    /// This character's Drive Impact, with the frames left to Drive Impact back or Drive Parry
+    /// after reacting within `reaction_frames`. `None` without a Drive Impact with known startup.
+    pub fn drive_impact_window(&self, reaction_frames: i32) -> Option<ReactableMove<'_>> {
+        let move_data = self.moves.iter().find(|m| m.archetype() == Some(MoveArchetype::DriveImpact))?;
+        let startup = signed_number(&move_data.startup).filter(|startup| *startup > 0)?;
+        Some(ReactableMove { move_data, startup, window: startup - reaction_frames })
+    }
+
+    fn reactable(&self, filter: impl Fn(&Move) -> bool, slower_than: i32, reaction_frames: i32) -> Vec<ReactableMove<'_>> {
+        let mut moves = self.moves.iter()
+            .filter(|m| filter(m))
+            .filter_map(|move_data| {
+                let startup = signed_number(&move_data.startup).filter(|startup| *startup > slower_than)?;
+                Some(ReactableMove { move_data, startup, window: startup - reaction_frames })
+            })
+            .collect::<Vec<ReactableMove>>();
+        moves.sort_by_key(|m| -m.startup);
+        moves
+    }
+}
+
+/// The gaps in a blockstring written in combo notation like `5LP, 5LP, 2MK`, see
+/// [`parse_combo`]. A link's gap is how much sooner the defender recovers from blocking the
+/// previous move than the next one hits; a Drive Rush cancel's uses the previous move's
+/// `dr_cancel_block` advantage.
+pub fn string_gaps(character: &CharacterFrameData, notation: &str) -> Result<Vec<StringGap>, ComboError> {
+    let mut gaps = Vec::new();
+    let mut previous: Option<&Move> = None;
+    for (step, combo_step) in parse_combo(notation).into_iter().enumerate() {
+        let Some(move_data) = find_move(character, &combo_step.input) else {
+            return Err(ComboError { step, input: combo_step.input, problem: ComboProblem::UnknownMove });
+        };
+        if let (Some(previous), Some(connection)) = (previous, combo_step.connection) {
+            let advantage = match connection {
+                Connection::Link => previous.block_frames(),
+                Connection::DriveRushCancel => signed_number(&previous.dr_cancel_block),
+                Connection::Cancel => None,
+            };
+            let startup = signed_number(&move_data.startup).filter(|startup| *startup > 0);
+            gaps.push(StringGap {
+                step,
+                identifier: move_data.identifier.clone(),
+                gap: advantage.zip(startup).map(|(advantage, startup)| startup - 1 - advantage),
+            });
+        }
+        previous = Some(move_data);
+    }
+    Ok(gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with(identifier: &str, name: &str, startup: &str, block: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn answers_reaction_questions() {
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            with("5LP", "Standing Light Punch", "4", "-1"),
+            with("2MK", "Crouching Medium Kick", "8", "-3"),
+            Move { guard: String::from("H"), ..with("6MP", "Collarbone Breaker", "20", "-1") },
+            Move { projectile_speed: String::from("Slow"), ..with("236P", "Hadoken", "16", "-6") },
+            Move { projectile_speed: String::from("Slow"), ..with("236P(charged)", "Denjin Hadoken", "28", "+2") },
+            with("6HPHK", "Drive Impact", "26", "-3"),
+        ]);
+        let ids = |moves: Vec<ReactableMove>| moves.iter().map(|m| m.move_data.identifier.clone()).collect::<Vec<String>>();
+        assert_eq!(ids(ryu.moves_slower_than(15)), vec!["236P(charged)", "6HPHK", "6MP", "236P"]);
+        assert_eq!(ids(ryu.reactable_overheads(15)), vec!["6MP"]);
+        assert_eq!(ids(ryu.jumpable_projectiles(REACTION_FRAMES)), vec!["236P(charged)"]);
+        assert_eq!(ryu.drive_impact_window(REACTION_FRAMES).unwrap().window, 6);
+
+        let gaps = string_gaps(&ryu, "5LP, 5LP, 2MK > 236P").unwrap();
+        assert_eq!(gaps.iter().map(|g| g.gap).collect::<Vec<_>>(), vec![Some(4), Some(8), None]);
+        assert!(!gaps[1].di_reactable(REACTION_FRAMES) && gaps[1].di_reactable(8));
+        assert_eq!(string_gaps(&ryu, "5LP, 5HP").unwrap_err().step, 1);
+    }
+}
@@ -0,0 +1,166 @@
+use regex::Regex;
+
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{is_placeholder, FrameData, Move};
+use crate::LazyLock;
+
+/// A frame or range of frames in an `invuln` value, e.g. `1-11`, `1~9` or `4`
+static FRAME_RANGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\d+)(?:\s*[-~]\s*(\d+))?\b").unwrap());
+
+/// What a reversal is invincible to, read from the wiki's `invuln` column
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvulnType {
+    Full,
+    StrikeAndThrow,
+    Strike,
+    Throw,
+    Projectile,
+    Air,
+    Other(String),
+}
+
+impl InvulnType {
+    fn parse(invuln: &str) -> Self {
+        let lower = invuln.to_ascii_lowercase();
+        let strike = lower.contains("strike");
+        let throw = lower.contains("throw");
+        if lower.contains("full") {
+            InvulnType::Full
+        } else if strike && throw {
+            InvulnType::StrikeAndThrow
+        } else if strike {
+            InvulnType::Strike
+        } else if throw {
+            InvulnType::Throw
+        } else if lower.contains("projectile") {
+            InvulnType::Projectile
+        } else if lower.contains("air") {
+            InvulnType::Air
+        } else {
+            InvulnType::Other(invuln.trim().to_string())
+        }
+    }
+}
+
+/// What an [`InvulnWindow`] is invincible to
+pub type InvulnKind = InvulnType;
+
+/// Frames a move is invincible during, both ends included, see [`Move::invuln_windows`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InvulnWindow {
+    pub start: u32,
+    pub end: u32,
+    /// [`InvulnType::Other`] with an empty string when the wiki gives frames without saying
+    /// what they are invincible to
+    pub kind: InvulnKind,
+}
+
+impl InvulnWindow {
+    /// Parses every window of an `invuln` value, in the order listed. The kind may follow the
+    /// frames, as in `1-11 Strike/Throw` or `1-5 Full, 6-10 Throw`, or come first, as in
+    /// `Full 1-9`.
+    pub fn parse_all(invuln: &str) -> Vec<InvulnWindow> {
+        if is_placeholder(invuln) {
+            return Vec::new();
+        }
+        let ranges = FRAME_RANGE_REGEX.captures_iter(invuln).collect::<Vec<_>>();
+        let Some(first) = ranges.first() else {
+            return Vec::new();
+        };
+        let has_letters = |text: &str| text.chars().any(|c| c.is_ascii_alphabetic());
+        let kind_first = has_letters(&invuln[..first.get(0).unwrap().start()]);
+        ranges.iter().enumerate()
+            .filter_map(|(i, range)| {
+                let whole = range.get(0).unwrap();
+                let text = if kind_first {
+                    let previous = i.checked_sub(1).map(|previous| ranges[previous].get(0).unwrap().end());
+                    &invuln[previous.unwrap_or(0)..whole.start()]
+                } else {
+                    &invuln[whole.end()..ranges.get(i + 1).map_or(invuln.len(), |next| next.get(0).unwrap().start())]
+                };
+                let start = range[1].parse::<u32>().ok()?;
+                let end = range.get(2).map_or(Some(start), |end| end.as_str().parse::<u32>().ok())?;
+                let text = text.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+                let kind = if has_letters(text) { InvulnType::parse(text) } else { InvulnType::Other(String::new()) };
+                (start <= end).then_some(InvulnWindow { start, end, kind })
+            })
+            .collect()
+    }
+
+    /// Whether `frame` is inside this window
+    pub fn contains(&self, frame: u32) -> bool {
+        (self.start..=self.end).contains(&frame)
+    }
+}
+
+/// One row of the roster-wide reversal table, see [`FrameData::reversals`]
+#[derive(Debug, Clone)]
+pub struct Reversal<'a> {
+    pub character_id: &'a CharacterId,
+    pub move_data: &'a Move,
+    pub invuln: InvulnType,
+    pub windows: Vec<InvulnWindow>,
+    pub startup: Option<i32>,
+    pub on_block: Option<i32>,
+}
+
+impl Move {
+    /// This move's invincibility windows, see [`InvulnWindow::parse_all`]
+    pub fn invuln_windows(&self) -> Vec<InvulnWindow> {
+        InvulnWindow::parse_all(&self.invuln)
+    }
+
+    /// Whether this move is invincible from its first frame, which is what makes it a reversal
+    pub fn is_reversal(&self) -> bool {
+        let invuln = self.invuln.trim();
+        invuln.starts_with("1-") || invuln.starts_with("1~") || invuln.to_ascii_lowercase().starts_with("full")
+    }
+}
+
+impl FrameData {
+    /// Every move across the roster that is invincible from frame 1, fastest first: the canonical
+    /// "what do I have to respect on wakeup" table
+    pub fn reversals(&self) -> Vec<Reversal<'_>> {
+        let mut reversals = self.character_frame_data.iter()
+            .flat_map(|c| c.moves.iter().map(move |m| (&c.character_id, m)))
+            .filter(|(_, m)| m.is_reversal())
+            .map(|(character_id, move_data)| Reversal {
+                character_id,
+                move_data,
+                invuln: InvulnType::parse(&move_data.invuln),
+                windows: move_data.invuln_windows(),
+                startup: signed_number(&move_data.startup),
+                on_block: signed_number(&move_data.block_advantage),
+            })
+            .collect::<Vec<Reversal>>();
+        reversals.sort_by(|a, b| (a.startup.unwrap_or(i32::MAX), &a.character_id.id).cmp(&(b.startup.unwrap_or(i32::MAX), &b.character_id.id)));
+        reversals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_invuln_types() {
+        assert_eq!(InvulnType::parse("1-9 Full"), InvulnType::Full);
+        assert_eq!(InvulnType::parse("1-5 Strike/Throw"), InvulnType::StrikeAndThrow);
+        assert_eq!(InvulnType::parse("1-14 Projectile"), InvulnType::Projectile);
+        assert_eq!(InvulnType::parse("1-3 Air"), InvulnType::Air);
+    }
+
+    #[test]
+    fn parses_invuln_windows() {
+        let window = |start: u32, end: u32, kind: InvulnKind| InvulnWindow { start, end, kind };
+        assert_eq!(InvulnWindow::parse_all("1-11 Strike/Throw"), vec![window(1, 11, InvulnType::StrikeAndThrow)]);
+        assert_eq!(InvulnWindow::parse_all("Full 1-9"), vec![window(1, 9, InvulnType::Full)]);
+        assert_eq!(InvulnWindow::parse_all("1-5 Full, 6~10 Throw"), vec![window(1, 5, InvulnType::Full), window(6, 10, InvulnType::Throw)]);
+        assert_eq!(InvulnWindow::parse_all("4 Projectile; 1-3"), vec![window(4, 4, InvulnType::Projectile), window(1, 3, InvulnType::Other(String::new()))]);
+        assert!(InvulnWindow::parse_all("-").is_empty());
+        assert!(window(1, 9, InvulnType::Full).contains(9));
+    }
+}
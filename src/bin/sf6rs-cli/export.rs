@@ -0,0 +1,117 @@
+//! `sf6rs-cli export` writes a slice of frame data as JSON for shell pipelines:
+//! ```text
+//! sf6rs-cli export --characters ryu,ken --fields identifier,startup --categories normals > normals.json
+//! ```
+//! Data is scraped unless `--input` points at a JSON snapshot of `FrameData`. `--full` writes the
+//! whole dataset in the versioned form `FrameData::load_json` reads instead of a slice, and
+//! `--format plain|markdown|discord` writes the slice as text instead of JSON, styled by
+//! `--locale`, `--notation` and `--verbosity`. `--art-bundle <file>` writes a zip of the selected
+//! characters' portraits and costumes with a manifest instead of frame data.
+
+use std::error::Error;
+use std::io::Write;
+
+use sf6rs::character::CHARACTERS;
+use sf6rs::export::art_bundle::write_art_bundle;
+use sf6rs::export::slice::Slice;
+use sf6rs::format::context::{FormatContext, Notation, Verbosity};
+use sf6rs::format::formatter::Formatters;
+use sf6rs::framedata::{self, FrameData, Loader};
+
+const USAGE: &str = "usage: sf6rs-cli export [--input <snapshot.json>] [--output <file>] [--characters <list>] [--fields <list>] [--categories <list>] [--compact] [--full]
+    [--format <name>] [--locale <tag>] [--notation numpad|classic] [--verbosity brief|normal|detailed]
+    [--art-bundle <file.zip>]
+Lists are comma separated. Output goes to stdout unless --output is given.";
+
+pub async fn run(args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let formatters = Formatters::default();
+    let mut slice = Slice::default();
+    let mut input = None;
+    let mut output = None;
+    let mut compact = false;
+    let mut full = false;
+    let mut art_bundle = None;
+    let mut formatter = None;
+    let mut context = FormatContext::default();
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value\n{}", flag, USAGE));
+        match flag.as_str() {
+            "--characters" => slice = slice.characters(&value()?)?,
+            "--fields" => {
+                let fields = value()?;
+                slice = slice.fields(&fields)?;
+                context = context.fields(&fields);
+            }
+            "--categories" => slice = slice.categories(&value()?)?,
+            "--locale" => context = context.locale(&value()?),
+            "--notation" => {
+                let name = value()?;
+                context = context.notation(Notation::from_name(&name).ok_or_else(|| format!("unknown notation `{}`", name))?);
+            }
+            "--verbosity" => {
+                let name = value()?;
+                context = context.verbosity(Verbosity::from_name(&name).ok_or_else(|| format!("unknown verbosity `{}`", name))?);
+            }
+            "--input" => input = Some(value()?),
+            "--output" => output = Some(value()?),
+            "--compact" => compact = true,
+            "--full" => full = true,
+            "--art-bundle" => art_bundle = Some(value()?),
+            "--format" => {
+                let name = value()?;
+                let names = formatters.names().collect::<Vec<&str>>().join(", ");
+                formatter = Some(formatters.get(&name).ok_or_else(|| format!("unknown format `{}`, expected one of {}", name, names))?);
+            }
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => return Err(format!("unknown argument `{}`\n{}", flag, USAGE).into()),
+        }
+    }
+
+    if let Some(path) = art_bundle {
+        let characters = slice.characters.unwrap_or_else(|| CHARACTERS.to_vec());
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let manifest = write_art_bundle(&Loader::default(), &characters, file).await?;
+        for url in &manifest.failed {
+            eprintln!("Could not download {}", url);
+        }
+        return Ok(());
+    }
+
+    let frame_data = match input {
+        Some(path) => FrameData::load_json(path)?,
+        None => {
+            let report = framedata::load_all().await;
+            for (character_id, error) in &report.failures {
+                eprintln!("Could not load {}: {}", character_id.id, error);
+            }
+            report.data
+        }
+    };
+    if full {
+        return match output {
+            Some(path) => Ok(frame_data.save_json(path)?),
+            None => Ok(writeln!(std::io::stdout().lock(), "{}", frame_data.to_json())?),
+        };
+    }
+    let mut bytes = match formatter {
+        Some(formatter) => slice.moves(&frame_data).into_iter()
+            .map(|(character_id, moves)| formatter.format_moves(&context, character_id, &moves))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+            .into_bytes(),
+        None => {
+            let json = slice.to_json(&frame_data);
+            if compact { serde_json::to_vec(&json)? } else { serde_json::to_vec_pretty(&json)? }
+        }
+    };
+    bytes.push(b'\n');
+    match output {
+        Some(path) => std::fs::write(path, bytes)?,
+        None => std::io::stdout().lock().write_all(&bytes)?,
+    }
+    Ok(())
+}
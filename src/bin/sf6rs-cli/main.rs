@@ -0,0 +1,266 @@
+//! Scrapes and queries frame data from the command line, for people who want the data without
+//! writing Rust:
+//! ```text
+//! sf6rs-cli scrape --out data.json
+//! sf6rs-cli move ken 236hk
+//! sf6rs-cli moves ken --category normals
+//! sf6rs-cli punish --attacker ryu 5hp --defender cammy
+//! sf6rs-cli publish mirror --version 2024.06.26
+//! sf6rs-cli watch --out overlay
+//! sf6rs-cli export --characters ryu,ken --fields identifier,startup --categories normals
+//! ```
+//! Characters are scraped as they're needed unless `--input` points at a JSON snapshot, like one
+//! written by `sf6rs-cli scrape`. `export` takes its own options, see [`export`].
+
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use sf6rs::analysis::punish::{find_punishes, PunishMethod, Range};
+use sf6rs::character::{self, CharacterId};
+use sf6rs::export::overlay::watch_overlay_files;
+use sf6rs::export::publish::publish;
+use sf6rs::format::context::{FormatContext, Verbosity};
+use sf6rs::framedata::refresh::RefreshProfile;
+use sf6rs::framedata::version::GameVersion;
+use sf6rs::framedata::{self, FrameData, Loader, Move};
+use sf6rs::tags::MoveCategory;
+
+mod export;
+
+const USAGE: &str = "usage: sf6rs-cli [--input <snapshot.json>] <command>
+commands:
+    scrape [--out <file>]                  scrape every character, as JSON
+    move <character> <move>                every field of one move
+    moves <character> [--category <name>]  a table of a character's moves
+    punish --attacker <character> <move> --defender <character> [--range close|mid|far]
+                                           the defender's punishes for a blocked move
+    publish <dir> --version <version>      scrape, validate and write a versioned mirror
+    watch --out <dir> [--interval <minutes>]
+                                           rewrite overlay files after every refresh, every
+                                           12 hours or so unless an interval is given
+    export [<options>]                     a slice of the data as JSON or text, see export --help";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Default)]
+struct Options {
+    input: Option<String>,
+    out: Option<String>,
+    category: Option<MoveCategory>,
+    attacker: Option<(String, String)>,
+    defender: Option<String>,
+    range: Option<Range>,
+    version: Option<String>,
+    interval: Option<u64>,
+    positional: Vec<String>,
+}
+
+fn parse(args: Vec<String>) -> Result<Options, Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value\n{}", arg, USAGE));
+        match arg.as_str() {
+            "--input" => options.input = Some(value()?),
+            "--out" => options.out = Some(value()?),
+            "--category" => {
+                let name = value()?;
+                options.category = Some(MoveCategory::from_name(&name).ok_or_else(|| format!("unknown category `{}`", name))?);
+            }
+            "--attacker" => options.attacker = Some((value()?, value()?)),
+            "--defender" => options.defender = Some(value()?),
+            "--version" => options.version = Some(value()?),
+            "--interval" => {
+                let minutes = value()?;
+                options.interval = Some(minutes.parse().ok().filter(|m| *m > 0).ok_or_else(|| format!("`{}` isn't a number of minutes", minutes))?);
+            }
+            "--range" => {
+                options.range = Some(match value()?.to_ascii_lowercase().as_str() {
+                    "close" => Range::Close,
+                    "mid" => Range::Mid,
+                    "far" => Range::Far,
+                    other => return Err(format!("unknown range `{}`, expected close, mid or far", other).into()),
+                });
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown argument `{}`\n{}", arg, USAGE).into()),
+            _ => options.positional.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+async fn run(args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    if args.first().is_some_and(|arg| arg == "export") {
+        return export::run(args.into_iter().skip(1).collect()).await;
+    }
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") || args.is_empty() {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let options = parse(args)?;
+    let context = FormatContext::default().verbosity(Verbosity::Detailed);
+    match options.positional.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        ["scrape"] => {
+            let frame_data = match &options.input {
+                Some(path) => FrameData::load_json(path)?,
+                None => {
+                    let report = framedata::load_all().await;
+                    for (character_id, error) in &report.failures {
+                        eprintln!("Could not load {}: {}", character_id.id, error);
+                    }
+                    report.data
+                }
+            };
+            match &options.out {
+                Some(path) => frame_data.save_json(path)?,
+                None => println!("{}", frame_data.to_json()),
+            }
+        }
+        ["move", character, move_query] => {
+            let character_id = find_character(character)?;
+            let frame_data = load(&options, &[character_id]).await?;
+            let move_data = frame_data.find_move_character(character_id, move_query)?;
+            println!("{} {} ({})", character_id.name(), move_data.identifier, move_data.name);
+            let rows = context.field_names().iter()
+                .map(|field| vec![context.label(field), context.value(move_data, field)])
+                .collect::<Vec<Vec<String>>>();
+            print!("{}", table(&[], &rows));
+        }
+        ["moves", character] => {
+            let character_id = find_character(character)?;
+            let frame_data = load(&options, &[character_id]).await?;
+            let moves = frame_data.find_character_frame_data(character_id)?.moves.iter()
+                .filter(|m| options.category.is_none_or(|category| m.category() == category))
+                .collect::<Vec<&Move>>();
+            let fields = Verbosity::Normal.fields();
+            let mut headers = vec![context.label("identifier")];
+            headers.extend(fields.iter().map(|field| context.label(field)));
+            let rows = moves.iter()
+                .map(|m| {
+                    let mut row = vec![m.identifier.clone()];
+                    row.extend(fields.iter().map(|field| context.value(m, field)));
+                    row
+                })
+                .collect::<Vec<Vec<String>>>();
+            print!("{}", table(&headers, &rows));
+        }
+        ["punish"] => {
+            let (attacker, move_query) = options.attacker.as_ref().ok_or_else(|| format!("punish needs --attacker\n{}", USAGE))?;
+            let defender = options.defender.as_ref().ok_or_else(|| format!("punish needs --defender\n{}", USAGE))?;
+            let (attacker, defender) = (find_character(attacker)?, find_character(defender)?);
+            let frame_data = load(&options, &[attacker, defender]).await?;
+            let attacker_move = frame_data.find_move_character(attacker, move_query)?;
+            let punishes = find_punishes(attacker_move, frame_data.find_character_frame_data(defender)?, options.range);
+            let subject = format!("{} {} is {} on block", attacker.name(), attacker_move.identifier, context.value(attacker_move, "block_advantage"));
+            if punishes.is_empty() {
+                println!("{}, {} has no punish for it", subject, defender.name());
+                return Ok(());
+            }
+            println!("{}, {} punishes with:", subject, defender.name());
+            let headers = [context.label("identifier"), context.label("name"), String::from("Method"), context.label("startup"), String::from("Spare"), context.label("punish_advantage")];
+            let rows = punishes.iter()
+                .map(|p| vec![
+                    p.move_data.identifier.clone(),
+                    p.move_data.name.clone(),
+                    String::from(match p.method {
+                        PunishMethod::Raw => "raw",
+                        PunishMethod::DriveRush => "drive rush",
+                    }),
+                    context.style.frames(p.startup),
+                    context.style.frames(p.margin),
+                    context.value(p.move_data, "punish_advantage"),
+                ])
+                .collect::<Vec<Vec<String>>>();
+            print!("{}", table(&headers, &rows));
+        }
+        ["publish", dir] => {
+            let version = options.version.as_ref().ok_or_else(|| format!("publish needs --version\n{}", USAGE))?;
+            let frame_data = match &options.input {
+                Some(path) => FrameData::load_json(path)?,
+                None => {
+                    let report = framedata::load_all().await;
+                    if !report.failures.is_empty() {
+                        for (character_id, error) in &report.failures {
+                            eprintln!("Could not load {}: {}", character_id.id, error);
+                        }
+                        return Err(format!("Not publishing, {} characters failed to load", report.failures.len()).into());
+                    }
+                    report.data
+                }
+            };
+            let manifest = publish(&frame_data, Path::new(dir), &GameVersion::new(version.as_str()))?;
+            println!("Published {} characters as {} to {}", manifest.characters.len(), manifest.version, dir);
+        }
+        ["watch"] => {
+            let out = options.out.as_ref().ok_or_else(|| format!("watch needs --out\n{}", USAGE))?;
+            let profile = match options.interval {
+                Some(minutes) => RefreshProfile::fixed(Duration::from_secs(minutes * 60)),
+                None => RefreshProfile::polite(),
+            };
+            watch_overlay_files(Loader::default(), profile, Path::new(out), |report, written| {
+                for (character_id, error) in &report.failures {
+                    eprintln!("Could not load {}: {}", character_id.id, error);
+                }
+                match written {
+                    Ok(paths) => println!("Wrote {} overlay files to {}", paths.len(), out),
+                    Err(e) => eprintln!("Could not write overlay files to {}: {}", out, e),
+                }
+                true
+            }).await;
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+fn find_character(query: &str) -> Result<&'static CharacterId, String> {
+    character::get_character_by_regex(query).ok_or_else(|| format!("Unknown character `{}`", query))
+}
+
+/// Reads `--input`, or scrapes just `characters`
+async fn load(options: &Options, characters: &[&CharacterId]) -> Result<FrameData, Box<dyn Error>> {
+    if let Some(path) = &options.input {
+        return Ok(FrameData::load_json(path)?);
+    }
+    let mut character_frame_data = Vec::new();
+    for character_id in characters {
+        character_frame_data.push(framedata::load(character_id).await?);
+    }
+    Ok(FrameData { character_frame_data })
+}
+
+/// Lines up `rows` under `headers` in columns two spaces apart, with a rule under the headers.
+/// Without headers, only the rows are printed.
+fn table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths = headers.iter().map(|h| h.chars().count()).collect::<Vec<usize>>();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: &[String]| {
+        let padded = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect::<Vec<String>>();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+    let mut table = String::new();
+    if !headers.is_empty() {
+        table.push_str(&line(headers));
+        table.push_str(&line(&widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<String>>()));
+    }
+    for row in rows {
+        table.push_str(&line(row));
+    }
+    table
+}
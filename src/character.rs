@@ -1,34 +1,42 @@
 extern crate regex;
 
+use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
 use std::sync::OnceLock;
 
 use regex::Regex;
 use crate::LazyLock;
 
-pub static RYU: CharacterId = CharacterId::new("ryu", "Ryu", "ryu", r"ryu");
-pub static LUKE: CharacterId = CharacterId::new("luke", "Luke", "luke", r"luke");
-pub static JAMIE: CharacterId = CharacterId::new("jamie", "Jamie", "jamie", r"jamie");
-pub static CHUNLI: CharacterId = CharacterId::new("chunli", "Chun-Li", "chunli", r"chun(-?li)?");
-pub static GUILE: CharacterId = CharacterId::new("guile", "Guile", "guile", r"guile");
-pub static KIMBERLY: CharacterId = CharacterId::new("kimberly", "Kimberly", "kimberly", r"kim(berly)?");
-pub static JURI: CharacterId = CharacterId::new("juri", "Juri", "juri", r"juri");
-pub static KEN: CharacterId = CharacterId::new("ken", "Ken", "ken", r"ken");
-pub static BLANKA: CharacterId = CharacterId::new("blanka", "Blanka", "blanka", r"blanka");
-pub static DHALSIM: CharacterId = CharacterId::new("dhalsim", "Dhalsim", "dhalsim", r"(dh?al)?sim");
-pub static EHONDA: CharacterId = CharacterId::new("ehonda", "E.Honda", "ehonda", r"e?honda");
-pub static DEEJAY: CharacterId = CharacterId::new("deejay", "Dee_Jay", "deejay", r"d(ee)?j(ay)?");
-pub static MANON: CharacterId = CharacterId::new("manon", "Manon", "manon", r"manon");
-pub static MARISA: CharacterId = CharacterId::new("marisa", "Marisa", "marisa", r"marisa");
-pub static JP: CharacterId = CharacterId::new("jp", "JP", "jp", r"jp");
-pub static ZANGIEF: CharacterId = CharacterId::new("zangief", "Zangief", "zangief", r"(zan)?gief");
-pub static LILY: CharacterId = CharacterId::new("lily", "Lily", "lily", r"lily");
-pub static CAMMY: CharacterId = CharacterId::new("cammy", "Cammy", "cammy", r"cammy");
-pub static RASHID: CharacterId = CharacterId::new("rashid", "Rashid", "rashid", r"rashid");
-pub static AKI: CharacterId = CharacterId::new("aki", "A.K.I.", "aki", r"a\.?k\.?i\.?");
-pub static ED: CharacterId = CharacterId::new("ed", "Ed", "ed", r"ed");
-pub static AKUMA: CharacterId = CharacterId::new("akuma", "Akuma", "akuma", r"akuma|gouki");
-pub static MBISON: CharacterId = CharacterId::new("mbison", "M.Bison", "mbison", r"(m.?)?bison");
+pub mod discovery;
+pub mod registry;
+
+#[cfg(feature = "async")]
+pub use discovery::discover_roster;
+pub use registry::CharacterRegistry;
+
+pub static RYU: CharacterId = CharacterId::builtin("ryu", "ryu", "Ryu", "ryu", r"ryu", 0, "2023-06-02");
+pub static LUKE: CharacterId = CharacterId::builtin("luke", "luke", "Luke", "luke", r"luke", 0, "2023-06-02");
+pub static JAMIE: CharacterId = CharacterId::builtin("jamie", "jamie", "Jamie", "jamie", r"jamie", 0, "2023-06-02");
+pub static CHUNLI: CharacterId = CharacterId::builtin("chunli", "chun-li", "Chun-Li", "chunli", r"chun(-?li)?", 0, "2023-06-02");
+pub static GUILE: CharacterId = CharacterId::builtin("guile", "guile", "Guile", "guile", r"guile", 0, "2023-06-02");
+pub static KIMBERLY: CharacterId = CharacterId::builtin("kimberly", "kimberly", "Kimberly", "kimberly", r"kim(berly)?", 0, "2023-06-02");
+pub static JURI: CharacterId = CharacterId::builtin("juri", "juri", "Juri", "juri", r"juri", 0, "2023-06-02");
+pub static KEN: CharacterId = CharacterId::builtin("ken", "ken", "Ken", "ken", r"ken", 0, "2023-06-02");
+pub static BLANKA: CharacterId = CharacterId::builtin("blanka", "blanka", "Blanka", "blanka", r"blanka", 0, "2023-06-02");
+pub static DHALSIM: CharacterId = CharacterId::builtin("dhalsim", "dhalsim", "Dhalsim", "dhalsim", r"(dh?al)?sim", 0, "2023-06-02");
+pub static EHONDA: CharacterId = CharacterId::builtin("ehonda", "e-honda", "E.Honda", "ehonda", r"e?honda", 0, "2023-06-02");
+pub static DEEJAY: CharacterId = CharacterId::builtin("deejay", "dee-jay", "Dee_Jay", "deejay", r"d(ee)?j(ay)?", 0, "2023-06-02");
+pub static MANON: CharacterId = CharacterId::builtin("manon", "manon", "Manon", "manon", r"manon", 0, "2023-06-02");
+pub static MARISA: CharacterId = CharacterId::builtin("marisa", "marisa", "Marisa", "marisa", r"marisa", 0, "2023-06-02");
+pub static JP: CharacterId = CharacterId::builtin("jp", "jp", "JP", "jp", r"jp", 0, "2023-06-02");
+pub static ZANGIEF: CharacterId = CharacterId::builtin("zangief", "zangief", "Zangief", "zangief", r"(zan)?gief", 0, "2023-06-02");
+pub static LILY: CharacterId = CharacterId::builtin("lily", "lily", "Lily", "lily", r"lily", 0, "2023-06-02");
+pub static CAMMY: CharacterId = CharacterId::builtin("cammy", "cammy", "Cammy", "cammy", r"cammy", 0, "2023-06-02");
+pub static RASHID: CharacterId = CharacterId::builtin("rashid", "rashid", "Rashid", "rashid", r"rashid", 1, "2023-07-24");
+pub static AKI: CharacterId = CharacterId::builtin("aki", "a-k-i", "A.K.I.", "aki", r"a\.?k\.?i\.?", 1, "2023-09-27");
+pub static ED: CharacterId = CharacterId::builtin("ed", "ed", "Ed", "ed", r"ed", 1, "2024-02-27");
+pub static AKUMA: CharacterId = CharacterId::builtin("akuma", "akuma", "Akuma", "akuma", r"akuma|gouki", 1, "2024-05-22");
+pub static MBISON: CharacterId = CharacterId::builtin("mbison", "m-bison", "M.Bison", "mbison", r"(m.?)?bison", 2, "2024-06-26");
 
 /// A collection of references to all the currently supported characters in this library
 pub static CHARACTERS: LazyLock<Vec<&CharacterId>> = LazyLock::new(|| vec!(
@@ -36,6 +44,19 @@ pub static CHARACTERS: LazyLock<Vec<&CharacterId>> = LazyLock::new(|| vec!(
     &DEEJAY, &MANON, &MARISA, &JP, &ZANGIEF, &LILY, &CAMMY, &RASHID, &AKI, &ED, &AKUMA, &MBISON
 ));
 
+/// Characters released in a DLC season, e.g. `characters_in_season(1)` for Rashid, A.K.I., Ed and
+/// Akuma. Season `0` is the launch roster.
+pub fn characters_in_season(season: u8) -> Vec<&'static CharacterId> {
+    CHARACTERS.iter().copied().filter(|c| c.season == season).collect()
+}
+
+/// Every character in the order they were released, the launch roster first
+pub fn characters_by_release() -> Vec<&'static CharacterId> {
+    let mut characters = CHARACTERS.to_vec();
+    characters.sort_by(|a, b| a.release_date.cmp(&b.release_date));
+    characters
+}
+
 /// Finds a character by matching against their regex
 pub fn get_character_by_regex<'a>(input: &str) -> Option<&'a CharacterId> {
     CHARACTERS.iter().find(|c| c.regex().is_match(input)).copied()
@@ -49,43 +70,164 @@ pub fn get_character_by_id<'a>(input: &str) -> Option<&'a CharacterId> {
 /// A struct representing a character this library supports. Unique by `id`
 #[derive(Clone, Debug)]
 pub struct CharacterId {
-    pub id: &'static str,
-    pub frame_data_id: &'static str,
-    pub gif_data_id: &'static str,
-    regex_str: &'static str,
-    regex_compiled: OnceLock<Regex>
+    pub id: Cow<'static, str>,
+    slug: Cow<'static, str>,
+    pub frame_data_id: Cow<'static, str>,
+    pub gif_data_id: Cow<'static, str>,
+    regex_str: Cow<'static, str>,
+    regex_compiled: OnceLock<Regex>,
+    season: u8,
+    release_date: Cow<'static, str>,
+    /// Replaces the wiki url of [`CharacterId::frame_data_url`], e.g. for a mirror
+    frame_data_url: Option<String>,
+    /// Replaces the url of [`CharacterId::gif_data_url`]
+    gif_data_url: Option<String>,
 }
 
 impl CharacterId {
-    const fn new(id: &'static str, frame_data_id: &'static str, gif_data_id: &'static str, regex: &'static str) -> Self {
+    /// Describes a character, e.g. one released after this version of the crate, for a
+    /// [`CharacterRegistry`]. `frame_data_id` is the character's name in wiki urls like
+    /// `Dee_Jay`, `gif_data_id` their name on ultimateframedata.com, and `regex` matches the names
+    /// users type for them, ignoring case. `season` is `0` for the launch roster and
+    /// `release_date` an ISO 8601 `YYYY-MM-DD` date. Every string can be borrowed or owned, so
+    /// characters can be built from config read at runtime. Fails if `regex` doesn't compile.
+    pub fn new(
+        id: impl Into<Cow<'static, str>>,
+        slug: impl Into<Cow<'static, str>>,
+        frame_data_id: impl Into<Cow<'static, str>>,
+        gif_data_id: impl Into<Cow<'static, str>>,
+        regex: impl Into<Cow<'static, str>>,
+        season: u8,
+        release_date: impl Into<Cow<'static, str>>,
+    ) -> Result<Self, regex::Error> {
+        let regex_str = regex.into();
+        let regex_compiled = OnceLock::from(compile_regex(&regex_str)?);
+        Ok(CharacterId {
+            id: id.into(),
+            slug: slug.into(),
+            frame_data_id: frame_data_id.into(),
+            gif_data_id: gif_data_id.into(),
+            regex_str,
+            regex_compiled,
+            season,
+            release_date: release_date.into(),
+            frame_data_url: None,
+            gif_data_url: None,
+        })
+    }
+
+    /// [`CharacterId::new`] for the built-in statics
+    const fn builtin(id: &'static str, slug: &'static str, frame_data_id: &'static str, gif_data_id: &'static str, regex: &'static str, season: u8, release_date: &'static str) -> Self {
         CharacterId {
-            id,
-            frame_data_id,
-            gif_data_id,
-            regex_str: regex,
+            id: Cow::Borrowed(id),
+            slug: Cow::Borrowed(slug),
+            frame_data_id: Cow::Borrowed(frame_data_id),
+            gif_data_id: Cow::Borrowed(gif_data_id),
+            regex_str: Cow::Borrowed(regex),
             regex_compiled: OnceLock::new(),
+            season,
+            release_date: Cow::Borrowed(release_date),
+            frame_data_url: None,
+            gif_data_url: None,
         }
     }
 
-    /// Gets the regex for this [`CharacterId`] via a [`OnceLock`]
+    /// Loads this character's frame data page from `url` instead of the wiki
+    pub fn with_frame_data_url(mut self, url: impl Into<String>) -> Self {
+        self.frame_data_url = Some(url.into());
+        self
+    }
+
+    /// Loads this character's gif page from `url` instead of ultimateframedata.com
+    pub fn with_gif_data_url(mut self, url: impl Into<String>) -> Self {
+        self.gif_data_url = Some(url.into());
+        self
+    }
+
+    /// A lowercase, URL-safe slug for web routes, e.g. `a-k-i` or `dee-jay`. Slugs are fixed
+    /// per character and do not follow changes to the wiki's display names.
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    /// Finds a built-in character by their [`CharacterId::slug`], ignoring case. Characters added to
+    /// a [`CharacterRegistry`] are found with [`CharacterRegistry::get_by_slug`].
+    pub fn from_slug(slug: &str) -> Option<&'static CharacterId> {
+        CHARACTERS.iter().find(|c| c.slug.eq_ignore_ascii_case(slug)).copied()
+    }
+
+    /// The DLC season (year) this character was released in, `0` for the launch roster
+    pub fn season(&self) -> u8 {
+        self.season
+    }
+
+    /// Whether this character was part of the launch roster rather than DLC
+    pub fn is_base_game(&self) -> bool {
+        self.season == 0
+    }
+
+    /// The date this character became playable, as an ISO 8601 `YYYY-MM-DD` string so that
+    /// release dates sort chronologically
+    pub fn release_date(&self) -> &str {
+        &self.release_date
+    }
+
+    /// Gets the regex for this [`CharacterId`], compiled by [`CharacterId::new`] or, for the
+    /// built-in statics, on first use
     pub fn regex(&self) -> &Regex {
-        self.regex_compiled.get_or_init(|| Regex::new(&format!(r"(?i)^{}$", self.regex_str)).unwrap())
+        self.regex_compiled.get_or_init(|| compile_regex(&self.regex_str).expect("built-in character regexes are valid"))
+    }
+
+    /// The id as the stem of a file written for this character. Ids with anything but ASCII
+    /// letters, digits, `-` and `_`, e.g. `../ryu`, could name a file outside the intended
+    /// directory and are rejected.
+    pub(crate) fn file_stem(&self) -> std::io::Result<&str> {
+        if !is_safe_file_stem(&self.id) {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("character id `{}` is not a safe file name", self.id)));
+        }
+        Ok(&self.id)
+    }
+
+    /// Returns a human readable name for this character, e.g. `Dee Jay` or `M.Bison`
+    pub fn name(&self) -> String {
+        self.frame_data_id.replace('_', " ")
     }
 
     /// Returns the url this library scrapes the data for each character
     pub fn frame_data_url(&self) -> String {
-        format!("https://wiki.supercombo.gg/w/Street_Fighter_6/{}/Data", self.frame_data_id)
+        match &self.frame_data_url {
+            Some(url) => url.clone(),
+            None => format!("https://wiki.supercombo.gg/w/Street_Fighter_6/{}/Data", self.frame_data_id),
+        }
+    }
+
+    /// Returns the url of each character's wiki overview page, which describes their unique mechanics
+    pub fn overview_url(&self) -> String {
+        format!("https://wiki.supercombo.gg/w/Street_Fighter_6/{}", self.frame_data_id)
     }
 
     /// Returns the url this library scrapes the data for each character's move gifs
     pub fn gif_data_url(&self) -> String {
-        format!("https://ultimateframedata.com/sf6/{}", self.gif_data_id)
+        match &self.gif_data_url {
+            Some(url) => url.clone(),
+            None => format!("https://ultimateframedata.com/sf6/{}", self.gif_data_id),
+        }
     }
 }
 
+/// Anchors a character's regex to the whole input, ignoring case
+fn compile_regex(regex: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!(r"(?i)^{}$", regex))
+}
+
+/// Whether `stem` can name a file without leaving its directory, see [`CharacterId::file_stem`]
+pub(crate) fn is_safe_file_stem(stem: &str) -> bool {
+    !stem.is_empty() && stem.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
 impl PartialEq<Self> for CharacterId {
     fn eq(&self, other: &Self) -> bool {
-        self.id.eq(other.id)
+        self.id == other.id
     }
 }
 
@@ -95,6 +237,87 @@ impl Eq for CharacterId {
 
 impl Hash for CharacterId {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write(self.id.as_ref())
+        state.write(self.id.as_bytes())
     }
-}
\ No newline at end of file
+}
+
+/// Serializes as the stable `id`, e.g. `"chunli"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for CharacterId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.id)
+    }
+}
+
+/// Deserializes from the stable `id`, resolving it against the supported characters, then those
+/// found by [`discover_roster`] or registered in a
+/// [`CharacterRegistry`]. Any other id, e.g. a typo like `"ryuu"`, is an error; to load data saved
+/// for a newer character, discover the roster or register them first.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CharacterId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = <Cow<str>>::deserialize(deserializer)?;
+        get_character_by_id(&id)
+            .or_else(|| discovery::find_discovered(&id))
+            .cloned()
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown character id `{}`", id)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_characters_by_slug() {
+        assert_eq!(CharacterId::from_slug("a-k-i"), Some(&AKI));
+        assert_eq!(CharacterId::from_slug("Dee-Jay"), Some(&DEEJAY));
+        assert_eq!(CharacterId::from_slug("deejay"), None);
+        assert_eq!(CharacterId::from_slug(""), None);
+        assert!(CHARACTERS.iter().all(|c| CharacterId::from_slug(c.slug()) == Some(*c)));
+    }
+
+    #[test]
+    fn slugs_are_unique_and_url_safe() {
+        let slugs = CHARACTERS.iter().map(|c| c.slug()).collect::<std::collections::HashSet<_>>();
+        assert_eq!(slugs.len(), CHARACTERS.len());
+        assert!(slugs.iter().all(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')));
+        assert_eq!((MBISON.slug(), MBISON.name()), ("m-bison", String::from("M.Bison")));
+        let mirrored = RYU.clone().with_frame_data_url("https://mirror.example/ryu.html");
+        assert_eq!(mirrored.slug(), "ryu");
+    }
+    #[test]
+    fn owns_its_strings() {
+        let name = String::from("Terry");
+        let terry = CharacterId::new(name.to_lowercase(), String::from("terry"), name, "terry", r"terry", 2, String::from("2024-09-24")).unwrap();
+        assert_eq!((terry.id.as_ref(), terry.slug(), terry.release_date()), ("terry", "terry", "2024-09-24"));
+        assert!(matches!(RYU.id, Cow::Borrowed("ryu")));
+        assert!(terry.regex().is_match("TERRY"));
+        assert!(CharacterId::new("terry", "terry", "Terry", "terry", r"terry(", 2, "").is_err());
+        assert!(CHARACTERS.iter().all(|c| compile_regex(&c.regex_str).is_ok()));
+    }
+
+    #[test]
+    fn rejects_unsafe_file_names() {
+        assert!(CHARACTERS.iter().all(|c| c.file_stem().is_ok()));
+        for id in ["../ryu", "ryu/ken", r"ryu\ken", "..", ""] {
+            let character_id = CharacterId::new(id, "x", "X", "x", r"x", 0, "").unwrap();
+            assert_eq!(character_id.file_stem().unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_only_known_ids() {
+        let ryu: CharacterId = serde_json::from_str(r#""ryu""#).unwrap();
+        assert_eq!(ryu, RYU);
+        assert_eq!(serde_json::from_str::<CharacterId>(r#""ryuu""#).unwrap_err().to_string(), "unknown character id `ryuu`");
+        assert!(serde_json::from_str::<CharacterId>(r#""""#).is_err());
+        assert!(serde_json::from_str::<CharacterId>(r#""sagat""#).is_err());
+        let sagat = CharacterId::new("sagat", "sagat", "Sagat", "sagat", r"sagat", 3, "").unwrap();
+        CharacterRegistry::empty().register(sagat.clone());
+        let registered: CharacterId = serde_json::from_str(r#""sagat""#).unwrap();
+        assert_eq!((registered.frame_data_id.as_ref(), serde_json::to_string(&registered).unwrap()), ("Sagat", String::from(r#""sagat""#)));
+    }
+}
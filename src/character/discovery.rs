@@ -0,0 +1,144 @@
+//! Finding the current roster on the wiki, so characters released after this version of the crate
+//! can still be loaded, see [`discover_roster`].
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use scraper::{Html, Selector};
+
+use crate::character::{CharacterId, CHARACTERS};
+#[cfg(feature = "async")]
+use crate::error::SF6Error;
+#[cfg(feature = "async")]
+use crate::framedata::Loader;
+use crate::LazyLock;
+
+/// The wiki's Street Fighter 6 page, whose character select grid links every character's page
+pub const ROSTER_URL: &str = "https://wiki.supercombo.gg/w/Street_Fighter_6";
+const CHARACTER_PATH: &str = "/w/Street_Fighter_6/";
+
+static LINK_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("a[href]").unwrap());
+static IMAGE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img").unwrap());
+/// Characters built by [`discover_roster`] or registered in a
+/// [`CharacterRegistry`](super::CharacterRegistry), by `frame_data_id`, so each is only built (and
+/// its box leaked) once however often the roster is discovered
+static DISCOVERED: LazyLock<Mutex<HashMap<String, &'static CharacterId>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Scrapes the wiki's roster with the default [`Loader`], see [`discover_roster_with`]
+#[cfg(feature = "async")]
+pub async fn discover_roster() -> Result<Vec<&'static CharacterId>, SF6Error> {
+    discover_roster_with(&Loader::default()).await
+}
+
+/// Scrapes the wiki's Street Fighter 6 page for every character on it. Characters the crate knows
+/// are returned as the statics in [`CHARACTERS`]; the rest are built from their wiki name with a
+/// lowercase id and gif page, a regex that ignores punctuation and spaces, the latest known season
+/// and no release date, see [`derive_character`]. Discovered characters can be loaded with
+/// [`Loader::load_roster`](crate::framedata::Loader::load_roster) and are deserialized from their
+/// id, but aren't found by [`get_character_by_regex`](super::get_character_by_regex).
+#[cfg(feature = "async")]
+pub async fn discover_roster_with(loader: &Loader) -> Result<Vec<&'static CharacterId>, SF6Error> {
+    let html = loader.request_page(ROSTER_URL.to_string()).await?;
+    let roster = parse_roster(&html);
+    if roster.is_empty() {
+        return Err(SF6Error::ParseStructureChanged { url: ROSTER_URL.to_string() });
+    }
+    Ok(roster)
+}
+
+/// Reads the roster from a saved copy of [`ROSTER_URL`], see [`discover_roster_with`]. Characters
+/// are linked by portrait images; text links to other pages like `Controls` are ignored.
+pub fn parse_roster(html: &str) -> Vec<&'static CharacterId> {
+    let html = Html::parse_document(html);
+    let mut roster: Vec<&'static CharacterId> = Vec::new();
+    for link in html.select(&LINK_SELECTOR).filter(|link| link.select(&IMAGE_SELECTOR).next().is_some()) {
+        let Some(frame_data_id) = link.value().attr("href").and_then(frame_data_id) else {
+            continue;
+        };
+        let character_id = CHARACTERS.iter()
+            .find(|c| c.frame_data_id.eq_ignore_ascii_case(frame_data_id))
+            .copied()
+            .unwrap_or_else(|| discovered(frame_data_id));
+        if !roster.contains(&character_id) {
+            roster.push(character_id);
+        }
+    }
+    roster
+}
+
+/// The character page a link points to, e.g. `Dee_Jay` for `/w/Street_Fighter_6/Dee_Jay`.
+/// Subpages like `Ryu/Data` and namespaced pages like `File:...` aren't character pages.
+fn frame_data_id(href: &str) -> Option<&str> {
+    let path = href.strip_prefix("https://wiki.supercombo.gg").unwrap_or(href);
+    let name = path.strip_prefix(CHARACTER_PATH)?.split(['#', '?']).next()?;
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    valid.then_some(name)
+}
+
+fn discovered(frame_data_id: &str) -> &'static CharacterId {
+    if let Some(character_id) = DISCOVERED.lock().unwrap().get(frame_data_id) {
+        return character_id;
+    }
+    remember(derive_character(frame_data_id))
+}
+
+/// Keeps a character the crate doesn't ship so it can be deserialized from its id, returning the
+/// copy already kept for its `frame_data_id` if there is one
+pub(crate) fn remember(character_id: CharacterId) -> &'static CharacterId {
+    let mut discovered = DISCOVERED.lock().unwrap();
+    if let Some(existing) = discovered.get(character_id.frame_data_id.as_ref()) {
+        return existing;
+    }
+    let character_id: &'static CharacterId = Box::leak(Box::new(character_id));
+    discovered.insert(character_id.frame_data_id.to_string(), character_id);
+    character_id
+}
+
+/// A character discovered by [`discover_roster`] or registered in a
+/// [`CharacterRegistry`](super::CharacterRegistry) with the given [`CharacterId::id`]
+#[cfg(feature = "serde")]
+pub(crate) fn find_discovered(id: &str) -> Option<&'static CharacterId> {
+    DISCOVERED.lock().unwrap().values().find(|c| c.id == id).copied()
+}
+
+/// Describes a character the crate doesn't know from their wiki name, e.g. `Terry` or
+/// `Terry_Bogard`: a lowercase id and gif page of its letters and digits (`terrybogard`), a slug
+/// of its words (`terry-bogard`), a regex matching the words with or without spaces or
+/// punctuation between them, the latest known season and no release date
+pub fn derive_character(frame_data_id: &str) -> CharacterId {
+    let lowercase = frame_data_id.to_ascii_lowercase();
+    let id = lowercase.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>();
+    let slug = lowercase.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+    let regex = lowercase.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join(r"[ ._-]?");
+    let season = CHARACTERS.iter().map(|c| c.season()).max().unwrap_or_default();
+    CharacterId::new(id.clone(), slug, frame_data_id.to_string(), id, regex, season, "")
+        .expect("derived regexes only join letters and digits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+
+    #[test]
+    fn parses_roster_page() {
+        let portrait = |href: &str| format!("<a href=\"{}\"><img src=\"/images/portrait.png\"></a>", href);
+        let html = format!("<html><body>{}{}{}{}<a href=\"/w/Street_Fighter_6/Controls\">Controls</a>{}</body></html>",
+            portrait("/w/Street_Fighter_6/Ryu"), portrait("/w/Street_Fighter_6/Terry"), portrait("/w/Street_Fighter_6/Ryu/Data"),
+            portrait("/w/Street_Fighter_6/Dee_Jay"), portrait("/w/Street_Fighter_6/Ryu"));
+        let roster = parse_roster(&html);
+        assert_eq!(roster.iter().map(|c| c.id.as_ref()).collect::<Vec<&str>>(), vec!["ryu", "terry", "deejay"]);
+        assert!(std::ptr::eq(roster[0], &character::RYU));
+        let terry = roster[1];
+        assert!(terry.regex().is_match("TERRY"));
+        assert_eq!(terry.frame_data_url(), "https://wiki.supercombo.gg/w/Street_Fighter_6/Terry/Data");
+        assert_eq!(terry.gif_data_url(), "https://ultimateframedata.com/sf6/terry");
+        assert!(std::ptr::eq(parse_roster(&html)[1], terry));
+    }
+}
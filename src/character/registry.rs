@@ -0,0 +1,131 @@
+//! A roster that can grow beyond the characters the crate ships with, see [`CharacterRegistry`].
+//! ```
+//! use sf6rs::character::{CharacterId, CharacterRegistry};
+//! let mut registry = CharacterRegistry::default();
+//! registry.register(CharacterId::new("terry", "terry", "Terry", "terry", r"terry|bogard", 2, "2024-09-24").unwrap());
+//! registry.override_urls("ryu", Some(String::from("https://mirror.example/ryu.html")), None);
+//! assert_eq!(registry.get_by_regex("Bogard").map(|c| c.id.as_ref()), Some("terry"));
+//! ```
+
+use crate::character::{discovery, get_character_by_id, CharacterId, CHARACTERS};
+
+/// The characters to load, starting from the built-in [`CHARACTERS`]. Characters can be added or
+/// replaced with [`CharacterRegistry::register`] and pointed at other pages with
+/// [`CharacterRegistry::override_urls`], then loaded with
+/// [`Loader::load_registry`](crate::framedata::Loader::load_registry).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterRegistry {
+    characters: Vec<CharacterId>,
+}
+
+impl Default for CharacterRegistry {
+    fn default() -> Self {
+        CharacterRegistry::builtin()
+    }
+}
+
+impl CharacterRegistry {
+    /// Every character the crate supports, see [`CHARACTERS`]
+    pub fn builtin() -> Self {
+        CharacterRegistry {
+            characters: CHARACTERS.iter().map(|c| (*c).clone()).collect(),
+        }
+    }
+
+    /// A registry without any characters
+    pub fn empty() -> Self {
+        CharacterRegistry {
+            characters: Vec::new(),
+        }
+    }
+
+    /// Adds `character_id`, replacing and returning a character with the same id. A character the
+    /// crate doesn't ship is also remembered for the rest of the process, so data saved for them
+    /// can be deserialized from their id.
+    pub fn register(&mut self, character_id: CharacterId) -> Option<CharacterId> {
+        if get_character_by_id(&character_id.id).is_none() {
+            discovery::remember(character_id.clone());
+        }
+        match self.characters.iter_mut().find(|c| **c == character_id) {
+            Some(existing) => Some(std::mem::replace(existing, character_id)),
+            None => {
+                self.characters.push(character_id);
+                None
+            }
+        }
+    }
+
+    /// Removes and returns the character with `id`
+    pub fn unregister(&mut self, id: &str) -> Option<CharacterId> {
+        let position = self.characters.iter().position(|c| c.id == id)?;
+        Some(self.characters.remove(position))
+    }
+
+    /// Loads the pages of the character with `id` from the given urls instead, `None` keeping the
+    /// current url. Returns whether the character is registered.
+    pub fn override_urls(&mut self, id: &str, frame_data_url: Option<String>, gif_data_url: Option<String>) -> bool {
+        let Some(position) = self.characters.iter().position(|c| c.id == id) else {
+            return false;
+        };
+        let mut character_id = self.characters[position].clone();
+        if let Some(url) = frame_data_url {
+            character_id = character_id.with_frame_data_url(url);
+        }
+        if let Some(url) = gif_data_url {
+            character_id = character_id.with_gif_data_url(url);
+        }
+        self.characters[position] = character_id;
+        true
+    }
+
+    /// Finds a character by their id. Case sensitive.
+    pub fn get_by_id(&self, id: &str) -> Option<&CharacterId> {
+        self.characters.iter().find(|c| c.id == id)
+    }
+
+    /// Finds a character by their [`CharacterId::slug`], ignoring case
+    pub fn get_by_slug(&self, slug: &str) -> Option<&CharacterId> {
+        self.characters.iter().find(|c| c.slug().eq_ignore_ascii_case(slug))
+    }
+
+    /// Finds a character by matching against their regex
+    pub fn get_by_regex(&self, input: &str) -> Option<&CharacterId> {
+        self.characters.iter().find(|c| c.regex().is_match(input))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CharacterId> {
+        self.characters.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.characters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+
+    #[test]
+    fn registers_characters() {
+        let mut registry = CharacterRegistry::builtin();
+        assert_eq!(registry.len(), CHARACTERS.len());
+        assert!(registry.register(CharacterId::new("mai", "mai", "Mai", "mai", r"mai", 2, "2025-02-05").unwrap()).is_none());
+        assert_eq!(registry.get_by_regex("MAI").unwrap().frame_data_url(), "https://wiki.supercombo.gg/w/Street_Fighter_6/Mai/Data");
+        assert_eq!(registry.get_by_slug("MAI").map(|c| c.id.as_ref()), Some("mai"));
+        assert_eq!(registry.get_by_slug("dee-jay"), Some(&character::DEEJAY));
+
+        assert!(registry.override_urls("ryu", Some(String::from("https://mirror.example/ryu")), None));
+        let ryu = registry.get_by_id("ryu").unwrap();
+        assert_eq!(ryu.frame_data_url(), "https://mirror.example/ryu");
+        assert_eq!(ryu.gif_data_url(), character::RYU.gif_data_url());
+        assert!(!registry.override_urls("terry", None, None));
+        assert_eq!(registry.unregister("mai").map(|c| c.id.into_owned()).as_deref(), Some("mai"));
+        assert_eq!(registry.len(), CHARACTERS.len());
+    }
+}
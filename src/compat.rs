@@ -0,0 +1,25 @@
+//! Stable paths for the string-based data model. The typed data model will replace the types in
+//! [`framedata`](crate::framedata); when it does, the types here stay string-based and gain
+//! conversions from the new model, so bots can switch imports to `compat::v1` now and upgrade the
+//! rest of their code incrementally instead of all at once.
+
+/// The original model where every frame data field is the string shown on the wiki
+pub mod v1 {
+    pub use crate::framedata::{CharacterFrameData, FrameData, Move, MoveGif, SF6FrameDataError};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::v1;
+    use crate::character;
+
+    #[test]
+    fn v1_paths_are_the_string_model() {
+        let moves: Vec<crate::framedata::Move> = vec![v1::Move { identifier: String::from("5LP"), startup: String::from("4"), ..v1::Move::default() }];
+        let frame_data = v1::FrameData {
+            character_frame_data: vec![v1::CharacterFrameData { character_id: character::RYU.clone(), moves, gifs: Vec::new(), stats: None }],
+        };
+        assert_eq!(frame_data.find_move("ryu", "5lp").unwrap().startup, "4");
+        assert!(matches!(frame_data.find_move("ryu", "9HK"), Err(v1::SF6FrameDataError::UnknownMove)));
+    }
+}
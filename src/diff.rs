@@ -0,0 +1,191 @@
+//! Differences between two frame data snapshots, e.g. scraped before and after the wiki is updated
+//! for a balance patch, for generating changelogs:
+//! ```
+//! use sf6rs::framedata::FrameData;
+//!
+//! let before = FrameData { character_frame_data: Vec::new() };
+//! let after = before.clone();
+//! assert!(before.diff(&after).is_empty());
+//! ```
+
+use std::fmt::Display;
+
+use crate::character::CharacterId;
+use crate::format::style::FormatStyle;
+use crate::framedata::{CharacterFrameData, FrameData, Move};
+
+/// Fields left out of a diff: the identifier moves are matched by, and the wiki's images, which
+/// change whenever one is re-uploaded
+pub const IGNORED_FIELDS: [&str; 3] = ["identifier", "image_link", "image_alt"];
+
+/// One field of a move that changed, e.g. `recovery` from `38` to `41`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldChange {
+    /// The field's name, see [`Move::FIELDS`]
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveChange {
+    Added,
+    Removed,
+    Changed(Vec<FieldChange>),
+}
+
+/// One move that differs between two snapshots, see [`FrameData::diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveDiff {
+    pub character_id: CharacterId,
+    pub identifier: String,
+    pub change: MoveChange,
+}
+
+/// Everything that differs between two snapshots, see [`FrameData::diff`]. Characters only in
+/// one of them are listed on their own rather than move by move.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameDataDiff {
+    pub added_characters: Vec<CharacterId>,
+    pub removed_characters: Vec<CharacterId>,
+    pub moves: Vec<MoveDiff>,
+}
+
+impl FrameDataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_characters.is_empty() && self.removed_characters.is_empty() && self.moves.is_empty()
+    }
+
+    /// One line per change, e.g. `Ken 236HK recovery 38 → 41`, for a patch changelog
+    pub fn changelog(&self) -> Vec<String> {
+        let style = FormatStyle::default();
+        let mut lines = Vec::new();
+        for character_id in &self.added_characters {
+            lines.push(format!("{} added", character_id.name()));
+        }
+        for character_id in &self.removed_characters {
+            lines.push(format!("{} removed", character_id.name()));
+        }
+        for move_diff in &self.moves {
+            let name = move_diff.character_id.name();
+            match &move_diff.change {
+                MoveChange::Added => lines.push(format!("{} {} added", name, move_diff.identifier)),
+                MoveChange::Removed => lines.push(format!("{} {} removed", name, move_diff.identifier)),
+                MoveChange::Changed(changes) => lines.extend(changes.iter().map(|change| {
+                    format!("{} {} {} {} → {}", name, move_diff.identifier, change.field, style.value(&change.before), style.value(&change.after))
+                })),
+            }
+        }
+        lines
+    }
+}
+
+impl Display for FrameDataDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.changelog().join("\n"))
+    }
+}
+
+impl FrameData {
+    /// What changed from this snapshot to `other`: added and removed characters and moves, and
+    /// each field that changed on moves in both. Moves are matched by `identifier`.
+    pub fn diff(&self, other: &FrameData) -> FrameDataDiff {
+        let mut diff = FrameDataDiff::default();
+        for before in &self.character_frame_data {
+            match other.find_character_frame_data(&before.character_id) {
+                Ok(after) => diff.moves.extend(diff_character(before, after)),
+                Err(_) => diff.removed_characters.push(before.character_id.clone()),
+            }
+        }
+        diff.added_characters = other.character_frame_data.iter()
+            .filter(|after| self.find_character_frame_data(&after.character_id).is_err())
+            .map(|after| after.character_id.clone())
+            .collect();
+        diff
+    }
+}
+
+fn diff_character(before: &CharacterFrameData, after: &CharacterFrameData) -> Vec<MoveDiff> {
+    let move_diff = |identifier: &str, change| MoveDiff {
+        character_id: before.character_id.clone(),
+        identifier: identifier.to_string(),
+        change,
+    };
+    let mut diffs = Vec::new();
+    for before_move in &before.moves {
+        match find(after, &before_move.identifier) {
+            Some(after_move) => {
+                let changes = diff_move(before_move, after_move);
+                if !changes.is_empty() {
+                    diffs.push(move_diff(&before_move.identifier, MoveChange::Changed(changes)));
+                }
+            }
+            None => diffs.push(move_diff(&before_move.identifier, MoveChange::Removed)),
+        }
+    }
+    for after_move in &after.moves {
+        if find(before, &after_move.identifier).is_none() {
+            diffs.push(move_diff(&after_move.identifier, MoveChange::Added));
+        }
+    }
+    diffs
+}
+
+fn find<'a>(character: &'a CharacterFrameData, identifier: &str) -> Option<&'a Move> {
+    character.moves.iter().find(|m| m.identifier == identifier)
+}
+
+fn diff_move(before: &Move, after: &Move) -> Vec<FieldChange> {
+    Move::FIELDS.iter()
+        .filter(|field| !IGNORED_FIELDS.contains(field))
+        .filter_map(|field| {
+            let (old, new) = (before.get(field)?.trim(), after.get(field)?.trim());
+            (old != new).then(|| FieldChange {
+                field: field.to_string(),
+                before: old.to_string(),
+                after: new.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with(identifier: &str, recovery: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            recovery: recovery.to_string(),
+            image_link: format!("{}.png", recovery),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn diffs_snapshots() {
+        let before = FrameData {
+            character_frame_data: vec![
+                testing::character_frame_data(&character::KEN, vec![with("5LP", "7"), with("236HK", "38"), with("2HP", "20")]),
+                testing::character_frame_data(&character::RYU, vec![with("5LP", "7")]),
+            ],
+        };
+        let after = FrameData {
+            character_frame_data: vec![
+                testing::character_frame_data(&character::KEN, vec![with("5LP", "7"), with("236HK", "41"), with("214LK", "18")]),
+                testing::character_frame_data(&character::JP, vec![with("5LP", "7")]),
+            ],
+        };
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_characters, vec![character::JP.clone()]);
+        assert_eq!(diff.removed_characters, vec![character::RYU.clone()]);
+        assert_eq!(diff.changelog(), vec!["JP added", "Ryu removed", "Ken 236HK recovery 38 → 41", "Ken 2HP removed", "Ken 214LK added"]);
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! Structured results for move queries that match more than one move, so bots can render the
+//! candidates as buttons or a select menu instead of each building their own ambiguity payload.
+//! ```no_run
+//! # fn run(data: sf6rs::framedata::FrameData) {
+//! use sf6rs::disambiguation::MoveResolution;
+//!
+//! match data.resolve_move("ryu", "hadoken").unwrap() {
+//!     MoveResolution::Found(move_data) => println!("{}", move_data.name),
+//!     MoveResolution::Ambiguous(choices) => for candidate in &choices.candidates {
+//!         println!("[{}] {} - {}", candidate.identifier, candidate.name, candidate.summary);
+//!     },
+//! }
+//! # }
+//! ```
+
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, FrameData, Move, SF6FrameDataError};
+use crate::tags::MoveCategory;
+use crate::text::normalize;
+
+/// The most candidates a [`Disambiguation`] holds, matching the option limit of Discord's select
+/// menus
+pub const MAX_CANDIDATES: usize = 25;
+
+/// One move a query could have meant
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate {
+    /// The identifier that resolves to exactly this move, suitable as a button's custom id
+    pub identifier: String,
+    pub name: String,
+    pub category: MoveCategory,
+    /// A one-line description, e.g. `6f startup, -30 on block`
+    pub summary: String,
+}
+
+impl Candidate {
+    pub fn new(move_data: &Move) -> Self {
+        Candidate {
+            identifier: move_data.identifier.clone(),
+            name: move_data.name.clone(),
+            category: move_data.category(),
+            summary: summary(move_data),
+        }
+    }
+}
+
+/// The moves an ambiguous query matched, in movelist order
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Disambiguation {
+    pub character: String,
+    pub query: String,
+    /// At most [`MAX_CANDIDATES`] candidates
+    pub candidates: Vec<Candidate>,
+    /// Whether more moves matched than fit in `candidates`
+    pub truncated: bool,
+}
+
+/// The result of resolving a move query
+#[derive(Debug, Clone)]
+pub enum MoveResolution<'a> {
+    Found(&'a Move),
+    Ambiguous(Disambiguation),
+}
+
+impl CharacterFrameData {
+    /// Resolves `query` to a move. An identifier matching exactly (ignoring case) always resolves
+    /// to that move; otherwise every move whose identifier, input or name contains the query is a
+    /// candidate, and more than one candidate gives a [`MoveResolution::Ambiguous`].
+    pub fn resolve_move(&self, query: &str) -> Result<MoveResolution<'_>, SF6FrameDataError> {
+        if let Some(exact) = self.moves.iter().find(|m| m.identifier.eq_ignore_ascii_case(query.trim())) {
+            return Ok(MoveResolution::Found(exact));
+        }
+        let needle = normalize(query);
+        if needle.is_empty() {
+            return Err(SF6FrameDataError::UnknownMove);
+        }
+        let matches = self.moves.iter()
+            .filter(|m| [&m.identifier, &m.input, &m.name].iter().any(|value| normalize(value).contains(&needle)))
+            .collect::<Vec<&Move>>();
+        match matches.as_slice() {
+            [] => Err(SF6FrameDataError::UnknownMove),
+            [single] => Ok(MoveResolution::Found(single)),
+            _ => Ok(MoveResolution::Ambiguous(disambiguation(&self.character_id, query, &matches))),
+        }
+    }
+}
+
+impl FrameData {
+    /// Resolves a move query for the character matching `character_query`, see
+    /// [`CharacterFrameData::resolve_move`]
+    pub fn resolve_move(&self, character_query: &str, move_query: &str) -> Result<MoveResolution<'_>, SF6FrameDataError> {
+        self.find_character_frame_data_query(character_query)?.resolve_move(move_query)
+    }
+}
+
+fn disambiguation(character_id: &CharacterId, query: &str, matches: &[&Move]) -> Disambiguation {
+    Disambiguation {
+        character: character_id.name(),
+        query: query.to_string(),
+        candidates: matches.iter().take(MAX_CANDIDATES).map(|m| Candidate::new(m)).collect(),
+        truncated: matches.len() > MAX_CANDIDATES,
+    }
+}
+
+fn summary(move_data: &Move) -> String {
+    let mut facts = Vec::new();
+    if let Some(startup) = signed_number(&move_data.startup) {
+        facts.push(format!("{}f startup", startup));
+    }
+    if let Some(block) = signed_number(&move_data.block_advantage) {
+        facts.push(format!("{:+} on block", block));
+    }
+    if facts.is_empty() {
+        move_data.category().name().to_string()
+    } else {
+        facts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn test_move(identifier: &str, name: &str, startup: &str, block: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            input: identifier.to_string(),
+            name: name.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolves_ambiguous_queries() {
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            test_move("5LP", "Standing Light Punch", "4", "-1"),
+            test_move("236LP", "Hadoken", "16", "-6"),
+            test_move("236HP", "Hadoken", "16", "-2"),
+            test_move("214LK", "Tatsumaki Senpu-kyaku", "11", "-8"),
+        ]);
+        assert!(matches!(ryu.resolve_move("5lp"), Ok(MoveResolution::Found(m)) if m.identifier == "5LP"));
+        assert!(matches!(ryu.resolve_move("tatsu"), Ok(MoveResolution::Found(m)) if m.identifier == "214LK"));
+        assert!(matches!(ryu.resolve_move("shoryuken"), Err(SF6FrameDataError::UnknownMove)));
+
+        let Ok(MoveResolution::Ambiguous(choices)) = ryu.resolve_move("hadoken") else {
+            panic!("hadoken should be ambiguous");
+        };
+        assert_eq!(choices.candidates.len(), 2);
+        assert!(!choices.truncated);
+        assert_eq!(choices.candidates[1].identifier, "236HP");
+        assert_eq!(choices.candidates[1].category, MoveCategory::Special);
+        assert_eq!(choices.candidates[1].summary, "16f startup, -2 on block");
+    }
+}
@@ -0,0 +1,243 @@
+//! Practice drills generated from frame data, for trainer bots and apps. Drills come from a seed,
+//! so the same seed and data always give the same drill, e.g. one drill of the day for everyone:
+//! ```
+//! use sf6rs::character;
+//! use sf6rs::drills::{Difficulty, DrillGenerator};
+//! use sf6rs::framedata::FrameData;
+//!
+//! let data = FrameData { character_frame_data: Vec::new() };
+//! let mut drills = DrillGenerator::new(&data, &character::RYU, 20261016).difficulty(Difficulty::Hard);
+//! let drill = drills.punish_drill();
+//! assert!(drill.items.is_empty());
+//! ```
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::analysis::punish::{find_punishes, PunishMethod};
+use crate::character::CharacterId;
+use crate::format::PUNISHABLE_THRESHOLD;
+use crate::framedata::{FrameData, Move};
+use crate::tags::MoveCategory;
+
+/// How demanding a drill is: how many moves it has and how close their frame data is to what
+/// can and can't be punished
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// A few moves that are very punishable, or plainly safe
+    Easy,
+    #[default]
+    Medium,
+    /// More moves, down to the tightest punish windows, and safe and punishable moves a frame apart
+    Hard,
+}
+
+impl Difficulty {
+    /// How many moves a drill has
+    pub fn moves(self) -> usize {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Medium => 5,
+            Difficulty::Hard => 8,
+        }
+    }
+
+    /// The best block advantage a move in a punish drill can have, e.g. `-8` for moves that are
+    /// -8 or worse
+    pub fn punish_threshold(self) -> i32 {
+        match self {
+            Difficulty::Easy => -8,
+            Difficulty::Medium => -6,
+            Difficulty::Hard => PUNISHABLE_THRESHOLD,
+        }
+    }
+
+    /// Whether a move this far from [`PUNISHABLE_THRESHOLD`] on block belongs in a safe or
+    /// punishable drill
+    fn fits_safety(self, block: i32) -> bool {
+        let distance = (block - PUNISHABLE_THRESHOLD).abs();
+        match self {
+            Difficulty::Easy => distance >= 4,
+            Difficulty::Medium => true,
+            Difficulty::Hard => distance <= 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrillKind {
+    /// Punish each move with the player's fastest punish
+    Punish,
+    /// Tell whether each move is safe or punishable on block
+    SafeOrPunishable,
+}
+
+/// What the player should answer for one move of a drill
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrillAnswer {
+    /// The player's fastest punish, see [`find_punishes`]
+    Punish { identifier: String, startup: i32, method: PunishMethod },
+    /// The player's character has nothing fast enough
+    NoPunish,
+    Safe,
+    Punishable,
+}
+
+/// One move of a [`Drill`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrillItem {
+    /// The character performing the move
+    pub character: CharacterId,
+    pub identifier: String,
+    pub block_advantage: i32,
+    pub answer: DrillAnswer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Drill {
+    pub kind: DrillKind,
+    pub difficulty: Difficulty,
+    /// What to tell the player, e.g. `Punish these 5 moves that are -6 or worse with Ryu's fastest punish`
+    pub prompt: String,
+    pub items: Vec<DrillItem>,
+}
+
+/// Generates drills for one player character from every loaded move, see the
+/// [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct DrillGenerator<'a> {
+    frame_data: &'a FrameData,
+    player: &'a CharacterId,
+    difficulty: Difficulty,
+    rng: StdRng,
+}
+
+impl<'a> DrillGenerator<'a> {
+    pub fn new(frame_data: &'a FrameData, player: &'a CharacterId, seed: u64) -> Self {
+        DrillGenerator {
+            frame_data,
+            player,
+            difficulty: Difficulty::default(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Moves at most [`Difficulty::punish_threshold`] on block, each with the player's fastest
+    /// punish. Empty if the player's character isn't loaded.
+    pub fn punish_drill(&mut self) -> Drill {
+        let threshold = self.difficulty.punish_threshold();
+        let player = self.frame_data.find_character_frame_data(self.player).ok();
+        let candidates = self.frame_data.query()
+            .block_at_most(threshold)
+            .into_iter()
+            .filter(|(_, m)| m.category() != MoveCategory::Throw)
+            .collect::<Vec<_>>();
+        let items = match player {
+            Some(player) => self.pick(candidates)
+                .map(|(character, move_data, block)| {
+                    let answer = match find_punishes(move_data, player, None).first() {
+                        Some(punish) => DrillAnswer::Punish {
+                            identifier: punish.move_data.identifier.clone(),
+                            startup: punish.startup,
+                            method: punish.method,
+                        },
+                        None => DrillAnswer::NoPunish,
+                    };
+                    DrillItem { character: character.clone(), identifier: move_data.identifier.clone(), block_advantage: block, answer }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Drill {
+            kind: DrillKind::Punish,
+            difficulty: self.difficulty,
+            prompt: format!("Punish these {} moves that are {} or worse with {}'s fastest punish", items.len(), threshold, self.player.name()),
+            items,
+        }
+    }
+
+    /// Moves on either side of [`PUNISHABLE_THRESHOLD`] on block, to be sorted into safe and
+    /// punishable
+    pub fn safety_drill(&mut self) -> Drill {
+        let difficulty = self.difficulty;
+        let candidates = self.frame_data.query()
+            .into_iter()
+            .filter(|(_, m)| m.block_frames().is_some_and(|block| difficulty.fits_safety(block)))
+            .collect::<Vec<_>>();
+        let items = self.pick(candidates)
+            .map(|(character, move_data, block)| DrillItem {
+                character: character.clone(),
+                identifier: move_data.identifier.clone(),
+                block_advantage: block,
+                answer: if block <= PUNISHABLE_THRESHOLD { DrillAnswer::Punishable } else { DrillAnswer::Safe },
+            })
+            .collect::<Vec<DrillItem>>();
+        Drill {
+            kind: DrillKind::SafeOrPunishable,
+            difficulty,
+            prompt: format!("Safe or punishable? Block these {} moves and decide", items.len()),
+            items,
+        }
+    }
+
+    /// [`Difficulty::moves`] of `candidates` at random, with their block advantage
+    fn pick<'b>(&mut self, candidates: Vec<(&'b CharacterId, &'b Move)>) -> impl Iterator<Item = (&'b CharacterId, &'b Move, i32)> {
+        candidates.choose_multiple(&mut self.rng, self.difficulty.moves())
+            .filter_map(|(character, move_data)| Some((*character, *move_data, move_data.block_frames()?)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with(identifier: &str, startup: &str, block: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn generates_seeded_drills() {
+        let data = FrameData {
+            character_frame_data: vec![
+                testing::character_frame_data(&character::RYU, vec![with("5LP", "4", "-1"), with("2MP", "5", "-3"), with("623HP", "6", "-30")]),
+                testing::character_frame_data(&character::KEN, vec![
+                    with("2HK", "8", "-12"), with("236HK", "15", "-7"), with("214K", "16", "-5"),
+                    with("5HP", "10", "-4"), with("5MP", "6", "+2"), with("236P", "16", "-6"),
+                ]),
+            ],
+        };
+
+        let drill = DrillGenerator::new(&data, &character::RYU, 7).difficulty(Difficulty::Easy).punish_drill();
+        assert_eq!(drill.prompt, "Punish these 2 moves that are -8 or worse with Ryu's fastest punish");
+        assert!(drill.items.iter().all(|item| item.block_advantage <= -8));
+        let sweep = drill.items.iter().find(|item| item.identifier == "2HK").unwrap();
+        assert_eq!(sweep.answer, DrillAnswer::Punish { identifier: String::from("5LP"), startup: 4, method: PunishMethod::Raw });
+
+        let mut generator = DrillGenerator::new(&data, &character::KEN, 42).difficulty(Difficulty::Hard);
+        let first = generator.safety_drill();
+        assert!(first.items.iter().all(|item| (item.block_advantage - PUNISHABLE_THRESHOLD).abs() <= 2));
+        assert!(first.items.iter().any(|item| item.answer == DrillAnswer::Safe) && first.items.iter().any(|item| item.answer == DrillAnswer::Punishable));
+        let again = DrillGenerator::new(&data, &character::KEN, 42).difficulty(Difficulty::Hard).safety_drill();
+        assert_eq!(first, again);
+    }
+}
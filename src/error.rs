@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::character::CharacterId;
+use crate::framedata::version::GameVersion;
+
+/// Why loading from the wiki failed, for every loading API including the per-character failures
+/// of a [`LoadReport`](crate::framedata::LoadReport), so callers can tell a flaky connection from
+/// a wiki that changed under the scraper
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SF6Error {
+    /// The request failed before a response arrived, e.g. a timeout or connection error
+    Network { url: String, message: String, transient: bool },
+    /// The server answered with an unsuccessful status
+    Http { url: String, status: u16 },
+    /// The character's page doesn't exist on the wiki (anymore), e.g. because it was renamed.
+    /// `character` is the character's id.
+    CharacterPageMissing { character: String, url: String },
+    /// The page was fetched but doesn't have the structure the parser expects, see
+    /// [`canary_check`](crate::framedata::canary_check)
+    ParseStructureChanged { url: String },
+    /// The task loading `character` panicked or was cancelled
+    Task { character: String, message: String },
+    /// The media store or a local file could not be read or written
+    Io(String),
+    /// A downloaded file could not be decoded, e.g. a corrupt gif
+    Media(String),
+    /// A character's page states another game version, or none, than the one asked for.
+    /// `character` is the character's id.
+    VersionMismatch { character: String, expected: GameVersion, found: Option<GameVersion> },
+}
+
+impl SF6Error {
+    #[cfg_attr(not(any(feature = "async", feature = "blocking")), allow(dead_code))]
+    pub(crate) fn from_reqwest(url: &str, error: reqwest::Error) -> Self {
+        if let Some(status) = error.status() {
+            return SF6Error::Http { url: url.to_string(), status: status.as_u16() };
+        }
+        SF6Error::Network {
+            url: url.to_string(),
+            message: error.to_string(),
+            // `is_request` also covers failures that would repeat, like a malformed url or a
+            // redirect loop, so only timeouts and connection errors are worth retrying
+            transient: error.is_timeout() || error.is_connect(),
+        }
+    }
+
+    /// The error for one of `character_id`'s pages, where a missing page becomes
+    /// [`SF6Error::CharacterPageMissing`]
+    #[cfg_attr(not(any(feature = "async", feature = "blocking")), allow(dead_code))]
+    pub(crate) fn for_character(self, character_id: &CharacterId) -> Self {
+        match self {
+            SF6Error::Http { url, status: 404 | 410 } => SF6Error::CharacterPageMissing { character: character_id.id.to_string(), url },
+            error => error,
+        }
+    }
+
+    /// Whether retrying might succeed: timeouts, connection errors, `429 Too Many Requests` and
+    /// 5xx responses
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SF6Error::Network { transient, .. } => *transient,
+            SF6Error::Http { status, .. } => *status == 429 || (500..600).contains(status),
+            _ => false,
+        }
+    }
+}
+
+impl Display for SF6Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SF6Error::Network { url, message, .. } => write!(f, "Request to {} failed: {}", url, message),
+            SF6Error::Http { url, status } => write!(f, "{} responded with status {}", url, status),
+            SF6Error::CharacterPageMissing { character, url } => write!(f, "No page for {} at {}", character, url),
+            SF6Error::ParseStructureChanged { url } => write!(f, "No moves could be parsed from {}, the page structure may have changed", url),
+            SF6Error::Task { character, message } => write!(f, "Loading {} failed: {}", character, message),
+            SF6Error::Io(e) => write!(f, "I/O error: {}", e),
+            SF6Error::Media(e) => write!(f, "Could not decode media: {}", e),
+            SF6Error::VersionMismatch { character, expected, found: Some(found) } => write!(f, "{}'s page is for game version {}, expected {}", character, found, expected),
+            SF6Error::VersionMismatch { character, expected, found: None } => write!(f, "{}'s page states no game version, expected {}", character, expected),
+        }
+    }
+}
+
+impl Error for SF6Error {}
+
+impl From<std::io::Error> for SF6Error {
+    fn from(e: std::io::Error) -> Self {
+        SF6Error::Io(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+
+    #[test]
+    fn classifies_fetch_errors() {
+        let missing = SF6Error::Http { url: character::RYU.frame_data_url(), status: 404 };
+        assert_eq!(missing.clone().for_character(&character::RYU), SF6Error::CharacterPageMissing {
+            character: String::from("ryu"),
+            url: character::RYU.frame_data_url(),
+        });
+        assert!(!missing.is_transient());
+        let status = |status| SF6Error::Http { url: String::new(), status };
+        assert!(status(503).is_transient() && status(429).is_transient());
+        assert_eq!(status(503).for_character(&character::RYU), status(503));
+        assert!(!SF6Error::ParseStructureChanged { url: String::new() }.is_transient());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn retries_only_timeouts_and_connection_errors() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let url = format!("http://127.0.0.1:{}/", port);
+        let refused = reqwest::get(&url).await.unwrap_err();
+        assert!(SF6Error::from_reqwest(&url, refused).is_transient());
+        let malformed = reqwest::get("http://exa mple.com/").await.unwrap_err();
+        assert!(!SF6Error::from_reqwest("http://exa mple.com/", malformed).is_transient());
+    }
+}
@@ -0,0 +1,16 @@
+//! Export profiles that reshape scraped data for specific consumers.
+
+pub mod artifact;
+pub mod cheatsheet;
+#[cfg(feature = "art-bundle")]
+pub mod art_bundle;
+#[cfg(feature = "serde")]
+pub mod http_cache;
+#[cfg(feature = "serde")]
+pub mod overlay;
+#[cfg(feature = "serde")]
+pub mod publish;
+#[cfg(feature = "serde")]
+pub mod slice;
+
+pub use cheatsheet::cheatsheet;
@@ -0,0 +1,108 @@
+//! A zip of character portraits and costume images with a `manifest.json` describing them, for
+//! tournament bracket software that needs local character art without scraping the wiki itself.
+//! Enabled with the `art-bundle` feature; `sf6rs-cli export --art-bundle <file>` writes one.
+//!
+//! Layout:
+//! ```text
+//! manifest.json
+//! <character id>/<file name on the wiki>
+//! ```
+
+use std::io::{self, Seek, Write};
+
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::character::CharacterId;
+use crate::framedata::art::ArtKind;
+use crate::framedata::Loader;
+
+/// The `manifest.json` of an art bundle
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArtManifest {
+    pub characters: Vec<ManifestCharacter>,
+    /// Urls of images that were found but could not be downloaded
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestCharacter {
+    pub id: String,
+    pub name: String,
+    pub images: Vec<ManifestImage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestImage {
+    pub kind: ArtKind,
+    /// Path of the image inside the zip
+    pub file: String,
+    pub source_url: String,
+    pub bytes: usize,
+}
+
+/// Downloads the art of `characters` with `loader` and writes the bundle to `out`. A character
+/// whose overview page can't be loaded is listed with no images; images that fail to download are
+/// listed in [`ArtManifest::failed`]. Entries are stored uncompressed since images already are,
+/// and the archive switches to zip64 where its size or entry count needs it.
+pub async fn write_art_bundle<W: Write + Seek>(loader: &Loader, characters: &[&CharacterId], out: W) -> io::Result<ArtManifest> {
+    let mut zip = ZipWriter::new(out);
+    let mut manifest = ArtManifest::default();
+    for character_id in characters {
+        let art = loader.load_character_art(character_id).await.unwrap_or_default();
+        let mut images = Vec::new();
+        for image in art {
+            let Ok(bytes) = loader.fetch_bytes(&image.url).await else {
+                manifest.failed.push(image.url);
+                continue;
+            };
+            let file = format!("{}/{}", character_id.id, image.name);
+            add(&mut zip, &file, &bytes)?;
+            images.push(ManifestImage {
+                kind: image.kind,
+                file,
+                source_url: image.url,
+                bytes: bytes.len(),
+            });
+        }
+        manifest.characters.push(ManifestCharacter {
+            id: character_id.id.to_string(),
+            name: character_id.name(),
+            images,
+        });
+    }
+    add(&mut zip, "manifest.json", &serde_json::to_vec_pretty(&manifest)?)?;
+    zip.finish()?;
+    Ok(manifest)
+}
+
+fn add<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, data: &[u8]) -> io::Result<()> {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(data.len() as u64 >= u32::MAX as u64);
+    zip.start_file(name, options)?;
+    zip.write_all(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn writes_stored_zip() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        add(&mut zip, "ryu/SF6_Ryu_Portrait.png", b"png bytes").unwrap();
+        add(&mut zip, "manifest.json", b"{}").unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut portrait = archive.by_name("ryu/SF6_Ryu_Portrait.png").unwrap();
+        assert_eq!(portrait.compression(), CompressionMethod::Stored);
+        let mut contents = Vec::new();
+        portrait.read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, b"png bytes");
+    }
+}
@@ -0,0 +1,232 @@
+//! A single flat, memory-mappable lookup artifact for environments where cold start matters (AWS
+//! Lambda, Cloudflare Workers...). The artifact is a sorted index of `<character id>/<identifier>`
+//! slugs over a string pool, so a lookup is a binary search over the raw bytes and fields are
+//! borrowed straight out of the buffer. Nothing is deserialized up front: open the bytes from
+//! `include_bytes!`, `std::fs::read`, or a memory map and query immediately.
+//!
+//! Layout, all integers little-endian `u32`:
+//! ```text
+//! "SF6A" | version | field count | entry count
+//! field names:  field count x (offset, length) into the pool
+//! index:        entry count x (key offset, key length, record offset), sorted by key
+//! records:      entry count x field count x (offset, length) into the pool
+//! string pool:  UTF-8 bytes
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::framedata::{FrameData, Move};
+
+const MAGIC: &[u8; 4] = b"SF6A";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 16;
+const INDEX_ENTRY_LEN: usize = 12;
+const SPAN_LEN: usize = 8;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArtifactError {
+    BadMagic,
+    UnsupportedVersion(u32),
+    Truncated,
+}
+
+impl Display for ArtifactError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactError::BadMagic => write!(f, "Not an sf6rs artifact"),
+            ArtifactError::UnsupportedVersion(v) => write!(f, "Unsupported artifact version {}", v),
+            ArtifactError::Truncated => write!(f, "Artifact is truncated"),
+        }
+    }
+}
+
+impl Error for ArtifactError {}
+
+/// Builds the lookup artifact for every move in `frame_data`. Identifiers are matched
+/// case-insensitively, like [`FrameData::find_move`].
+pub fn write_artifact(frame_data: &FrameData) -> Vec<u8> {
+    let mut entries: BTreeMap<String, &Move> = BTreeMap::new();
+    for character_frame_data in &frame_data.character_frame_data {
+        for move_data in &character_frame_data.moves {
+            entries.entry(slug(&character_frame_data.character_id.id, &move_data.identifier))
+                .or_insert(move_data);
+        }
+    }
+
+    let mut pool = Vec::new();
+    let mut push = |value: &str| {
+        let span = (pool.len() as u32, value.len() as u32);
+        pool.extend_from_slice(value.as_bytes());
+        span
+    };
+    let field_spans = Move::FIELDS.iter().map(|name| push(name)).collect::<Vec<_>>();
+    let mut index = Vec::with_capacity(entries.len());
+    let mut records = Vec::with_capacity(entries.len() * Move::FIELDS.len());
+    for (key, move_data) in &entries {
+        index.push((push(key), (records.len() * SPAN_LEN) as u32));
+        for name in Move::FIELDS {
+            records.push(push(move_data.get(name).unwrap_or_default()));
+        }
+    }
+
+    let records_start = HEADER_LEN + field_spans.len() * SPAN_LEN + index.len() * INDEX_ENTRY_LEN;
+    let pool_start = records_start + records.len() * SPAN_LEN;
+    let mut bytes = Vec::with_capacity(pool_start + pool.len());
+    bytes.extend_from_slice(MAGIC);
+    for value in [VERSION, field_spans.len() as u32, index.len() as u32] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for (offset, len) in field_spans {
+        bytes.extend_from_slice(&offset.to_le_bytes());
+        bytes.extend_from_slice(&len.to_le_bytes());
+    }
+    for ((key_offset, key_len), record_offset) in index {
+        bytes.extend_from_slice(&key_offset.to_le_bytes());
+        bytes.extend_from_slice(&key_len.to_le_bytes());
+        bytes.extend_from_slice(&record_offset.to_le_bytes());
+    }
+    for (offset, len) in records {
+        bytes.extend_from_slice(&offset.to_le_bytes());
+        bytes.extend_from_slice(&len.to_le_bytes());
+    }
+    bytes.extend_from_slice(&pool);
+    bytes
+}
+
+fn slug(character_id: &str, identifier: &str) -> String {
+    format!("{}/{}", character_id, identifier).to_ascii_lowercase()
+}
+
+/// A zero-copy reader over bytes produced by [`write_artifact`]
+#[derive(Debug, Clone, Copy)]
+pub struct Artifact<'a> {
+    bytes: &'a [u8],
+    field_count: usize,
+    entry_count: usize,
+    records_start: usize,
+    pool_start: usize,
+}
+
+impl<'a> Artifact<'a> {
+    /// Validates the header and section bounds. This is constant time; no entries are read.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ArtifactError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(ArtifactError::Truncated);
+        }
+        if &bytes[..4] != MAGIC {
+            return Err(ArtifactError::BadMagic);
+        }
+        let version = read_u32(bytes, 4).ok_or(ArtifactError::Truncated)?;
+        if version != VERSION {
+            return Err(ArtifactError::UnsupportedVersion(version));
+        }
+        let field_count = read_u32(bytes, 8).ok_or(ArtifactError::Truncated)? as usize;
+        let entry_count = read_u32(bytes, 12).ok_or(ArtifactError::Truncated)? as usize;
+        let records_start = HEADER_LEN + field_count * SPAN_LEN + entry_count * INDEX_ENTRY_LEN;
+        let pool_start = records_start + entry_count * field_count * SPAN_LEN;
+        if bytes.len() < pool_start {
+            return Err(ArtifactError::Truncated);
+        }
+        Ok(Artifact {
+            bytes,
+            field_count,
+            entry_count,
+            records_start,
+            pool_start,
+        })
+    }
+
+    /// The number of moves in this artifact
+    pub fn len(&self) -> usize {
+        self.entry_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entry_count == 0
+    }
+
+    /// The field names every record carries, in order
+    pub fn fields(&self) -> impl Iterator<Item = &'a str> + '_ {
+        (0..self.field_count).filter_map(|i| self.span(HEADER_LEN + i * SPAN_LEN))
+    }
+
+    /// Looks up a move by character id and identifier in `O(log n)`
+    pub fn get(&self, character_id: &str, identifier: &str) -> Option<ArtifactMove<'a>> {
+        let key = slug(character_id, identifier);
+        let (mut low, mut high) = (0, self.entry_count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry = HEADER_LEN + self.field_count * SPAN_LEN + mid * INDEX_ENTRY_LEN;
+            let candidate = self.span(entry)?;
+            match candidate.cmp(key.as_str()) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => {
+                    let record = self.records_start + read_u32(self.bytes, entry + 8)? as usize;
+                    return Some(ArtifactMove {
+                        artifact: *self,
+                        record,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Reads an `(offset, length)` pair at `at` and borrows that string out of the pool
+    fn span(&self, at: usize) -> Option<&'a str> {
+        let offset = self.pool_start + read_u32(self.bytes, at)? as usize;
+        let len = read_u32(self.bytes, at + 4)? as usize;
+        std::str::from_utf8(self.bytes.get(offset..offset + len)?).ok()
+    }
+}
+
+/// A move record borrowed from an [`Artifact`]
+#[derive(Debug, Clone, Copy)]
+pub struct ArtifactMove<'a> {
+    artifact: Artifact<'a>,
+    record: usize,
+}
+
+impl<'a> ArtifactMove<'a> {
+    /// Returns a field by name, e.g. `startup`. See [`Artifact::fields`].
+    pub fn field(&self, name: &str) -> Option<&'a str> {
+        let position = self.artifact.fields().position(|f| f == name)?;
+        self.artifact.span(self.record + position * SPAN_LEN)
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    bytes.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn looks_up_written_moves() {
+        let moves = ["5LP", "2MK", "236P"].iter()
+            .map(|identifier| Move {
+                identifier: identifier.to_string(),
+                startup: format!("{}f", identifier.len()),
+                ..Move::default()
+            })
+            .collect();
+        let frame_data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, moves)],
+        };
+        let bytes = write_artifact(&frame_data);
+        let artifact = Artifact::from_bytes(&bytes).unwrap();
+        assert_eq!(artifact.len(), 3);
+        assert_eq!(artifact.get("ryu", "236p").unwrap().field("startup"), Some("4f"));
+        assert_eq!(artifact.get("ryu", "5LP").unwrap().field("identifier"), Some("5LP"));
+        assert!(artifact.get("ryu", "5HP").is_none());
+        assert!(artifact.get("ken", "5LP").is_none());
+        assert_eq!(Artifact::from_bytes(b"nope").unwrap_err(), ArtifactError::Truncated);
+    }
+}
@@ -0,0 +1,171 @@
+//! A one-page sheet of a character's key numbers, the document players otherwise put together by
+//! hand from the wiki: fastest buttons, plus frames, reversals, punishable specials and knockdown
+//! advantage for oki.
+//! ```
+//! use sf6rs::character;
+//! use sf6rs::export::cheatsheet;
+//! use sf6rs::framedata::CharacterFrameData;
+//!
+//! let ryu = CharacterFrameData { character_id: character::RYU.clone(), moves: Vec::new(), gifs: Vec::new(), stats: None };
+//! let sheet = cheatsheet(&ryu);
+//! assert!(sheet.to_markdown().starts_with("# Ryu"));
+//! ```
+
+use crate::analysis::advantage::SAFE_ON_BLOCK;
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::format::style::FormatStyle;
+use crate::framedata::{CharacterFrameData, Move};
+use crate::parsed::Advantage;
+use crate::tags::MoveCategory;
+use crate::viz::escape;
+
+/// How many moves each section lists at most
+pub const SECTION_LENGTH: usize = 6;
+
+/// One titled list of a [`Cheatsheet`], each row a move and its number, e.g. `5LP` and `4f`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheatsheetSection {
+    pub title: String,
+    pub rows: Vec<(String, String)>,
+}
+
+/// A character's key numbers, see [`cheatsheet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cheatsheet {
+    pub character_id: CharacterId,
+    /// Sections in page order, empty sections included so every sheet has the same layout
+    pub sections: Vec<CheatsheetSection>,
+}
+
+/// Builds `character`'s cheat sheet with [`FormatStyle::ENGLISH`] numbers
+pub fn cheatsheet(character: &CharacterFrameData) -> Cheatsheet {
+    cheatsheet_styled(character, &FormatStyle::default())
+}
+
+/// Like [`cheatsheet`], with numbers following `style`
+pub fn cheatsheet_styled(character: &CharacterFrameData, style: &FormatStyle) -> Cheatsheet {
+    let mut fastest = ranked(character, |m| m.category().is_normal().then(|| signed_number(&m.startup)).flatten(), false);
+    fastest.truncate(SECTION_LENGTH);
+
+    let mut plus = ranked(character, |m| m.block_frames().filter(|block| *block > 0), true);
+    plus.truncate(SECTION_LENGTH);
+
+    let mut reversals = ranked(character, |m| m.is_reversal().then(|| signed_number(&m.startup)).flatten(), false);
+    reversals.truncate(SECTION_LENGTH);
+
+    let special = |m: &Move| matches!(m.category(), MoveCategory::Special | MoveCategory::OverDriveSpecial);
+    let mut punishable = ranked(character, |m| m.block_frames().filter(|block| special(m) && *block < SAFE_ON_BLOCK), false);
+    punishable.truncate(SECTION_LENGTH);
+
+    let mut knockdowns = ranked(character, |m| match Advantage::parse(&m.hit_advantage) {
+        Some(Advantage::Knockdown(frames)) => Some(frames),
+        _ => None,
+    }, true);
+    knockdowns.truncate(SECTION_LENGTH);
+
+    let section = |title: &str, moves: Vec<(i32, &Move)>, value: &dyn Fn(i32, &Move) -> String| CheatsheetSection {
+        title: title.to_string(),
+        rows: moves.into_iter().map(|(frames, m)| (m.identifier.clone(), value(frames, m))).collect(),
+    };
+    Cheatsheet {
+        character_id: character.character_id.clone(),
+        sections: vec![
+            section("Fastest buttons", fastest, &|startup, _| style.frames(startup)),
+            section("Plus on block", plus, &|block, _| style.advantage(block)),
+            section("Reversals", reversals, &|startup, m| format!("{}, invincible {}", style.frames(startup), style.value(&m.invuln))),
+            section("Punishable specials", punishable, &|block, _| style.advantage(block)),
+            section("Knockdown advantage", knockdowns, &|frames, _| format!("KD {}", style.advantage(frames))),
+        ],
+    }
+}
+
+/// `character`'s moves with a number from `frames`, lowest first or highest first if
+/// `descending`
+fn ranked(character: &CharacterFrameData, frames: impl Fn(&Move) -> Option<i32>, descending: bool) -> Vec<(i32, &Move)> {
+    let mut moves = character.moves.iter()
+        .filter_map(|m| frames(m).map(|f| (f, m)))
+        .collect::<Vec<(i32, &Move)>>();
+    moves.sort_by_key(|(f, _)| if descending { -f } else { *f });
+    moves
+}
+
+impl Cheatsheet {
+    /// The sheet as a Markdown page, one table per section
+    pub fn to_markdown(&self) -> String {
+        let mut page = format!("# {}\n", self.character_id.name());
+        for section in &self.sections {
+            page.push_str(&format!("\n## {}\n", section.title));
+            if section.rows.is_empty() {
+                page.push_str("\nNone\n");
+                continue;
+            }
+            page.push_str("\n| Move | Frames |\n| --- | --- |\n");
+            for (identifier, value) in &section.rows {
+                page.push_str(&format!("| {} | {} |\n", identifier.replace('|', "\\|"), value.replace('|', "\\|")));
+            }
+        }
+        page
+    }
+
+    /// The sheet as a standalone HTML page, one table per section
+    pub fn to_html(&self) -> String {
+        let name = escape(&self.character_id.name());
+        let mut page = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{} cheat sheet</title>\n</head>\n<body>\n<h1>{}</h1>\n", name, name);
+        for section in &self.sections {
+            page.push_str(&format!("<h2>{}</h2>\n", escape(&section.title)));
+            if section.rows.is_empty() {
+                page.push_str("<p>None</p>\n");
+                continue;
+            }
+            page.push_str("<table>\n<tr><th>Move</th><th>Frames</th></tr>\n");
+            for (identifier, value) in &section.rows {
+                page.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape(identifier), escape(value)));
+            }
+            page.push_str("</table>\n");
+        }
+        page.push_str("</body>\n</html>\n");
+        page
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with(identifier: &str, startup: &str, block: &str, hit: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            hit_advantage: hit.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn builds_cheatsheet() {
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            with("5MP", "6", "+1", "+7"),
+            with("5LP", "4", "-1", "+4"),
+            with("2HK", "8", "-12", "HKD +29"),
+            with("236HK", "15", "-7", "KD +21"),
+            Move { invuln: String::from("1-9 Strike & Throw"), ..with("623HP", "6", "-30", "KD +25") },
+        ]);
+        let sheet = cheatsheet(&ryu);
+        let rows = |title: &str| sheet.sections.iter().find(|s| s.title == title).unwrap().rows.clone();
+        assert_eq!(rows("Fastest buttons"), vec![("5LP".to_string(), "4f".to_string()), ("5MP".to_string(), "6f".to_string()), ("2HK".to_string(), "8f".to_string())]);
+        assert_eq!(rows("Plus on block"), vec![("5MP".to_string(), "+1".to_string())]);
+        assert_eq!(rows("Reversals")[0].1, "6f, invincible 1-9 Strike & Throw");
+        assert_eq!(rows("Punishable specials").iter().map(|(id, _)| id.as_str()).collect::<Vec<&str>>(), vec!["623HP", "236HK"]);
+        assert_eq!(rows("Knockdown advantage")[0], ("2HK".to_string(), "KD +29".to_string()));
+
+        let markdown = sheet.to_markdown();
+        assert!(markdown.contains("## Plus on block\n\n| Move | Frames |\n| --- | --- |\n| 5MP | +1 |\n"));
+        assert!(sheet.to_html().contains("<td>6f, invincible 1-9 Strike &amp; Throw</td>"));
+    }
+}
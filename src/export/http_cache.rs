@@ -0,0 +1,166 @@
+//! Caching headers for serving exported data over HTTP, so an API put behind a CDN or polled by
+//! bots only sends a body when the data actually changed. This crate has no server of its own;
+//! whatever framework serves the data builds a [`CachedBody`] once per refresh and answers each
+//! request with [`CachedBody::respond`].
+//! ```
+//! # let data = sf6rs::framedata::FrameData { character_frame_data: Vec::new() };
+//! use sf6rs::export::http_cache::{CachedBody, CachePolicy};
+//!
+//! let body = CachedBody::json(&data, &CachePolicy::default()).unwrap();
+//! let if_none_match = Some(body.etag.clone());
+//! assert!(body.respond(if_none_match.as_deref()).is_not_modified());
+//! ```
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::framedata::media::store::sha256_hex;
+
+/// How long clients and shared caches may reuse a response before revalidating it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// How long browsers and bots may reuse a response
+    pub max_age: Duration,
+    /// How long shared caches like CDNs may reuse a response, `None` to follow `max_age`
+    pub shared_max_age: Option<Duration>,
+    /// How long a stale response may still be served while it is revalidated in the background
+    pub stale_while_revalidate: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// The `Cache-Control` header value for this policy
+    pub fn cache_control(&self) -> String {
+        let mut directives = vec![String::from("public"), format!("max-age={}", self.max_age.as_secs())];
+        if let Some(shared_max_age) = self.shared_max_age {
+            directives.push(format!("s-maxage={}", shared_max_age.as_secs()));
+        }
+        if let Some(stale) = self.stale_while_revalidate {
+            directives.push(format!("stale-while-revalidate={}", stale.as_secs()));
+        }
+        directives.join(", ")
+    }
+}
+
+impl Default for CachePolicy {
+    /// Frame data changes a few times a year, but clients should still notice a refresh within
+    /// minutes: five minutes for clients, an hour for CDNs, which revalidate cheaply by ETag
+    fn default() -> Self {
+        CachePolicy {
+            max_age: Duration::from_secs(5 * 60),
+            shared_max_age: Some(Duration::from_secs(60 * 60)),
+            stale_while_revalidate: Some(Duration::from_secs(24 * 60 * 60)),
+        }
+    }
+}
+
+/// A response body along with the headers that make it cacheable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedBody {
+    pub body: Vec<u8>,
+    pub content_type: &'static str,
+    /// A strong entity tag, the SHA-256 of `body`, quoted as it appears in the `ETag` header
+    pub etag: String,
+    pub cache_control: String,
+}
+
+/// How to answer a request for a [`CachedBody`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CachedResponse<'a> {
+    /// `200 OK` with the body
+    Full(&'a CachedBody),
+    /// `304 Not Modified`, the client's copy is current. The headers are still sent but the body
+    /// isn't.
+    NotModified(&'a CachedBody),
+}
+
+impl CachedResponse<'_> {
+    pub fn status(&self) -> u16 {
+        match self {
+            CachedResponse::Full(_) => 200,
+            CachedResponse::NotModified(_) => 304,
+        }
+    }
+
+    pub fn is_not_modified(&self) -> bool {
+        matches!(self, CachedResponse::NotModified(_))
+    }
+
+    /// The body to send, empty for [`CachedResponse::NotModified`]
+    pub fn body(&self) -> &[u8] {
+        match self {
+            CachedResponse::Full(body) => &body.body,
+            CachedResponse::NotModified(_) => &[],
+        }
+    }
+
+    /// The headers to send, as `(name, value)` pairs
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let body = match self {
+            CachedResponse::Full(body) | CachedResponse::NotModified(body) => body,
+        };
+        let mut headers = vec![
+            ("ETag", body.etag.clone()),
+            ("Cache-Control", body.cache_control.clone()),
+        ];
+        if let CachedResponse::Full(body) = self {
+            headers.push(("Content-Type", body.content_type.to_string()));
+        }
+        headers
+    }
+}
+
+impl CachedBody {
+    pub fn new(body: Vec<u8>, content_type: &'static str, policy: &CachePolicy) -> Self {
+        let etag = format!("\"{}\"", sha256_hex(&body));
+        CachedBody {
+            body,
+            content_type,
+            etag,
+            cache_control: policy.cache_control(),
+        }
+    }
+
+    /// Serializes `value` as the JSON body
+    pub fn json<T: Serialize>(value: &T, policy: &CachePolicy) -> serde_json::Result<Self> {
+        Ok(CachedBody::new(serde_json::to_vec(value)?, "application/json", policy))
+    }
+
+    /// Answers a request carrying the given `If-None-Match` header, see [`CachedResponse`]
+    pub fn respond(&self, if_none_match: Option<&str>) -> CachedResponse<'_> {
+        match if_none_match {
+            Some(if_none_match) if self.matches(if_none_match) => CachedResponse::NotModified(self),
+            _ => CachedResponse::Full(self),
+        }
+    }
+
+    /// Whether an `If-None-Match` header names this body's ETag. Comparison is weak, as RFC 9110
+    /// requires for `If-None-Match`, so a CDN's `W/` prefixed copy of the tag still matches.
+    fn matches(&self, if_none_match: &str) -> bool {
+        if_none_match.split(',')
+            .map(|tag| tag.trim())
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == self.etag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_conditional_requests() {
+        let body = CachedBody::new(b"{}".to_vec(), "application/json", &CachePolicy::default());
+        assert_eq!(body.cache_control, "public, max-age=300, s-maxage=3600, stale-while-revalidate=86400");
+        assert_eq!(body.respond(None).status(), 200);
+        assert_eq!(body.respond(Some("\"other\"")).status(), 200);
+        let not_modified = body.respond(Some(&format!("\"other\", W/{}", body.etag)));
+        assert!(not_modified.is_not_modified());
+        assert!(not_modified.body().is_empty());
+        assert_eq!(not_modified.headers().len(), 2);
+        assert!(body.respond(Some("*")).is_not_modified());
+
+        let changed = CachedBody::new(b"{\"a\":1}".to_vec(), "application/json", &CachePolicy::default());
+        assert_ne!(changed.etag, body.etag);
+        assert_eq!(body.etag, "\"44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a\"");
+    }
+}
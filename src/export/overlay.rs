@@ -0,0 +1,139 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::framedata::{CharacterFrameData, FrameData, Move, MoveGif};
+#[cfg(feature = "async")]
+use crate::framedata::refresh::{self, RefreshProfile};
+#[cfg(feature = "async")]
+use crate::framedata::{LoadReport, Loader};
+
+/// A compact, display-only view of a character's frame data intended for streaming overlays such
+/// as OBS browser sources. Only fields that are shown on screen are kept.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayCharacter {
+    pub character: String,
+    pub name: String,
+    pub moves: Vec<OverlayMove>,
+}
+
+/// A single move as displayed on an overlay panel
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayMove {
+    pub identifier: String,
+    pub name: String,
+    pub startup: String,
+    pub active: String,
+    pub recovery: String,
+    pub on_hit: String,
+    pub on_block: String,
+    pub damage: String,
+    pub image_url: String,
+    pub image_alt: String,
+    pub gif_url: Option<String>,
+}
+
+impl OverlayMove {
+    fn new(move_data: &Move, gif: Option<&MoveGif>) -> Self {
+        OverlayMove {
+            identifier: move_data.identifier.clone(),
+            name: move_data.name.clone(),
+            startup: move_data.startup.clone(),
+            active: move_data.active.clone(),
+            recovery: move_data.recovery.clone(),
+            on_hit: move_data.hit_advantage.clone(),
+            on_block: move_data.block_advantage.clone(),
+            damage: move_data.damage.clone(),
+            image_url: move_data.image_link.clone(),
+            image_alt: move_data.image_alt.clone(),
+            gif_url: gif.map(|g| g.url.clone()),
+        }
+    }
+}
+
+/// Builds the overlay view of one character's frame data, pairing moves with their gifs by
+/// [`CharacterFrameData::gif_for`]
+pub fn overlay(character_frame_data: &CharacterFrameData) -> OverlayCharacter {
+    OverlayCharacter {
+        character: character_frame_data.character_id.id.to_string(),
+        name: character_frame_data.character_id.name(),
+        moves: character_frame_data.moves.iter()
+            .map(|m| OverlayMove::new(m, character_frame_data.gif_for(m)))
+            .collect(),
+    }
+}
+
+/// Writes one `<character id>.json` overlay file per character into `dir`, creating it if needed,
+/// and returns the written paths. Each file is written to a temporary path and renamed into place
+/// so browser sources polling the directory never read a half-written file.
+pub fn write_overlay_files(frame_data: &FrameData, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for character_frame_data in &frame_data.character_frame_data {
+        let file_stem = character_frame_data.character_id.file_stem()?;
+        let json = serde_json::to_vec_pretty(&overlay(character_frame_data))?;
+        let path = dir.join(format!("{}.json", file_stem));
+        let tmp = dir.join(format!(".{}.json.tmp", file_stem));
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Keeps the overlay files in `dir` current: loads with `loader` on `profile`'s schedule through
+/// [`refresh::watch`] and rewrites the files after every refresh. Characters that fail to load
+/// keep their previous file. `on_write` is handed each refresh's report and the written paths,
+/// and watching stops once it returns `false`.
+#[cfg(feature = "async")]
+pub async fn watch_overlay_files<F>(loader: Loader, profile: RefreshProfile, dir: &Path, mut on_write: F)
+where
+    F: FnMut(&LoadReport, io::Result<Vec<PathBuf>>) -> bool,
+{
+    refresh::watch(loader, profile, |report| {
+        let written = write_overlay_files(&report.data, dir);
+        on_write(&report, written)
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn builds_overlay_view() {
+        let mut character_frame_data = testing::character_frame_data(&character::RYU, vec![Move {
+            identifier: String::from("5LP"),
+            name: String::from("Standing Light Punch"),
+            startup: String::from("4"),
+            hit_advantage: String::from("+4"),
+            block_advantage: String::from("-1"),
+            ..Move::default()
+        }]);
+        character_frame_data.gifs = vec![
+            MoveGif {
+                name: String::from("standing light punch"),
+                move_identifier: None,
+                url: String::from("https://example.com/jump-lp.gif"),
+                thumbnail_url: None,
+                info: Default::default(),
+            },
+            MoveGif {
+                name: String::from("5lp (close)"),
+                move_identifier: Some(String::from("5LP")),
+                url: String::from("https://example.com/5lp.gif"),
+                thumbnail_url: None,
+                info: Default::default(),
+            },
+        ];
+        let overlay = overlay(&character_frame_data);
+        assert_eq!(overlay.character, "ryu");
+        assert_eq!(overlay.moves[0].on_hit, "+4");
+        assert_eq!(overlay.moves[0].on_block, "-1");
+        assert_eq!(overlay.moves[0].gif_url.as_deref(), Some("https://example.com/5lp.gif"));
+    }
+}
@@ -0,0 +1,240 @@
+//! A versioned directory of JSON artifacts for hosting a public mirror of the data on GitHub
+//! Pages, S3 or any static file host, so communities can serve data built from this crate without
+//! everyone scraping the wiki:
+//! ```text
+//! index.json                      the latest version and every published one
+//! <version>/manifest.json         the version's files with their SHA-256 and size
+//! <version>/framedata.json        the whole dataset, see FrameData::to_json
+//! <version>/characters/<id>.json  one character's CharacterFrameData
+//! <version>/schema.json           a JSON Schema of framedata.json
+//! <version>/SHA256SUMS            the manifest's checksums, for sha256sum -c
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::framedata::json::SCHEMA_VERSION;
+use crate::framedata::media::store::sha256_hex;
+use crate::framedata::verify::Deviation;
+use crate::framedata::version::GameVersion;
+use crate::framedata::{CharacterStats, FrameData, Move};
+
+pub const INDEX_FILE: &str = "index.json";
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const DATA_FILE: &str = "framedata.json";
+pub const SCHEMA_FILE: &str = "schema.json";
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// The `index.json` at the root of a mirror
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorIndex {
+    /// The directory of the most recently published version
+    pub latest: String,
+    /// Every published version's directory, sorted
+    pub versions: Vec<String>,
+}
+
+/// The `manifest.json` of one published version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorManifest {
+    pub version: String,
+    /// The [`SCHEMA_VERSION`] of `framedata.json`
+    pub schema_version: u32,
+    /// The version of the crate that published it, for debugging only
+    pub generator: String,
+    /// The ids of the published characters
+    pub characters: Vec<String>,
+    pub files: Vec<MirrorFile>,
+}
+
+/// One file of a [`MirrorManifest`], with its path relative to the version's directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorFile {
+    pub path: String,
+    pub sha256: String,
+    pub bytes: u64,
+}
+
+/// Why a version couldn't be published
+#[derive(Debug)]
+pub enum PublishError {
+    Io(io::Error),
+    /// There is no character to publish
+    Empty,
+    /// Scraped values disagree with the regression corpus, see
+    /// [`FrameData::verify_against_corpus`]
+    Deviations(Vec<Deviation>),
+}
+
+impl Display for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PublishError::Io(e) => write!(f, "Could not write mirror: {}", e),
+            PublishError::Empty => write!(f, "No frame data to publish"),
+            PublishError::Deviations(deviations) => write!(f, "{} scraped values disagree with the regression corpus", deviations.len()),
+        }
+    }
+}
+
+impl Error for PublishError {}
+
+impl From<io::Error> for PublishError {
+    fn from(e: io::Error) -> Self {
+        PublishError::Io(e)
+    }
+}
+
+/// Validates `frame_data` against the regression corpus and writes it to `dir` as `version`, then
+/// makes it the latest version in `index.json`. Versions already in `dir` are kept; publishing a
+/// version again replaces it.
+pub fn publish(frame_data: &FrameData, dir: &Path, version: &GameVersion) -> Result<MirrorManifest, PublishError> {
+    if frame_data.character_frame_data.is_empty() {
+        return Err(PublishError::Empty);
+    }
+    let deviations = frame_data.verify_against_corpus(None);
+    if !deviations.is_empty() {
+        return Err(PublishError::Deviations(deviations));
+    }
+
+    let key = version.key();
+    let version_dir = dir.join(&key);
+    fs::create_dir_all(version_dir.join("characters"))?;
+    let mut files = Vec::new();
+    let mut write = |path: String, contents: String| -> io::Result<()> {
+        fs::write(version_dir.join(&path), &contents)?;
+        files.push(MirrorFile {
+            sha256: sha256_hex(contents.as_bytes()),
+            bytes: contents.len() as u64,
+            path,
+        });
+        Ok(())
+    };
+    write(DATA_FILE.to_string(), frame_data.to_json() + "\n")?;
+    let mut characters = frame_data.character_frame_data.iter().collect::<Vec<_>>();
+    characters.sort_by(|a, b| a.character_id.id.cmp(&b.character_id.id));
+    for character in &characters {
+        let json = serde_json::to_string_pretty(character).expect("frame data serializes to JSON");
+        write(format!("characters/{}.json", character.character_id.id), json + "\n")?;
+    }
+    write(SCHEMA_FILE.to_string(), serde_json::to_string_pretty(&schema()).expect("schema serializes to JSON") + "\n")?;
+
+    let checksums = files.iter().map(|file| format!("{}  {}\n", file.sha256, file.path)).collect::<String>();
+    fs::write(version_dir.join(CHECKSUMS_FILE), checksums)?;
+    let manifest = MirrorManifest {
+        version: version.to_string(),
+        schema_version: SCHEMA_VERSION,
+        generator: concat!("sf6rs ", env!("CARGO_PKG_VERSION")).to_string(),
+        characters: characters.iter().map(|c| c.character_id.id.to_string()).collect(),
+        files,
+    };
+    fs::write(version_dir.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest).expect("manifest serializes to JSON") + "\n")?;
+
+    let mut index = match fs::read_to_string(dir.join(INDEX_FILE)) {
+        Ok(json) => serde_json::from_str(&json).map_err(io::Error::from)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => MirrorIndex::default(),
+        Err(e) => return Err(e.into()),
+    };
+    if !index.versions.contains(&key) {
+        index.versions.push(key.clone());
+        index.versions.sort();
+    }
+    index.latest = key;
+    // the index is what readers look at first, so it's swapped in only once the version is complete
+    let tmp = dir.join(format!("{}.tmp", INDEX_FILE));
+    fs::write(&tmp, serde_json::to_string_pretty(&index).expect("index serializes to JSON") + "\n")?;
+    fs::rename(&tmp, dir.join(INDEX_FILE))?;
+    Ok(manifest)
+}
+
+/// A JSON Schema of the document [`FrameData::to_json`] writes
+pub fn schema() -> serde_json::Value {
+    let strings = |fields: &[&str]| fields.iter()
+        .map(|field| (field.to_string(), json!({ "type": "string" })))
+        .collect::<serde_json::Map<String, serde_json::Value>>();
+    let optional_integer = json!({ "type": ["integer", "null"], "minimum": 0 });
+    let mut move_properties = strings(&Move::FIELDS);
+    move_properties.insert(String::from("modern_input"), json!({ "type": ["string", "null"] }));
+    move_properties.insert(String::from("category"), json!({ "type": ["string", "null"] }));
+    move_properties.insert(String::from("cancel_options"), json!({
+        "type": "object",
+        "properties": {
+            "chain": { "type": "boolean" },
+            "special": { "type": "boolean" },
+            "super_arts": { "type": "array", "items": { "type": "boolean" }, "minItems": 3, "maxItems": 3 },
+            "target_combo": { "type": "boolean" },
+            "drive_rush": { "type": "boolean" },
+        },
+    }));
+    move_properties.insert(String::from("image_info"), json!({
+        "type": ["object", "null"],
+        "properties": {
+            "content_type": { "type": ["string", "null"] },
+            "width": optional_integer,
+            "height": optional_integer,
+            "bytes": optional_integer,
+        },
+    }));
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "sf6rs frame data",
+        "type": "object",
+        "required": ["schema_version", "character_frame_data"],
+        "properties": {
+            "schema_version": { "const": SCHEMA_VERSION },
+            "generator": { "type": "string" },
+            "character_frame_data": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["character_id", "moves", "gifs"],
+                    "properties": {
+                        "character_id": { "type": "string" },
+                        "moves": {
+                            "type": "array",
+                            "items": { "type": "object", "required": ["identifier"], "properties": move_properties },
+                        },
+                        "gifs": { "type": "array", "items": { "type": "object" } },
+                        "stats": {
+                            "type": ["object", "null"],
+                            "properties": strings(&CharacterStats::FIELDS),
+                        },
+                    },
+                },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    #[test]
+    fn publishes_versioned_mirror() {
+        let dir = TempDir::new("sf6rs-publish-test");
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::JP, vec![Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() }])],
+        };
+        let manifest = publish(&data, &dir, &GameVersion::new("2024/06/26")).unwrap();
+        publish(&data, &dir, &GameVersion::new("2024.03.01")).unwrap();
+
+        let index: MirrorIndex = serde_json::from_str(&fs::read_to_string(dir.join(INDEX_FILE)).unwrap()).unwrap();
+        assert_eq!(index, MirrorIndex { latest: String::from("2024.03.01"), versions: vec![String::from("2024-06-26"), String::from("2024.03.01")] });
+        assert_eq!(manifest.files.iter().map(|f| f.path.as_str()).collect::<Vec<&str>>(), vec![DATA_FILE, "characters/jp.json", SCHEMA_FILE]);
+        let bytes = fs::read(dir.join("2024-06-26").join(DATA_FILE)).unwrap();
+        assert_eq!(manifest.files[0].sha256, sha256_hex(&bytes));
+        assert!(fs::read_to_string(dir.join("2024-06-26").join(CHECKSUMS_FILE)).unwrap().ends_with("  schema.json\n"));
+
+        let empty = FrameData { character_frame_data: Vec::new() };
+        assert!(matches!(publish(&empty, &dir, &GameVersion::new("1")), Err(PublishError::Empty)));
+    }
+}
@@ -0,0 +1,125 @@
+//! Exports a slice of frame data, only the characters, fields and move categories asked for, as
+//! JSON. This backs the `sf6rs-cli export` command line tool.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::{Map, Value};
+
+use crate::character::{self, CharacterId};
+use crate::framedata::{FrameData, Move};
+use crate::tags::MoveCategory;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SliceError {
+    UnknownCharacter(String),
+    UnknownField(String),
+    UnknownCategory(String),
+}
+
+impl Display for SliceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SliceError::UnknownCharacter(c) => write!(f, "Unknown character `{}`", c),
+            SliceError::UnknownField(field) => write!(f, "Unknown field `{}`", field),
+            SliceError::UnknownCategory(c) => write!(f, "Unknown category `{}`", c),
+        }
+    }
+}
+
+impl Error for SliceError {}
+
+/// Which part of the data to export. `None` means everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Slice {
+    pub characters: Option<Vec<&'static CharacterId>>,
+    pub fields: Option<Vec<&'static str>>,
+    pub categories: Option<Vec<MoveCategory>>,
+}
+
+impl Slice {
+    /// Keeps only characters matched by these comma separated queries, e.g. `ryu,chun-li`
+    pub fn characters(mut self, queries: &str) -> Result<Self, SliceError> {
+        let characters = split(queries)
+            .map(|q| character::get_character_by_regex(q).ok_or_else(|| SliceError::UnknownCharacter(q.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.characters = Some(characters);
+        Ok(self)
+    }
+
+    /// Keeps only these comma separated [`Move`] fields, e.g. `identifier,startup,block_advantage`
+    pub fn fields(mut self, names: &str) -> Result<Self, SliceError> {
+        let fields = split(names)
+            .map(|name| Move::FIELDS.iter().copied().find(|f| *f == name).ok_or_else(|| SliceError::UnknownField(name.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.fields = Some(fields);
+        Ok(self)
+    }
+
+    /// Keeps only moves in these comma separated categories, e.g. `normals,specials`
+    pub fn categories(mut self, names: &str) -> Result<Self, SliceError> {
+        let categories = split(names)
+            .map(|name| MoveCategory::from_name(name).ok_or_else(|| SliceError::UnknownCategory(name.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.categories = Some(categories);
+        Ok(self)
+    }
+
+    /// The moves in the slice, grouped by character in the order they're loaded
+    pub fn moves<'a>(&self, frame_data: &'a FrameData) -> Vec<(&'a CharacterId, Vec<&'a Move>)> {
+        frame_data.character_frame_data.iter()
+            .filter(|c| self.characters.as_ref().is_none_or(|characters| characters.contains(&&c.character_id)))
+            .map(|c| {
+                let moves = c.moves.iter()
+                    .filter(|m| self.categories.as_ref().is_none_or(|categories| categories.contains(&m.category())))
+                    .collect();
+                (&c.character_id, moves)
+            })
+            .collect()
+    }
+
+    /// Exports the slice as `{"<character id>": [{"<field>": "<value>", ...}, ...], ...}`
+    pub fn to_json(&self, frame_data: &FrameData) -> Value {
+        let fields = self.fields.as_deref().unwrap_or(&Move::FIELDS);
+        let mut characters = Map::new();
+        for (character_id, moves) in self.moves(frame_data) {
+            let moves = moves.iter()
+                .map(|m| {
+                    let values = fields.iter()
+                        .map(|field| (field.to_string(), Value::from(m.get(field).unwrap_or_default())))
+                        .collect::<Map<String, Value>>();
+                    Value::Object(values)
+                })
+                .collect();
+            characters.insert(character_id.id.to_string(), Value::Array(moves));
+        }
+        Value::Object(characters)
+    }
+}
+
+fn split(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn exports_slice() {
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![
+                Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() },
+                Move { identifier: String::from("236P"), startup: String::from("16"), ..Move::default() },
+            ])],
+        };
+        let slice = Slice::default()
+            .characters("ryu").unwrap()
+            .fields("identifier, startup").unwrap()
+            .categories("specials").unwrap();
+        assert_eq!(slice.to_json(&data), serde_json::json!({"ryu": [{"identifier": "236P", "startup": "16"}]}));
+        assert_eq!(Slice::default().characters("ryu,nobody"), Err(SliceError::UnknownCharacter(String::from("nobody"))));
+        assert_eq!(Slice::default().fields("frames"), Err(SliceError::UnknownField(String::from("frames"))));
+    }
+}
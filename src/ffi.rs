@@ -0,0 +1,126 @@
+//! A C ABI over the data layer, enabled with the `ffi` feature. Build a shared or static library
+//! with `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`) and generate a
+//! header with `cbindgen --config cbindgen.toml`.
+//!
+//! Data is loaded from a JSON snapshot of [`FrameData`] rather than scraped, so C, C++ and C#
+//! tools never need an async runtime. Every pointer returned by this module is owned by the
+//! caller unless documented as borrowed, and must be released with the matching `*_free` function.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::framedata::{FrameData, Move};
+
+/// An opaque handle to a loaded [`FrameData`]
+pub struct Sf6FrameData(FrameData);
+
+/// An opaque handle to a [`Move`] borrowed from an [`Sf6FrameData`]
+#[repr(transparent)]
+pub struct Sf6Move(Move);
+
+/// Loads frame data from a NUL-terminated UTF-8 JSON snapshot. Returns null if the string is not
+/// valid UTF-8 or not a valid snapshot.
+///
+/// # Safety
+/// `json` must be null or a valid pointer to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sf6_frame_data_from_json(json: *const c_char) -> *mut Sf6FrameData {
+    let Some(json) = str_arg(json) else {
+        return ptr::null_mut();
+    };
+    match FrameData::from_json(json) {
+        Ok(frame_data) => Box::into_raw(Box::new(Sf6FrameData(frame_data))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Releases frame data returned by [`sf6_frame_data_from_json`]. Any [`Sf6Move`] borrowed from it
+/// becomes invalid.
+///
+/// # Safety
+/// `frame_data` must be null or a pointer returned by [`sf6_frame_data_from_json`] that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn sf6_frame_data_free(frame_data: *mut Sf6FrameData) {
+    if !frame_data.is_null() {
+        drop(Box::from_raw(frame_data));
+    }
+}
+
+/// Finds a move by character query and move identifier, see [`FrameData::find_move`]. The result
+/// is borrowed from `frame_data` and must not be freed. Returns null if nothing matches.
+///
+/// # Safety
+/// `frame_data` must be null or a live pointer from [`sf6_frame_data_from_json`], and the queries
+/// must be null or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn sf6_find_move(frame_data: *const Sf6FrameData, character: *const c_char, move_query: *const c_char) -> *const Sf6Move {
+    let (Some(frame_data), Some(character), Some(move_query)) = (frame_data.as_ref(), str_arg(character), str_arg(move_query)) else {
+        return ptr::null();
+    };
+    match frame_data.0.find_move(character, move_query) {
+        // Sf6Move is a transparent wrapper so the cast keeps the borrow pointing into frame_data
+        Ok(found) => (found as *const Move).cast::<Sf6Move>(),
+        Err(_) => ptr::null(),
+    }
+}
+
+/// Returns a field of a move as a newly allocated UTF-8 string, e.g. `startup` or
+/// `block_advantage`. Returns null for unknown fields. Free the result with [`sf6_string_free`].
+///
+/// # Safety
+/// `move_data` must be null or a live pointer from [`sf6_find_move`], and `field` must be null or
+/// a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sf6_move_field(move_data: *const Sf6Move, field: *const c_char) -> *mut c_char {
+    let (Some(move_data), Some(field)) = (move_data.as_ref(), str_arg(field)) else {
+        return ptr::null_mut();
+    };
+    move_data.0.get(field)
+        .and_then(|value| CString::new(value).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this module
+///
+/// # Safety
+/// `string` must be null or a pointer returned by this module that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn sf6_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+unsafe fn str_arg<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn finds_moves_through_c_abi() {
+        let json = CString::new(testing::ryu_jab().to_json()).unwrap();
+        let character = CString::new("ryu").unwrap();
+        let move_query = CString::new("5lp").unwrap();
+        let field = CString::new("startup").unwrap();
+        unsafe {
+            let frame_data = sf6_frame_data_from_json(json.as_ptr());
+            assert!(!frame_data.is_null());
+            let found = sf6_find_move(frame_data, character.as_ptr(), move_query.as_ptr());
+            assert!(!found.is_null());
+            let startup = sf6_move_field(found, field.as_ptr());
+            assert_eq!(CStr::from_ptr(startup).to_str(), Ok("4"));
+            sf6_string_free(startup);
+            assert!(sf6_find_move(frame_data, character.as_ptr(), ptr::null()).is_null());
+            sf6_frame_data_free(frame_data);
+            assert!(sf6_frame_data_from_json(ptr::null()).is_null());
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use regex::Regex;
+
+use crate::character::CharacterId;
+use crate::framedata::{CharacterFrameData, Move, DEFAULT_IMAGE};
+use crate::LazyLock;
+use crate::format::style::FormatStyle;
+use crate::parsed::{CancelOptions, CancelType};
+
+pub mod context;
+pub mod formatter;
+pub mod style;
+
+static NUMPAD_INPUT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^(j\.)?(\[?[1-9]\]?[0-9]*)?(LP|MP|HP|LK|MK|HK|PPP|KKK|PP|KK|P|K)$").unwrap());
+static LEADING_NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*([+-]?)\s*(\d+)").unwrap());
+
+/// Produces a natural-language description of a move for text-to-speech engines, screen readers,
+/// and voice assistants. Numbers are spelled out and notation is expanded, e.g.
+/// `Ryu's standing medium punch: six frame startup, plus one on block, plus four on hit.`
+///
+/// Fields the wiki leaves empty are skipped rather than read out as symbols.
+pub fn speech(character: &CharacterId, move_data: &Move) -> String {
+    let subject = match speak_input(&move_data.identifier) {
+        Some(spoken) if is_normal(&move_data.identifier) => spoken,
+        Some(spoken) => format!("{}, {}", move_data.name, spoken),
+        None => move_data.name.clone(),
+    };
+    let mut clauses = Vec::new();
+    if let Some((_, startup)) = leading_number(&move_data.startup) {
+        clauses.push(format!("{} frame startup", number_words(startup)));
+    }
+    if let Some(block) = speak_advantage(&move_data.block_advantage) {
+        clauses.push(format!("{} on block", block));
+    }
+    if let Some(hit) = speak_advantage(&move_data.hit_advantage) {
+        clauses.push(format!("{} on hit", hit));
+    }
+    if let Some((_, damage)) = leading_number(&move_data.damage) {
+        clauses.push(format!("{} damage", number_words(damage)));
+    }
+    if clauses.is_empty() {
+        return format!("{}'s {}. No frame data available.", character.name(), subject);
+    }
+    format!("{}'s {}: {}.", character.name(), subject, clauses.join(", "))
+}
+
+/// Produces descriptive alt text for a move's `image_link`, e.g.
+/// `Ryu Crouching Heavy Punch (2HP) hitbox, active frames`. Moves without an image on the wiki
+/// fall back to the game logo, which is described as such.
+pub fn alt_text(character: &CharacterId, move_data: &Move) -> String {
+    if move_data.image_link == DEFAULT_IMAGE {
+        return format!("Street Fighter 6 logo, no image available for {} {}", character.name(), move_data.identifier);
+    }
+    let subject = if move_data.name.is_empty() || move_data.name.eq_ignore_ascii_case(&move_data.identifier) {
+        format!("{} {}", character.name(), move_data.identifier)
+    } else {
+        format!("{} {} ({})", character.name(), move_data.name, move_data.identifier)
+    };
+    if move_data.image_link.to_ascii_lowercase().contains("hitbox") {
+        format!("{} hitbox, active frames", subject)
+    } else {
+        format!("{} animation frame", subject)
+    }
+}
+
+/// The fastest normal in SF6 is 4 frames, so anything at or below this on block can be punished
+pub(crate) const PUNISHABLE_THRESHOLD: i32 = -4;
+
+/// Produces a two-line summary of a move for commentary: the first line states the move with its
+/// startup and whether it is punishable, the second lists reversal properties and cancel options.
+/// ```text
+/// Ryu 623HP (Shoryuken): 6f startup, -30 on block, PUNISHABLE
+/// Invincible reversal (1-9 Full) | Cancels into Super
+/// ```
+pub fn broadcast(character: &CharacterId, move_data: &Move) -> String {
+    broadcast_styled(character, move_data, &FormatStyle::default())
+}
+
+/// Like [`broadcast`], with numbers and punctuation following `style`
+pub fn broadcast_styled(character: &CharacterId, move_data: &Move, style: &FormatStyle) -> String {
+    let subject = format!("{} {} ({})", character.name(), move_data.identifier, move_data.name);
+    let mut facts = Vec::new();
+    if let Some(startup) = signed_number(&move_data.startup) {
+        facts.push(format!("{} startup", style.frames(startup)));
+    }
+    if let Some(block) = signed_number(&move_data.block_advantage) {
+        facts.push(format!("{} on block", style.advantage(block)));
+        facts.push(String::from(if block <= PUNISHABLE_THRESHOLD { "PUNISHABLE" } else { "safe" }));
+    }
+    let first = if facts.is_empty() { subject } else { style.labeled(&subject, &style.list(&facts)) };
+
+    let mut second = Vec::new();
+    if move_data.is_reversal() {
+        second.push(format!("Invincible reversal ({})", move_data.invuln));
+    }
+    let cancels = cancel_options(&move_data.cancel_options);
+    if cancels.is_empty() {
+        second.push(String::from("No cancels"));
+    } else {
+        second.push(format!("Cancels into {}", style.list(&cancels)));
+    }
+    format!("{}\n{}", first, second.join(" | "))
+}
+
+/// Produces a short head-to-head reference sheet for a matchup: each side's fastest normal,
+/// reversals, and most punishable moves on block.
+pub fn matchup_quickref(a: &CharacterFrameData, b: &CharacterFrameData) -> String {
+    matchup_quickref_styled(a, b, &FormatStyle::default())
+}
+
+/// Like [`matchup_quickref`], with numbers and punctuation following `style`
+pub fn matchup_quickref_styled(a: &CharacterFrameData, b: &CharacterFrameData, style: &FormatStyle) -> String {
+    let mut sheet = format!("{} vs {}", a.character_id.name(), b.character_id.name());
+    for side in [a, b] {
+        sheet.push_str(&format!("\n\n{}", side.character_id.name()));
+
+        let fastest = side.moves.iter()
+            .filter(|m| is_normal(&m.identifier))
+            .filter_map(|m| signed_number(&m.startup).map(|s| (s, m)))
+            .min_by_key(|(s, _)| *s);
+        if let Some((startup, m)) = fastest {
+            sheet.push_str(&format!("\n{}", style.labeled("Fastest normal", &format!("{} ({})", m.identifier, style.frames(startup)))));
+        }
+
+        let reversals = side.moves.iter()
+            .filter(|m| m.is_reversal())
+            .map(|m| m.identifier.as_str())
+            .collect::<Vec<&str>>();
+        let reversals = if reversals.is_empty() { String::from("none") } else { style.list(&reversals) };
+        sheet.push_str(&format!("\n{}", style.labeled("Reversals", &reversals)));
+
+        let mut punishable = side.moves.iter()
+            .filter_map(|m| signed_number(&m.block_advantage).map(|b| (b, m)))
+            .filter(|(b, _)| *b <= PUNISHABLE_THRESHOLD)
+            .collect::<Vec<(i32, &Move)>>();
+        punishable.sort_by_key(|(b, _)| *b);
+        let punishable = punishable.iter()
+            .take(5)
+            .map(|(b, m)| format!("{} ({})", m.identifier, style.advantage(*b)))
+            .collect::<Vec<String>>();
+        if !punishable.is_empty() {
+            sheet.push_str(&format!("\n{}", style.labeled("Punishable on block", &style.list(&punishable))));
+        }
+    }
+    sheet
+}
+
+/// Readable names for the cancels a move allows, with the Super Art levels when only some are,
+/// e.g. `Super (SA2/SA3)`
+fn cancel_options(options: &CancelOptions) -> Vec<String> {
+    options.cancel_types()
+        .into_iter()
+        .map(|cancel| match options.super_levels() {
+            levels if cancel == CancelType::Super && levels.len() < 3 => {
+                let levels = levels.iter().map(|level| format!("SA{}", level)).collect::<Vec<String>>();
+                format!("{} ({})", cancel.name(), levels.join("/"))
+            }
+            _ => cancel.name().to_string(),
+        })
+        .collect()
+}
+
+/// Reads the first number out of a wiki value like `+3`, `-6` or `4(5)` as a signed integer
+pub(crate) fn signed_number(value: &str) -> Option<i32> {
+    let (sign, number) = leading_number(value)?;
+    let number = i32::try_from(number).ok()?;
+    Some(if sign == '-' { -number } else { number })
+}
+
+/// Expands numpad notation into spoken words, e.g. `2MK` becomes `crouching medium kick` and
+/// `236P` becomes `quarter circle forward punch`. Returns `None` for inputs it doesn't recognise.
+pub fn speak_input(input: &str) -> Option<String> {
+    let caps = NUMPAD_INPUT_REGEX.captures(input.trim())?;
+    let jumping = caps.get(1).is_some();
+    let direction = caps.get(2).map(|m| m.as_str());
+    let button = speak_button(caps.get(3)?.as_str())?;
+    let prefix = match (jumping, direction) {
+        (true, None) => "jumping",
+        (true, Some(_)) => return None,
+        (false, None) => "",
+        (false, Some(d)) => speak_motion(d)?,
+    };
+    if prefix.is_empty() {
+        Some(button.to_string())
+    } else {
+        Some(format!("{} {}", prefix, button))
+    }
+}
+
+/// Rewrites numpad notation in the classic style, e.g. `2MK` becomes `cr.MK` and `236P` becomes
+/// `qcf+P`. Returns `None` for inputs it doesn't recognise.
+pub fn classic_input(input: &str) -> Option<String> {
+    let caps = NUMPAD_INPUT_REGEX.captures(input.trim())?;
+    let button = caps.get(3)?.as_str().to_ascii_uppercase();
+    let prefix = match (caps.get(1).is_some(), caps.get(2).map(|m| m.as_str())) {
+        (true, None) => "j.",
+        (true, Some(_)) => return None,
+        (false, None) => "",
+        (false, Some(d)) => classic_motion(d)?,
+    };
+    Some(format!("{}{}", prefix, button))
+}
+
+pub(crate) fn is_normal(identifier: &str) -> bool {
+    NUMPAD_INPUT_REGEX.captures(identifier.trim())
+        .map(|caps| caps.get(2).is_none_or(|d| d.as_str().len() == 1))
+        .unwrap_or(false)
+}
+
+fn speak_motion(motion: &str) -> Option<&'static str> {
+    let spoken = match motion {
+        "1" => "down back",
+        "2" => "crouching",
+        "3" => "down forward",
+        "4" => "back",
+        "5" => "standing",
+        "6" => "forward",
+        "7" => "up back",
+        "8" => "up",
+        "9" => "up forward",
+        "22" => "down down",
+        "236" => "quarter circle forward",
+        "214" => "quarter circle back",
+        "623" => "dragon punch",
+        "421" => "reverse dragon punch",
+        "41236" => "half circle forward",
+        "63214" => "half circle back",
+        "360" => "full circle",
+        "236236" => "double quarter circle forward",
+        "214214" => "double quarter circle back",
+        "[4]6" => "charge back, forward",
+        "[2]8" => "charge down, up",
+        _ => return None,
+    };
+    Some(spoken)
+}
+
+fn classic_motion(motion: &str) -> Option<&'static str> {
+    let classic = match motion {
+        "1" => "db.",
+        "2" => "cr.",
+        "3" => "df.",
+        "4" => "b.",
+        "5" => "st.",
+        "6" => "f.",
+        "7" => "ub.",
+        "8" => "u.",
+        "9" => "uf.",
+        "22" => "dd+",
+        "236" => "qcf+",
+        "214" => "qcb+",
+        "623" => "dp+",
+        "421" => "rdp+",
+        "41236" => "hcf+",
+        "63214" => "hcb+",
+        "360" => "360+",
+        "236236" => "qcf qcf+",
+        "214214" => "qcb qcb+",
+        "[4]6" => "[b]f+",
+        "[2]8" => "[d]u+",
+        _ => return None,
+    };
+    Some(classic)
+}
+
+fn speak_button(button: &str) -> Option<&'static str> {
+    let spoken = match button.to_ascii_uppercase().as_str() {
+        "LP" => "light punch",
+        "MP" => "medium punch",
+        "HP" => "heavy punch",
+        "LK" => "light kick",
+        "MK" => "medium kick",
+        "HK" => "heavy kick",
+        "P" => "punch",
+        "K" => "kick",
+        "PP" => "two punches",
+        "KK" => "two kicks",
+        "PPP" => "three punches",
+        "KKK" => "three kicks",
+        _ => return None,
+    };
+    Some(spoken)
+}
+
+fn speak_advantage(value: &str) -> Option<String> {
+    if value.trim_start().to_ascii_uppercase().starts_with("KD") {
+        return Some(String::from("knockdown"));
+    }
+    let (sign, frames) = leading_number(value)?;
+    let spoken = match (sign, frames) {
+        (_, 0) => String::from("even"),
+        ('-', n) => format!("minus {}", number_words(n)),
+        (_, n) => format!("plus {}", number_words(n)),
+    };
+    Some(spoken)
+}
+
+/// Reads the first (optionally signed) number out of a wiki value like `+3`, `-6` or `4(5)`
+pub(crate) fn leading_number(value: &str) -> Option<(char, u32)> {
+    let caps = LEADING_NUMBER_REGEX.captures(value)?;
+    let sign = caps.get(1).and_then(|m| m.as_str().chars().next()).unwrap_or('+');
+    let number = caps.get(2)?.as_str().parse().ok()?;
+    Some((sign, number))
+}
+
+/// Spells out a number in English words, e.g. `38` becomes `thirty-eight`
+pub fn number_words(number: u32) -> String {
+    const ONES: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    match number {
+        0..=19 => ONES[number as usize].to_string(),
+        20..=99 => match number % 10 {
+            0 => TENS[(number / 10) as usize].to_string(),
+            rest => format!("{}-{}", TENS[(number / 10) as usize], ONES[rest as usize]),
+        },
+        100..=999 => match number % 100 {
+            0 => format!("{} hundred", ONES[(number / 100) as usize]),
+            rest => format!("{} hundred {}", ONES[(number / 100) as usize], number_words(rest)),
+        },
+        _ => match number % 1000 {
+            0 => format!("{} thousand", number_words(number / 1000)),
+            rest => format!("{} thousand {}", number_words(number / 1000), number_words(rest)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spells_numbers() {
+        assert_eq!(number_words(6), "six");
+        assert_eq!(number_words(38), "thirty-eight");
+        assert_eq!(number_words(800), "eight hundred");
+        assert_eq!(number_words(1200), "one thousand two hundred");
+    }
+
+    #[test]
+    fn speaks_inputs() {
+        assert_eq!(speak_input("5MP").as_deref(), Some("standing medium punch"));
+        assert_eq!(speak_input("2lk").as_deref(), Some("crouching light kick"));
+        assert_eq!(speak_input("j.HP").as_deref(), Some("jumping heavy punch"));
+        assert_eq!(speak_input("236P").as_deref(), Some("quarter circle forward punch"));
+        assert_eq!(speak_input("214P(charged)"), None);
+        assert_eq!(classic_input("2mk").as_deref(), Some("cr.MK"));
+        assert_eq!(classic_input("236236P").as_deref(), Some("qcf qcf+P"));
+    }
+
+    #[test]
+    fn speaks_advantage() {
+        assert_eq!(speak_advantage("+1").as_deref(), Some("plus one"));
+        assert_eq!(speak_advantage("-6").as_deref(), Some("minus six"));
+        assert_eq!(speak_advantage("0").as_deref(), Some("even"));
+        assert_eq!(speak_advantage("KD +38").as_deref(), Some("knockdown"));
+        assert_eq!(speak_advantage("-"), None);
+    }
+
+    #[test]
+    fn speaks_moves() {
+        let ryu = &crate::character::RYU;
+        let mut move_data = Move {
+            identifier: String::from("5MP"),
+            name: String::from("Standing Medium Punch"),
+            startup: String::from("6"),
+            block_advantage: String::from("+1"),
+            hit_advantage: String::from("+4"),
+            damage: String::from("600"),
+            ..Move::default()
+        };
+        assert_eq!(speech(ryu, &move_data), "Ryu's standing medium punch: six frame startup, plus one on block, plus four on hit, six hundred damage.");
+        move_data.identifier = String::from("236P");
+        move_data.name = String::from("Hadoken");
+        move_data.damage = String::from("-");
+        assert_eq!(speech(ryu, &move_data), "Ryu's Hadoken, quarter circle forward punch: six frame startup, plus one on block, plus four on hit.");
+        let empty = Move { identifier: String::from("Taunt"), name: String::from("Taunt"), ..Move::default() };
+        assert_eq!(speech(ryu, &empty), "Ryu's Taunt. No frame data available.");
+    }
+
+    #[test]
+    fn describes_images() {
+        let ryu = &crate::character::RYU;
+        let mut move_data = Move {
+            identifier: String::from("2HP"),
+            name: String::from("Crouching Heavy Punch"),
+            image_link: String::from("https://wiki.supercombo.gg/images/thumb/SF6_Ryu_2HP_Hitbox.png/175px-SF6_Ryu_2HP_Hitbox.png"),
+            ..Move::default()
+        };
+        assert_eq!(alt_text(ryu, &move_data), "Ryu Crouching Heavy Punch (2HP) hitbox, active frames");
+        move_data.name = String::from("2hp");
+        move_data.image_link = String::from("https://wiki.supercombo.gg/images/SF6_Ryu_2HP.png");
+        assert_eq!(alt_text(ryu, &move_data), "Ryu 2HP animation frame");
+        move_data.image_link = DEFAULT_IMAGE.to_string();
+        assert_eq!(alt_text(ryu, &move_data), "Street Fighter 6 logo, no image available for Ryu 2HP");
+    }
+
+    #[test]
+    fn expands_cancel_options() {
+        assert_eq!(cancel_options(&CancelOptions::parse("Chn Sp SA")), vec!["Chain", "Special", "Super"]);
+        assert_eq!(cancel_options(&CancelOptions::parse("SA2/SA3")), vec!["Super (SA2/SA3)"]);
+        assert!(cancel_options(&CancelOptions::parse("-")).is_empty());
+    }
+
+    #[test]
+    fn styles_broadcasts() {
+        let move_data = Move {
+            identifier: String::from("5MP"),
+            name: String::from("Standing Medium Punch"),
+            startup: String::from("6"),
+            block_advantage: String::from("0"),
+            cancel: String::from("Chn Sp SA"),
+            cancel_options: CancelOptions::parse("Chn Sp SA"),
+            ..Move::default()
+        };
+        let ryu = &crate::character::RYU;
+        assert_eq!(broadcast(ryu, &move_data), "Ryu 5MP (Standing Medium Punch): 6f startup, +0 on block, safe\nCancels into Chain, Special, Super");
+        assert_eq!(
+            broadcast_styled(ryu, &move_data, &FormatStyle::JAPANESE),
+            "Ryu 5MP (Standing Medium Punch)：6F startup、±0 on block、safe\nCancels into Chain、Special、Super",
+        );
+    }
+}
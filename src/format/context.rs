@@ -0,0 +1,204 @@
+use crate::format::classic_input;
+use crate::format::style::FormatStyle;
+use crate::framedata::{columns, Move};
+
+/// How move inputs are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Notation {
+    /// As the wiki writes them, e.g. `2MK` and `236P`
+    #[default]
+    Numpad,
+    /// e.g. `cr.MK` and `qcf+P`, for inputs [`classic_input`] understands
+    Classic,
+}
+
+impl Notation {
+    /// Reads `numpad` or `classic`, ignoring case
+    pub fn from_name(name: &str) -> Option<Notation> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "numpad" => Some(Notation::Numpad),
+            "classic" => Some(Notation::Classic),
+            _ => None,
+        }
+    }
+}
+
+/// How much of a move is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verbosity {
+    /// Startup and block advantage only
+    Brief,
+    /// Startup, active, recovery, advantage and damage
+    #[default]
+    Normal,
+    /// Everything in [`Verbosity::Normal`] plus guard, cancels, invincibility, armor and notes
+    Detailed,
+}
+
+impl Verbosity {
+    /// Reads `brief`, `normal` or `detailed`, ignoring case
+    pub fn from_name(name: &str) -> Option<Verbosity> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "brief" => Some(Verbosity::Brief),
+            "normal" => Some(Verbosity::Normal),
+            "detailed" => Some(Verbosity::Detailed),
+            _ => None,
+        }
+    }
+
+    /// The [`Move`] fields shown, in order
+    pub fn fields(self) -> &'static [&'static str] {
+        const NORMAL: [&str; 6] = ["startup", "active", "recovery", "hit_advantage", "block_advantage", "damage"];
+        const DETAILED: [&str; 11] = ["startup", "active", "recovery", "hit_advantage", "block_advantage", "damage", "guard", "cancel", "invuln", "armor", "notes"];
+        match self {
+            Verbosity::Brief => &["startup", "block_advantage"],
+            Verbosity::Normal => &NORMAL,
+            Verbosity::Detailed => &DETAILED,
+        }
+    }
+}
+
+/// The preferences a [`MoveFormatter`](crate::format::formatter::MoveFormatter) formats with, so
+/// one bot can show the same move differently per server or user. Keep one per guild or user and
+/// pass it along with each move:
+/// ```
+/// use sf6rs::format::context::{FormatContext, Notation, Verbosity};
+///
+/// let guild = FormatContext::default().locale("ja").notation(Notation::Classic);
+/// let user = guild.clone().verbosity(Verbosity::Brief);
+/// assert_eq!(user.field_names(), ["startup", "block_advantage"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatContext {
+    pub notation: Notation,
+    /// Number and punctuation conventions, see [`FormatContext::locale`]
+    pub style: FormatStyle,
+    pub verbosity: Verbosity,
+    /// [`Move::FIELDS`] to show instead of the verbosity's, in order
+    pub fields: Option<Vec<&'static str>>,
+}
+
+impl FormatContext {
+    pub fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Uses the conventions of a language tag like `en-US` or Discord's `ja`, see
+    /// [`FormatStyle::for_locale`]
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.style = FormatStyle::for_locale(locale);
+        self
+    }
+
+    pub fn style(mut self, style: FormatStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Shows only these comma separated [`Move`] fields, e.g. `startup,block_advantage`. Names
+    /// that aren't fields are left out, so a saved preference from an older version still works.
+    pub fn fields(mut self, names: &str) -> Self {
+        let fields = names.split(',')
+            .filter_map(|name| Move::FIELDS.iter().copied().find(|field| *field == name.trim()))
+            .collect();
+        self.fields = Some(fields);
+        self
+    }
+
+    /// The fields to show, in order
+    pub fn field_names(&self) -> &[&'static str] {
+        self.fields.as_deref().unwrap_or(self.verbosity.fields())
+    }
+
+    /// The move's identifier in the preferred [`Notation`]
+    pub fn identifier(&self, move_data: &Move) -> String {
+        match self.notation {
+            Notation::Classic => classic_input(&move_data.identifier).unwrap_or_else(|| move_data.identifier.clone()),
+            Notation::Numpad => move_data.identifier.clone(),
+        }
+    }
+
+    /// A field's [`Column::label`](crate::framedata::columns::Column::label), e.g. `On Block` for
+    /// `block_advantage`
+    pub fn label(&self, field: &str) -> String {
+        match columns::by_field(field) {
+            Some(column) => column.label.to_string(),
+            None => {
+                let words = field.replace('_', " ");
+                let mut chars = words.chars();
+                chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+            }
+        }
+    }
+
+    /// A field's value in the preferred style, e.g. `4f` for startup, `+3` for advantage, or
+    /// [`FormatStyle::missing`] for fields the wiki doesn't have
+    pub fn value(&self, move_data: &Move, field: &str) -> String {
+        let value = move_data.get(field).unwrap_or_default();
+        match value.trim().parse::<i32>() {
+            Ok(frames) if field == "startup" => self.style.frames(frames),
+            Ok(frames) if field.ends_with("advantage") => self.style.advantage(frames),
+            _ => self.style.value(value).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_preference_names() {
+        assert_eq!(Notation::from_name(" Classic "), Some(Notation::Classic));
+        assert_eq!(Notation::from_name("anime"), None);
+        assert_eq!(Verbosity::from_name("DETAILED"), Some(Verbosity::Detailed));
+        assert_eq!(Verbosity::from_name(""), None);
+    }
+
+    #[test]
+    fn picks_fields() {
+        let context = FormatContext::default();
+        assert_eq!(context.field_names(), Verbosity::Normal.fields());
+        assert_eq!(context.clone().verbosity(Verbosity::Detailed).field_names().last(), Some(&"notes"));
+        let picked = context.verbosity(Verbosity::Brief).fields("block_advantage,startup,frames");
+        assert_eq!(picked.field_names(), ["block_advantage", "startup"]);
+        assert!(FormatContext::default().fields("").field_names().is_empty());
+    }
+
+    #[test]
+    fn formats_with_preferences() {
+        let sweep = Move {
+            identifier: String::from("2HK"),
+            startup: String::from("8"),
+            block_advantage: String::from("-12"),
+            hit_advantage: String::from("0"),
+            guard: String::from("L"),
+            ..Move::default()
+        };
+        let context = FormatContext::default();
+        assert_eq!(context.identifier(&sweep), "2HK");
+        assert_eq!(context.value(&sweep, "startup"), "8f");
+        assert_eq!(context.value(&sweep, "block_advantage"), "-12");
+        assert_eq!(context.value(&sweep, "hit_advantage"), "+0");
+        assert_eq!(context.value(&sweep, "guard"), "L");
+        assert_eq!(context.value(&sweep, "recovery"), "-");
+        assert_eq!(context.label("block_advantage"), "On Block");
+        assert_eq!(context.label("frame_meter"), "Frame meter");
+
+        let classic = FormatContext::default().notation(Notation::Classic).locale("ja-JP");
+        assert_eq!(classic.identifier(&sweep), "cr.HK");
+        assert_eq!(classic.identifier(&Move { identifier: String::from("236236K(SA3)"), ..Move::default() }), "236236K(SA3)");
+        assert_eq!(classic.value(&sweep, "startup"), "8F");
+        assert_eq!(classic.value(&sweep, "hit_advantage"), "±0");
+        assert_eq!(classic.value(&sweep, "recovery"), "—");
+        assert_eq!(classic.style(FormatStyle::TYPOGRAPHIC).value(&sweep, "block_advantage"), "−12");
+    }
+}
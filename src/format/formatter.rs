@@ -0,0 +1,262 @@
+//! Pluggable move formatters, so a bot or tool can swap in its own house style without forking.
+//! The built-in [`PlainFormatter`], [`MarkdownFormatter`] and [`DiscordFormatter`] are
+//! registered by name in [`Formatters`], next to any a downstream project adds. Each call takes
+//! the [`FormatContext`] of the server or user it's formatting for:
+//! ```
+//! use sf6rs::character::CharacterId;
+//! use sf6rs::format::context::FormatContext;
+//! use sf6rs::format::formatter::Formatters;
+//! use sf6rs::framedata::Move;
+//!
+//! let formatters = Formatters::default()
+//!     .register("terse", |context: &FormatContext, character: &CharacterId, m: &Move| {
+//!         format!("{} {}: {}", character.name(), context.identifier(m), context.value(m, "startup"))
+//!     });
+//! assert!(formatters.get("terse").is_some() && formatters.get("discord").is_some());
+//! ```
+
+use crate::character::CharacterId;
+use crate::format::context::{FormatContext, Verbosity};
+use crate::format::{signed_number, PUNISHABLE_THRESHOLD};
+use crate::framedata::Move;
+
+/// The longest message Discord accepts, in characters
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+/// How many fields the plain and Discord formatters put on one line
+const FIELDS_PER_LINE: usize = 3;
+
+/// Turns moves into text for one kind of output
+pub trait MoveFormatter: Send + Sync {
+    fn format_move(&self, context: &FormatContext, character: &CharacterId, move_data: &Move) -> String;
+
+    /// Formats several of a character's moves, by default one after another separated by a
+    /// blank line
+    fn format_moves(&self, context: &FormatContext, character: &CharacterId, moves: &[&Move]) -> String {
+        moves.iter()
+            .map(|m| self.format_move(context, character, m))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+impl<F> MoveFormatter for F
+where
+    F: Fn(&FormatContext, &CharacterId, &Move) -> String + Send + Sync,
+{
+    fn format_move(&self, context: &FormatContext, character: &CharacterId, move_data: &Move) -> String {
+        self(context, character, move_data)
+    }
+}
+
+/// Plain text, a label and value per field:
+/// ```text
+/// Ryu 5LP (Standing Light Punch)
+/// Startup: 4f, Active: 3, Recovery: 7
+/// On Hit: +4, On Block: -1, Damage: 300
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlainFormatter;
+
+impl MoveFormatter for PlainFormatter {
+    fn format_move(&self, context: &FormatContext, character: &CharacterId, move_data: &Move) -> String {
+        let lines = fields(context, move_data)
+            .chunks(FIELDS_PER_LINE)
+            .map(|line| {
+                let labeled = line.iter().map(|(label, value)| context.style.labeled(label, value)).collect::<Vec<String>>();
+                context.style.list(&labeled)
+            })
+            .collect::<Vec<String>>();
+        format!("{}\n{}", subject(context, character, move_data), lines.join("\n"))
+    }
+}
+
+/// A Markdown heading and table per move, or a single table for several moves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarkdownFormatter;
+
+impl MarkdownFormatter {
+    fn header(context: &FormatContext) -> (String, String) {
+        let labels = context.field_names().iter().map(|field| context.label(field)).collect::<Vec<String>>();
+        let separator = labels.iter().map(|_| "---").collect::<Vec<&str>>();
+        (labels.join(" | "), separator.join(" | "))
+    }
+
+    fn row(context: &FormatContext, move_data: &Move) -> String {
+        let cells = fields(context, move_data).into_iter().map(|(_, value)| markdown_cell(&value)).collect::<Vec<String>>();
+        cells.join(" | ")
+    }
+}
+
+impl MoveFormatter for MarkdownFormatter {
+    fn format_move(&self, context: &FormatContext, character: &CharacterId, move_data: &Move) -> String {
+        let (header, separator) = MarkdownFormatter::header(context);
+        format!("### {}\n| {} |\n| {} |\n| {} |", subject(context, character, move_data), header, separator, MarkdownFormatter::row(context, move_data))
+    }
+
+    fn format_moves(&self, context: &FormatContext, character: &CharacterId, moves: &[&Move]) -> String {
+        let (header, separator) = MarkdownFormatter::header(context);
+        let mut table = format!("### {}\n| Move | {} |\n| --- | {} |", character.name(), header, separator);
+        for move_data in moves {
+            table.push_str(&format!("\n| {} | {} |", markdown_cell(&context.identifier(move_data)), MarkdownFormatter::row(context, move_data)));
+        }
+        table
+    }
+}
+
+/// Discord message markdown, which has no tables: a bold title with values in inline code, and a
+/// warning for moves punishable on block. Several moves are cut short to fit
+/// [`DISCORD_MESSAGE_LIMIT`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiscordFormatter;
+
+impl MoveFormatter for DiscordFormatter {
+    fn format_move(&self, context: &FormatContext, character: &CharacterId, move_data: &Move) -> String {
+        let mut message = format!("**{} {}**", character.name(), context.identifier(move_data));
+        if context.verbosity != Verbosity::Brief {
+            message.push_str(&format!(" ({})", move_data.name));
+        }
+        for line in fields(context, move_data).chunks(FIELDS_PER_LINE) {
+            let values = line.iter().map(|(label, value)| format!("{} `{}`", label, value)).collect::<Vec<String>>();
+            message.push_str(&format!("\n> {}", values.join(" · ")));
+        }
+        if signed_number(&move_data.block_advantage).is_some_and(|block| block <= PUNISHABLE_THRESHOLD) {
+            message.push_str("\n> **Punishable on block**");
+        }
+        message
+    }
+
+    fn format_moves(&self, context: &FormatContext, character: &CharacterId, moves: &[&Move]) -> String {
+        let mut message = String::new();
+        for (index, move_data) in moves.iter().enumerate() {
+            let formatted = self.format_move(context, character, move_data);
+            let rest = format!("\n…and {} more", moves.len() - index);
+            if message.chars().count() + formatted.chars().count() + rest.chars().count() + 2 > DISCORD_MESSAGE_LIMIT {
+                message.push_str(&rest);
+                break;
+            }
+            if !message.is_empty() {
+                message.push_str("\n\n");
+            }
+            message.push_str(&formatted);
+        }
+        message
+    }
+}
+
+/// Formatters by name, for tools that let their users pick one, like `sf6rs-cli export --format`
+pub struct Formatters {
+    formatters: Vec<(String, Box<dyn MoveFormatter>)>,
+}
+
+impl Formatters {
+    /// No formatters at all, not even the built-in ones
+    pub fn new() -> Self {
+        Formatters {
+            formatters: Vec::new(),
+        }
+    }
+
+    /// Adds `formatter` under `name`, replacing any formatter already registered under it
+    pub fn register(mut self, name: &str, formatter: impl MoveFormatter + 'static) -> Self {
+        self.formatters.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        self.formatters.push((name.to_string(), Box::new(formatter)));
+        self
+    }
+
+    /// The formatter registered under `name`, ignoring case
+    pub fn get(&self, name: &str) -> Option<&dyn MoveFormatter> {
+        self.formatters.iter()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            .map(|(_, formatter)| formatter.as_ref())
+    }
+
+    /// Every registered name, in the order registered
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.formatters.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl Default for Formatters {
+    /// The built-in `plain`, `markdown` and `discord` formatters
+    fn default() -> Self {
+        Formatters::new()
+            .register("plain", PlainFormatter)
+            .register("markdown", MarkdownFormatter)
+            .register("discord", DiscordFormatter)
+    }
+}
+
+impl std::fmt::Debug for Formatters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// e.g. `Ryu 5LP (Standing Light Punch)`, without the name when brief
+fn subject(context: &FormatContext, character: &CharacterId, move_data: &Move) -> String {
+    match context.verbosity {
+        Verbosity::Brief => format!("{} {}", character.name(), context.identifier(move_data)),
+        _ => format!("{} {} ({})", character.name(), context.identifier(move_data), move_data.name),
+    }
+}
+
+/// The labels and values of the fields the context shows
+fn fields(context: &FormatContext, move_data: &Move) -> Vec<(String, String)> {
+    context.field_names().iter()
+        .map(|field| (context.label(field), context.value(move_data, field)))
+        .collect()
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::format::context::Notation;
+
+    fn jab() -> Move {
+        Move {
+            identifier: String::from("5LP"),
+            name: String::from("Standing Light Punch"),
+            startup: String::from("4"),
+            active: String::from("3"),
+            recovery: String::from("7"),
+            hit_advantage: String::from("4"),
+            block_advantage: String::from("-1"),
+            damage: String::from("300"),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn formats_built_in_styles() {
+        let jab = jab();
+        let sweep = Move { identifier: String::from("2HK"), block_advantage: String::from("-12"), hit_advantage: String::from("KD +30"), ..jab.clone() };
+        let ryu = &character::RYU;
+        let context = FormatContext::default();
+        assert_eq!(
+            PlainFormatter.format_move(&context, ryu, &jab),
+            "Ryu 5LP (Standing Light Punch)\nStartup: 4f, Active: 3, Recovery: 7\nOn Hit: +4, On Block: -1, Damage: 300",
+        );
+        assert!(MarkdownFormatter.format_moves(&context, ryu, &[&jab, &sweep]).ends_with("| 2HK | 4f | 3 | 7 | KD +30 | -12 | 300 |"));
+        assert!(DiscordFormatter.format_move(&context, ryu, &sweep).ends_with("> **Punishable on block**"));
+
+        let many = vec![&jab; 40];
+        let message = DiscordFormatter.format_moves(&context, ryu, &many);
+        assert!(message.chars().count() <= DISCORD_MESSAGE_LIMIT);
+        assert!(message.ends_with("more"));
+
+        let guild = FormatContext::default().locale("ja").notation(Notation::Classic).verbosity(Verbosity::Brief);
+        assert_eq!(PlainFormatter.format_move(&guild, ryu, &sweep), "Ryu cr.HK\nStartup：4F、On Block：-12");
+        let picked = FormatContext::default().fields("damage, hitbox, guard");
+        assert_eq!(picked.field_names(), ["damage", "guard"]);
+        assert_eq!(PlainFormatter.format_move(&picked, ryu, &jab), "Ryu 5LP (Standing Light Punch)\nDamage: 300, Guard: -");
+
+        let formatters = Formatters::default().register("PLAIN", |_: &FormatContext, _: &CharacterId, m: &Move| m.identifier.clone());
+        assert_eq!(formatters.get("plain").unwrap().format_move(&context, ryu, &jab), "5LP");
+        assert_eq!(formatters.names().collect::<Vec<&str>>(), vec!["markdown", "discord", "PLAIN"]);
+    }
+}
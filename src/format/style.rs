@@ -0,0 +1,120 @@
+use crate::framedata::is_placeholder;
+
+/// Number and punctuation conventions for the formatters, so a bot posting in another language
+/// doesn't mix its own conventions with this crate's. Wording is left as is; only signs, missing
+/// values, frame counts and separators change.
+/// ```
+/// use sf6rs::format::style::FormatStyle;
+///
+/// assert_eq!(FormatStyle::ENGLISH.advantage(0), "+0");
+/// assert_eq!(FormatStyle::TYPOGRAPHIC.advantage(-3), "−3");
+/// assert_eq!(FormatStyle::JAPANESE.list(&["5LP", "2LK"]), "5LP、2LK");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatStyle {
+    /// An advantage of zero, e.g. `+0` or `±0`
+    pub zero: &'static str,
+    /// The sign of negative advantage, e.g. `-` or the typographic minus `−`
+    pub minus: &'static str,
+    /// A value the wiki doesn't have, e.g. `-` or `—`
+    pub missing: &'static str,
+    /// Appended to frame counts, e.g. the `f` in `4f`
+    pub frames_suffix: &'static str,
+    /// Between list items, e.g. `, ` or `、`
+    pub list_separator: &'static str,
+    /// Between a label and its value, e.g. `: ` or `：`
+    pub label_separator: &'static str,
+}
+
+impl FormatStyle {
+    /// The conventions the formatters have always used, e.g. `+0`, `-3` and `4f`
+    pub const ENGLISH: FormatStyle = FormatStyle {
+        zero: "+0",
+        minus: "-",
+        missing: "-",
+        frames_suffix: "f",
+        list_separator: ", ",
+        label_separator: ": ",
+    };
+
+    /// English punctuation with typographic signs, e.g. `±0`, `−3` and `—`
+    pub const TYPOGRAPHIC: FormatStyle = FormatStyle {
+        zero: "±0",
+        minus: "−",
+        missing: "—",
+        ..FormatStyle::ENGLISH
+    };
+
+    /// Japanese punctuation and the `F` frame suffix used in Japanese frame data, e.g. `4F`
+    pub const JAPANESE: FormatStyle = FormatStyle {
+        zero: "±0",
+        minus: "-",
+        missing: "—",
+        frames_suffix: "F",
+        list_separator: "、",
+        label_separator: "：",
+    };
+
+    /// The conventions for a language tag like `en-US`, `ja` or `ja-JP`, [`FormatStyle::ENGLISH`]
+    /// for languages without their own
+    pub fn for_locale(locale: &str) -> FormatStyle {
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "ja" => FormatStyle::JAPANESE,
+            _ => FormatStyle::ENGLISH,
+        }
+    }
+
+    /// Formats an advantage with its sign, e.g. `+3`, `-6` or [`FormatStyle::zero`]
+    pub fn advantage(&self, frames: i32) -> String {
+        match frames {
+            0 => self.zero.to_string(),
+            f if f < 0 => format!("{}{}", self.minus, f.unsigned_abs()),
+            f => format!("+{}", f),
+        }
+    }
+
+    /// Formats a frame count, e.g. `4f`
+    pub fn frames(&self, frames: i32) -> String {
+        format!("{}{}", frames, self.frames_suffix)
+    }
+
+    /// A wiki value as is, or [`FormatStyle::missing`] for the wiki's placeholders
+    pub fn value<'a>(&self, value: &'a str) -> &'a str {
+        if is_placeholder(value) { self.missing } else { value.trim() }
+    }
+
+    /// Joins list items with [`FormatStyle::list_separator`]
+    pub fn list<T: AsRef<str>>(&self, items: &[T]) -> String {
+        items.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(self.list_separator)
+    }
+
+    /// Joins a label and its value with [`FormatStyle::label_separator`]
+    pub fn labeled(&self, label: &str, value: &str) -> String {
+        format!("{}{}{}", label, self.label_separator, value)
+    }
+}
+
+impl Default for FormatStyle {
+    fn default() -> Self {
+        FormatStyle::ENGLISH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_by_style() {
+        assert_eq!(FormatStyle::ENGLISH.advantage(-3), "-3");
+        assert_eq!(FormatStyle::ENGLISH.advantage(2), "+2");
+        assert_eq!(FormatStyle::JAPANESE.advantage(0), "±0");
+        assert_eq!(FormatStyle::JAPANESE.frames(4), "4F");
+        assert_eq!(FormatStyle::TYPOGRAPHIC.value("-"), "—");
+        assert_eq!(FormatStyle::TYPOGRAPHIC.value(" 12 "), "12");
+        assert_eq!(FormatStyle::JAPANESE.labeled("Reversals", "none"), "Reversals：none");
+        assert_eq!(FormatStyle::for_locale("ja-JP"), FormatStyle::JAPANESE);
+        assert_eq!(FormatStyle::for_locale("en-US"), FormatStyle::ENGLISH);
+    }
+}
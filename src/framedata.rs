@@ -2,13 +2,62 @@ use std::error::Error;
 use std::fmt::Display;
 use std::iter::zip;
 
+use rayon::prelude::*;
 use regex::Regex;
 use scraper::{Element, ElementRef, Html, Selector};
-use tokio::task::JoinSet;
 
-use crate::{character, LazyLock};
-use crate::character::{CharacterId, CHARACTERS};
+use crate::{character, format, LazyLock};
+use crate::character::CharacterId;
+#[cfg(feature = "async")]
+use crate::character::CharacterRegistry;
+use crate::error::SF6Error;
+#[cfg(feature = "async")]
+use crate::framedata::archive::HtmlArchive;
+use crate::framedata::media::MediaInfo;
 use crate::framedata::SF6FrameDataError::{UnknownCharacter, UnknownGif, UnknownMove};
+use crate::parsed::CancelOptions;
+use crate::tags::MoveCategory;
+
+pub mod archive;
+pub mod art;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(all(feature = "serde", feature = "async"))]
+pub mod cache;
+pub mod canary;
+pub mod columns;
+pub mod controls;
+pub mod dropped;
+pub mod corrections;
+pub mod gifs;
+pub mod health;
+#[cfg(feature = "async")]
+pub mod images;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod mechanics;
+#[cfg(all(feature = "serde", feature = "async"))]
+pub mod mirror;
+pub mod overrides;
+pub mod media;
+pub mod refresh;
+pub mod report;
+pub mod retry;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod stats;
+pub mod telemetry;
+pub mod verify;
+pub mod version;
+#[cfg(feature = "async")]
+mod loader;
+
+pub use canary::CanaryStatus;
+#[cfg(feature = "async")]
+pub use loader::{Loader, UrlRewrite, DEFAULT_MAX_CONCURRENT_REQUESTS};
+pub use report::LoadReport;
+pub use retry::RetryPolicy;
+pub use stats::CharacterStats;
 
 #[derive(Debug)]
 pub enum SF6FrameDataError {
@@ -31,6 +80,7 @@ impl Error for SF6FrameDataError {}
 
 /// Contains data regarding frame data in this library
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrameData {
     /// A character's specific frame data
     pub character_frame_data: Vec<CharacterFrameData>,
@@ -60,7 +110,7 @@ impl FrameData {
         let Some(character) = character_id_opt else {
             return Err(UnknownCharacter);
         };
-        return self.find_move_character(character, move_query);
+        self.find_move_character(character, move_query)
     }
 
     /// Returns a reference to a [`Move`] of a Character by a [`CharacterId`] and `move_query`.
@@ -79,7 +129,7 @@ impl FrameData {
         let Some(character) = character_id_opt else {
             return Err(UnknownCharacter);
         };
-        return self.find_gif_character(character, gif_query);
+        self.find_gif_character(character, gif_query)
     }
 
     pub fn find_gif_character(&self, character_id: &CharacterId, gif_query: &str) -> Result<&MoveGif, SF6FrameDataError> {
@@ -91,14 +141,83 @@ impl FrameData {
 
 /// Represents a characters frame data
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterFrameData {
     pub character_id: CharacterId,
     pub moves: Vec<Move>,
-    pub gifs: Vec<MoveGif>
+    pub gifs: Vec<MoveGif>,
+    /// Movement and defensive stats, `None` if the page has no stats table
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: Option<CharacterStats>,
+}
+
+impl CharacterFrameData {
+    /// The fraction (`0.0..=1.0`) of frame data fields across all moves that hold a real value
+    /// rather than a wiki placeholder. A freshly released character whose page is still being
+    /// filled in scores low; a character with no moves at all scores `0.0`.
+    pub fn completeness(&self) -> f32 {
+        let (filled, total) = self.moves.iter()
+            .map(Move::filled_data_fields)
+            .fold((0, 0), |(filled, total), (f, t)| (filled + f, total + t));
+        if total == 0 {
+            return 0.0;
+        }
+        filled as f32 / total as f32
+    }
+
+    /// Classifies how usable this character's data is. A page for a just-released character
+    /// usually exists before its tables are filled in; bots should check for
+    /// [`DataStatus::PartialData`] and tell users the data is still being filled in rather than
+    /// presenting mostly empty moves as if they were real.
+    pub fn status(&self) -> DataStatus {
+        if self.moves.is_empty() {
+            return DataStatus::Empty;
+        }
+        let (filled, total) = self.moves.iter()
+            .flat_map(|m| DataStatus::CORE_FIELDS.iter().filter_map(|name| m.get(name)))
+            .fold((0, 0), |(filled, total), value| (filled + usize::from(!is_placeholder(value)), total + 1));
+        let core_completeness = filled as f32 / total as f32;
+        if self.moves.len() < DataStatus::MIN_MOVES || core_completeness < DataStatus::MIN_CORE_COMPLETENESS {
+            DataStatus::PartialData {
+                moves: self.moves.len(),
+                completeness: core_completeness,
+            }
+        } else {
+            DataStatus::Complete
+        }
+    }
+}
+
+/// How usable a character's scraped data is, see [`CharacterFrameData::status`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataStatus {
+    /// The data looks like a fully documented character
+    Complete,
+    /// The page exists and something was parsed, but it is still being filled in. `completeness`
+    /// is the fraction of core fields (startup, damage, advantage...) that have values.
+    PartialData { moves: usize, completeness: f32 },
+    /// Nothing could be parsed from the page
+    Empty,
+}
+
+impl DataStatus {
+    /// Every released character has far more moves than this once their normals are documented
+    const MIN_MOVES: usize = 15;
+    /// Below this fraction of filled core fields a page is considered a stub
+    const MIN_CORE_COMPLETENESS: f32 = 0.5;
+    /// The fields nearly every documented move has, unlike armor or projectile speed
+    const CORE_FIELDS: [&'static str; 6] = ["damage", "guard", "startup", "recovery", "hit_advantage", "block_advantage"];
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, DataStatus::Complete)
+    }
 }
 
 /// A data struct holding all info scraped by this library for a given Move
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Move {
     /// A unique identifier for this move. Often an input. Will provide differences for same-input
     /// moves like Ryu's `Hashogeki (214p)` and `Denjin Hashogeki (214p)`, representing them as
@@ -107,13 +226,31 @@ pub struct Move {
     /// The input for this move. Often duplicated. For example: Ryu's `Hashogeki (214p)` and `Denjin
     /// Hashogeki (214p)`
     pub input: String,
+    /// The input on the Modern control scheme, e.g. `2M` or `SP`, `None` where the wiki lists
+    /// none. `input` is the Classic input, see [`ControlScheme`](controls::ControlScheme).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modern_input: Option<String>,
     pub name: String,
+    /// The movelist section the wiki lists this move under, `None` for moves outside a section
+    /// the scraper recognizes. [`Move::category`] falls back to a guess for those.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub category: Option<MoveCategory>,
     pub image_link: String,
+    /// Descriptive alt text for `image_link`, e.g. `Ryu Crouching Heavy Punch (2HP) hitbox, active
+    /// frames`, for web frontends and embeds that need accessible images
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub image_alt: String,
+    /// Size and format of the image at `image_link`, `None` for the [`DEFAULT_IMAGE`] fallback
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub image_info: Option<MediaInfo>,
     pub damage: String,
     pub chip_damage: String,
     pub damage_scaling: String,
     pub guard: String,
     pub cancel: String,
+    /// `cancel` parsed into the cancels it allows, see [`CancelOptions`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cancel_options: CancelOptions,
     pub hitconfirm_window: String,
     pub startup: String,
     pub active: String,
@@ -144,182 +281,400 @@ pub struct Move {
     pub notes: String,
 }
 
+impl Move {
+    /// The name of every field [`Move::get`] accepts, in declaration order, for renderers and
+    /// exporters that work over fields generically
+    pub const FIELDS: [&'static str; 38] = [
+        "identifier", "input", "name", "image_link", "image_alt", "damage", "chip_damage",
+        "damage_scaling", "guard", "cancel", "hitconfirm_window", "startup", "active", "recovery",
+        "total", "hitstun",
+        "blockstun", "drive_damage_block", "drive_damage_hit", "drive_gain", "super_gain_hit",
+        "super_gain_block", "projectile_speed", "invuln", "armor", "airborne", "juggle_start",
+        "juggle_increase", "juggle_limit", "perfect_parry_advantage", "after_dr_hit",
+        "after_dr_block", "dr_cancel_hit", "dr_cancel_block", "punish_advantage", "hit_advantage",
+        "block_advantage", "notes",
+    ];
+
+    /// Fields describing what the move is rather than how it performs, which every scraped move has
+    const IDENTITY_FIELDS: [&'static str; 5] = ["identifier", "input", "name", "image_link", "image_alt"];
+
+    /// The fraction (`0.0..=1.0`) of this move's frame data fields that hold a real value rather
+    /// than a wiki placeholder like `-`
+    pub fn completeness(&self) -> f32 {
+        let (filled, total) = self.filled_data_fields();
+        filled as f32 / total as f32
+    }
+
+    fn filled_data_fields(&self) -> (usize, usize) {
+        let data_fields = Move::FIELDS.iter()
+            .filter(|name| !Move::IDENTITY_FIELDS.contains(name));
+        let (mut filled, mut total) = (0, 0);
+        for name in data_fields {
+            total += 1;
+            if self.get(name).is_some_and(|value| !is_placeholder(value)) {
+                filled += 1;
+            }
+        }
+        (filled, total)
+    }
+
+    /// Returns the value of a field by its struct field name, e.g. `startup`, or `None` if it
+    /// isn't one of [`Move::FIELDS`]
+    pub fn get(&self, field: &str) -> Option<&str> {
+        let value = match field {
+            "identifier" => &self.identifier,
+            "input" => &self.input,
+            "name" => &self.name,
+            "image_link" => &self.image_link,
+            "image_alt" => &self.image_alt,
+            "damage" => &self.damage,
+            "chip_damage" => &self.chip_damage,
+            "damage_scaling" => &self.damage_scaling,
+            "guard" => &self.guard,
+            "cancel" => &self.cancel,
+            "hitconfirm_window" => &self.hitconfirm_window,
+            "startup" => &self.startup,
+            "active" => &self.active,
+            "recovery" => &self.recovery,
+            "total" => &self.total,
+            "hitstun" => &self.hitstun,
+            "blockstun" => &self.blockstun,
+            "drive_damage_block" => &self.drive_damage_block,
+            "drive_damage_hit" => &self.drive_damage_hit,
+            "drive_gain" => &self.drive_gain,
+            "super_gain_hit" => &self.super_gain_hit,
+            "super_gain_block" => &self.super_gain_block,
+            "projectile_speed" => &self.projectile_speed,
+            "invuln" => &self.invuln,
+            "armor" => &self.armor,
+            "airborne" => &self.airborne,
+            "juggle_start" => &self.juggle_start,
+            "juggle_increase" => &self.juggle_increase,
+            "juggle_limit" => &self.juggle_limit,
+            "perfect_parry_advantage" => &self.perfect_parry_advantage,
+            "after_dr_hit" => &self.after_dr_hit,
+            "after_dr_block" => &self.after_dr_block,
+            "dr_cancel_hit" => &self.dr_cancel_hit,
+            "dr_cancel_block" => &self.dr_cancel_block,
+            "punish_advantage" => &self.punish_advantage,
+            "hit_advantage" => &self.hit_advantage,
+            "block_advantage" => &self.block_advantage,
+            "notes" => &self.notes,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns a mutable reference to a field by its struct field name, see [`Move::get`]
+    pub(crate) fn get_mut(&mut self, field: &str) -> Option<&mut String> {
+        let value = match field {
+            "identifier" => &mut self.identifier,
+            "input" => &mut self.input,
+            "name" => &mut self.name,
+            "image_link" => &mut self.image_link,
+            "image_alt" => &mut self.image_alt,
+            "damage" => &mut self.damage,
+            "chip_damage" => &mut self.chip_damage,
+            "damage_scaling" => &mut self.damage_scaling,
+            "guard" => &mut self.guard,
+            "cancel" => &mut self.cancel,
+            "hitconfirm_window" => &mut self.hitconfirm_window,
+            "startup" => &mut self.startup,
+            "active" => &mut self.active,
+            "recovery" => &mut self.recovery,
+            "total" => &mut self.total,
+            "hitstun" => &mut self.hitstun,
+            "blockstun" => &mut self.blockstun,
+            "drive_damage_block" => &mut self.drive_damage_block,
+            "drive_damage_hit" => &mut self.drive_damage_hit,
+            "drive_gain" => &mut self.drive_gain,
+            "super_gain_hit" => &mut self.super_gain_hit,
+            "super_gain_block" => &mut self.super_gain_block,
+            "projectile_speed" => &mut self.projectile_speed,
+            "invuln" => &mut self.invuln,
+            "armor" => &mut self.armor,
+            "airborne" => &mut self.airborne,
+            "juggle_start" => &mut self.juggle_start,
+            "juggle_increase" => &mut self.juggle_increase,
+            "juggle_limit" => &mut self.juggle_limit,
+            "perfect_parry_advantage" => &mut self.perfect_parry_advantage,
+            "after_dr_hit" => &mut self.after_dr_hit,
+            "after_dr_block" => &mut self.after_dr_block,
+            "dr_cancel_hit" => &mut self.dr_cancel_hit,
+            "dr_cancel_block" => &mut self.dr_cancel_block,
+            "punish_advantage" => &mut self.punish_advantage,
+            "hit_advantage" => &mut self.hit_advantage,
+            "block_advantage" => &mut self.block_advantage,
+            "notes" => &mut self.notes,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Whether a scraped value is one of the wiki's "no data" placeholders
+pub(crate) fn is_placeholder(value: &str) -> bool {
+    matches!(value.trim(), "" | "-" | "--" | "?" | "N/A" | "n/a")
+}
+
+/// An animation of a move scraped from ultimateframedata.com, see [`gifs`]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveGif {
+    /// The move's name on ultimateframedata.com, which rarely matches the wiki's exactly
     pub name: String,
-    pub url: String
+    /// The `identifier` of the [`Move`] this gif animates, `None` if it couldn't be paired with
+    /// one, see [`CharacterFrameData::link_gifs`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub move_identifier: Option<String>,
+    pub url: String,
+    /// A smaller preview of the gif, `None` if the page only links the full gif
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thumbnail_url: Option<String>,
+    /// Size and format of the gif at `url`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub info: MediaInfo,
 }
 
 /// Loads all frame data provided by this module. This function makes web-requests for each
 /// characters frame data page, scrapes it, parses it, and collects it. It is recommended to cache
-/// the result of this load function.
-pub async fn load_all() -> FrameData {
-    let mut frame_data = FrameData {
-        character_frame_data: Vec::new()
-    };
-    let mut set = JoinSet::new();
-    for character_id in (*CHARACTERS).iter() {
-        set.spawn(load(character_id));
-    }
-    while let Some(res) = set.join_next().await {
-        let Ok(character_frame_data) = res else {
-            println!("Error handling character frame data loading future {}", res.unwrap_err());
-            continue;
-        };
-        frame_data.character_frame_data.push(character_frame_data);
-    }
-    frame_data
+/// the result of this load function. Use a [`Loader`] to configure how pages are loaded.
+/// Characters that fail to load are reported in [`LoadReport::failures`] rather than failing the
+/// whole load.
+#[cfg(feature = "async")]
+pub async fn load_all() -> LoadReport {
+    Loader::default().load_all().await
+}
+
+/// Loads the frame data of every character in `registry` like [`load_all`], for characters the
+/// crate doesn't know yet or pages served from a mirror
+#[cfg(feature = "async")]
+pub async fn load_registry(registry: &CharacterRegistry) -> LoadReport {
+    Loader::default().load_registry(registry).await
 }
 
 /// This function loads frame data, similar to [`load_all`], however only requesting, scraping,
-/// parsing, and collecting the data for one given [`CharacterId`]
-pub async fn load(character_id: &CharacterId) -> CharacterFrameData {
-    let moves = {
-        let html = request_data_page(character_id).await.unwrap();
-        let move_identifiers = select_move_identifiers(&html);
-        let move_blocks = select_move_blocks(&html);
-        let zip = zip(move_identifiers, move_blocks);
-        let moves: Vec<Move> = zip.filter_map(|(identifier, block)| parse_move(identifier, block)).collect();
-        moves
-    };
+/// parsing, and collecting the data for one given [`CharacterId`]. Transient failures are retried
+/// with the default [`RetryPolicy`].
+#[cfg(feature = "async")]
+pub async fn load(character_id: &CharacterId) -> Result<CharacterFrameData, SF6Error> {
+    Loader::default().load(character_id).await
+}
 
-    let gifs = {
-        let gif_html = request_gif_page(character_id).await.unwrap();
-        let containers = parse_move_containers(&gif_html);
-        let gifs: Vec<MoveGif> = containers.into_iter().filter_map(|e| parse_move_container(e)).collect();
-        gifs
-    };
-    CharacterFrameData {
+/// This function loads frame data exactly like [`load`], but additionally stores the raw html of
+/// both pages in `archive` so they can be re-parsed later with [`archive::reparse_cache`].
+#[cfg(feature = "async")]
+pub async fn load_archived(character_id: &CharacterId, archive: &HtmlArchive) -> Result<CharacterFrameData, SF6Error> {
+    Loader::default().load_archived(character_id, archive).await
+}
+
+/// Loads all frame data like [`load_all`], reading characters cached in `dir` within
+/// [`cache::DEFAULT_TTL`] instead of requesting them, see [`cache::CachedLoader`]
+#[cfg(all(feature = "serde", feature = "async"))]
+pub async fn load_all_cached(dir: impl Into<std::path::PathBuf>) -> LoadReport {
+    cache::CachedLoader::new(dir).load_all_cached().await
+}
+
+/// Loads one character like [`load`], reading them from `dir` if they were cached within
+/// [`cache::DEFAULT_TTL`], see [`cache::CachedLoader`]
+#[cfg(all(feature = "serde", feature = "async"))]
+pub async fn load_cached(character_id: &CharacterId, dir: impl Into<std::path::PathBuf>) -> Result<CharacterFrameData, SF6Error> {
+    cache::CachedLoader::new(dir).load_cached(character_id).await
+}
+
+/// Fetches a single known-good frame data page and checks that it still has the structure this
+/// library expects. Meant to be called by monitoring before a scheduled full refresh, so a wiki
+/// template change is noticed before it turns into a refresh full of empty moves.
+#[cfg(feature = "async")]
+pub async fn canary_check() -> CanaryStatus {
+    Loader::default().canary_check().await
+}
+
+/// Fails with [`SF6Error::ParseStructureChanged`] if no moves were parsed from the character's
+/// page, which every load treats as a failure rather than a character without moves
+#[cfg_attr(not(any(feature = "async", feature = "blocking")), allow(dead_code))]
+pub(crate) fn require_moves(character_frame_data: CharacterFrameData) -> Result<CharacterFrameData, SF6Error> {
+    if character_frame_data.moves.is_empty() {
+        return Err(SF6Error::ParseStructureChanged { url: character_frame_data.character_id.frame_data_url() });
+    }
+    Ok(character_frame_data)
+}
+
+/// Parses a saved copy of a character's frame data page (the html of
+/// [`CharacterId::frame_data_url`]) without making any web-requests. `gifs` is left empty; use
+/// [`parse_character_pages`] to include the gif page.
+pub fn parse_character_page(html: &str, character_id: &CharacterId) -> CharacterFrameData {
+    parse_character_pages(character_id, html, None)
+}
+
+/// Parses saved copies of a character's frame data page and, if given, their gif page (the html
+/// of [`CharacterId::gif_data_url`]) without making any web-requests
+pub fn parse_character_pages(character_id: &CharacterId, html: &str, gif_html: Option<&str>) -> CharacterFrameData {
+    let html = Html::parse_document(html);
+    let mut moves = parse_moves(&html);
+    for move_data in &mut moves {
+        move_data.image_alt = format::alt_text(character_id, move_data);
+    }
+    let gifs = gif_html
+        .map(|gif_html| parse_gifs(&Html::parse_document(gif_html)))
+        .unwrap_or_default();
+    let mut character_frame_data = CharacterFrameData {
         character_id: character_id.clone(),
         moves,
-        gifs
+        gifs,
+        stats: stats::parse_stats(&html),
+    };
+    character_frame_data.link_gifs();
+    character_frame_data
+}
+
+/// Parses many pre-fetched frame data pages in parallel without making any web-requests. Each
+/// page is the raw html of a character's [`CharacterId::frame_data_url`]. Useful for reprocessing
+/// archived pages after a parser improvement. Gif pages are not part of this input, so the
+/// resulting `gifs` are empty.
+pub fn parse_all(pages: Vec<(CharacterId, String)>) -> FrameData {
+    let character_frame_data = pages.into_par_iter()
+        .map(|(character_id, page)| parse_character_pages(&character_id, &page, None))
+        .collect();
+    FrameData {
+        character_frame_data
+    }
+}
+
+impl FrameData {
+    /// Builds frame data from saved frame data pages, see [`parse_all`]
+    pub fn from_html_pages(pages: Vec<(CharacterId, String)>) -> FrameData {
+        parse_all(pages)
     }
 }
 
+fn parse_moves(html: &Html) -> Vec<Move> {
+    let move_identifiers = select_move_identifiers(html);
+    let categories = section_categories(html, &move_identifiers);
+    zip(move_identifiers, categories)
+        .filter_map(|(identifier, category)| parse_move(identifier, category, move_block(identifier)?))
+        .collect()
+}
+
+pub(crate) fn parse_gifs(html: &Html) -> Vec<MoveGif> {
+    let containers = parse_move_containers(html);
+    containers.into_iter().filter_map(parse_move_container).collect()
+}
+
 static MOVE_IDENTIFIER_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div > div > section.section-collapsible > h5 > span").unwrap());
 
-fn select_move_identifiers(html: &Html) -> Vec<ElementRef> {
+fn select_move_identifiers(html: &Html) -> Vec<ElementRef<'_>> {
     html.select(&MOVE_IDENTIFIER_SELECTOR)
         .filter(|id| !id.is_empty())
         .collect::<Vec<ElementRef>>()
 }
 
+static SECTION_HEADINGS: [&str; 3] = ["h2", "h3", "h4"];
+
+/// The category of the movelist section each of `identifiers` is in, going by the last section
+/// heading before it that names one
+fn section_categories(html: &Html, identifiers: &[ElementRef]) -> Vec<Option<MoveCategory>> {
+    let mut categories = Vec::with_capacity(identifiers.len());
+    let mut current = None;
+    for element in html.root_element().descendants().filter_map(ElementRef::wrap) {
+        if SECTION_HEADINGS.contains(&element.value().name()) {
+            current = MoveCategory::from_heading(&element.text().collect::<String>()).or(current);
+        } else if identifiers.get(categories.len()).is_some_and(|identifier| identifier.id() == element.id()) {
+            categories.push(current);
+        }
+    }
+    categories
+}
+
 static MOVE_BLOCK_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div > div > section.section-collapsible > h5 + table.wikitable").unwrap());
 
-fn select_move_blocks(html: &Html) -> Vec<ElementRef> {
+fn select_move_blocks(html: &Html) -> Vec<ElementRef<'_>> {
     html.select(&MOVE_BLOCK_SELECTOR)
         .filter(|id| !id.is_empty())
         .collect::<Vec<ElementRef>>()
 }
 
-async fn request_data_page(character_id: &CharacterId) -> Result<Html, Box<dyn Error>> {
-    let text = reqwest::get(character_id.frame_data_url()).await?.text().await?;
-    Ok(Html::parse_document(&text))
+/// The move table right after `identifier`'s heading, `None` if its section has none. Pairing by
+/// position instead would shift every later move onto the wrong table after one such section.
+fn move_block(identifier: ElementRef) -> Option<ElementRef> {
+    let block = identifier.parent_element()?.next_sibling_element()?;
+    (MOVE_BLOCK_SELECTOR.matches(&block) && !block.is_empty()).then_some(block)
 }
 
-static INPUT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > th > div > p > span").unwrap());
+static INPUT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > th > div > p > span:not([class*=modern])").unwrap());
+static MODERN_INPUT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > th > div > p > span[class*=modern]").unwrap());
 static NAME_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > th > div > div").unwrap());
 static HITBOX_IMAGE_ELEMENT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > th > a").unwrap());
 static HITBOX_IMAGE_URL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(/images/thumb\S+) 2x").unwrap());
 static DATA_ROW_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr > td").unwrap());
-const DEFAULT_IMAGE: &str = "https://wiki.supercombo.gg/images/thumb/4/42/SF6_Logo.png/300px-SF6_Logo.png";
+static ROW_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr").unwrap());
+static HEADER_CELL_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("th").unwrap());
+pub(crate) const DEFAULT_IMAGE: &str = "https://wiki.supercombo.gg/images/thumb/4/42/SF6_Logo.png/300px-SF6_Logo.png";
 
-fn parse_move(identifier: ElementRef, block: ElementRef) -> Option<Move> {
+fn parse_move(identifier: ElementRef, category: Option<MoveCategory>, block: ElementRef) -> Option<Move> {
     let identifier = identifier.inner_html();
     let input = block.select(&INPUT_SELECTOR)
         .next()
         .map(|e| e.inner_html())?;
+    let modern_input = block.select(&MODERN_INPUT_SELECTOR)
+        .next()
+        .map(|e| e.text().collect::<String>().trim().to_string())
+        .filter(|input| !is_placeholder(input));
     let name = block.select(&NAME_SELECTOR)
         .next()
         .map(|e| e.inner_html())?;
-    // need to initialize this as its own variable first since 'e' is consumed
-    let mut select = block.select(&HITBOX_IMAGE_ELEMENT_SELECTOR).map(|e| e.html());
-    let hitbox_image_url = {
-        let image = select.next().and_then(hitbox_image_matcher);
-        let hitbox = select.next().and_then(hitbox_image_matcher);
-        hitbox.or(image).unwrap_or_else(|| DEFAULT_IMAGE.to_string())
+    let mut select = block.select(&HITBOX_IMAGE_ELEMENT_SELECTOR);
+    let (hitbox_image_url, image_info) = {
+        let image = select.next().and_then(hitbox_image);
+        let hitbox = select.next().and_then(hitbox_image);
+        match hitbox.or(image) {
+            Some((url, info)) => (url, Some(info)),
+            None => (DEFAULT_IMAGE.to_string(), None),
+        }
     };
-    let mut data = block.select(&DATA_ROW_SELECTOR)
+    let data = block.select(&DATA_ROW_SELECTOR)
         .map(|e| get_lowest_child(e))
         .map(|e| e.inner_html())
         .collect::<Vec<String>>()
         .into_iter();
-    let damage = data.next().unwrap_or_else(|| String::from("-"));
-    let chip_damage = data.next().unwrap_or_else(|| String::from("-"));
-    let damage_scaling = data.next().unwrap_or_else(|| String::from("-"));
-    let guard = data.next().unwrap_or_else(|| String::from("-"));
-    let cancel = data.next().unwrap_or_else(|| String::from("-"));
-    let hitconfirm_window = data.next().unwrap_or_else(|| String::from("-"));
-    let startup = data.next().unwrap_or_else(|| String::from("-"));
-    let active = data.next().unwrap_or_else(|| String::from("-"));
-    let recovery = data.next().unwrap_or_else(|| String::from("-"));
-    let total = data.next().unwrap_or_else(|| String::from("-"));
-    let hitstun = data.next().unwrap_or_else(|| String::from("-"));
-    let blockstun = data.next().unwrap_or_else(|| String::from("-"));
-    let drive_damage_block = data.next().unwrap_or_else(|| String::from("-"));
-    let drive_damage_hit = data.next().unwrap_or_else(|| String::from("-"));
-    let drive_gain = data.next().unwrap_or_else(|| String::from("-"));
-    let super_gain_hit = data.next().unwrap_or_else(|| String::from("-"));
-    let super_gain_block = data.next().unwrap_or_else(|| String::from("-"));
-    let projectile_speed = data.next().unwrap_or_else(|| String::from("-"));
-    let invuln = data.next().unwrap_or_else(|| String::from("-"));
-    let armor = data.next().unwrap_or_else(|| String::from("-"));
-    let airborne = data.next().unwrap_or_else(|| String::from("-"));
-    let juggle_start = data.next().unwrap_or_else(|| String::from("-"));
-    let juggle_increase = data.next().unwrap_or_else(|| String::from("-"));
-    let juggle_limit = data.next().unwrap_or_else(|| String::from("-"));
-    let perfect_parry_advantage = data.next().unwrap_or_else(|| String::from("-"));
-    let after_dr_hit = data.next().unwrap_or_else(|| String::from("-"));
-    let after_dr_block = data.next().unwrap_or_else(|| String::from("-"));
-    let dr_cancel_hit = data.next().unwrap_or_else(|| String::from("-"));
-    let dr_cancel_block = data.next().unwrap_or_else(|| String::from("-"));
-    let punish_advantage = data.next().unwrap_or_else(|| String::from("-"));
-    let hit_advantage = data.next().unwrap_or_else(|| String::from("-"));
-    let block_advantage = data.next().unwrap_or_else(|| String::from("-"));
-    let notes = data.next().unwrap_or_else(|| String::from("-"));
-
-    let move_constructed = Move {
+
+    let mut move_constructed = Move {
         identifier,
         input,
+        modern_input,
         name,
         image_link: hitbox_image_url,
-        damage,
-        chip_damage,
-        damage_scaling,
-        guard,
-        cancel,
-        hitconfirm_window,
-        startup,
-        active,
-        recovery,
-        total,
-        hitstun,
-        blockstun,
-        drive_damage_block,
-        drive_damage_hit,
-        drive_gain,
-        super_gain_hit,
-        super_gain_block,
-        projectile_speed,
-        invuln,
-        armor,
-        airborne,
-        juggle_start,
-        juggle_increase,
-        juggle_limit,
-        perfect_parry_advantage,
-        after_dr_hit,
-        after_dr_block,
-        dr_cancel_hit,
-        dr_cancel_block,
-        punish_advantage,
-        hit_advantage,
-        block_advantage,
-        notes,
+        image_alt: String::new(),
+        image_info,
+        ..Move::default()
     };
+    for column in columns::wiki_columns() {
+        if let Some(value) = move_constructed.get_mut(column.field) {
+            *value = String::from("-");
+        }
+    }
+    let columns = table_header(block).unwrap_or_else(|| columns::wiki_columns().map(Some).collect());
+    for (column, value) in zip(columns, data) {
+        if let Some(field) = column.and_then(|column| move_constructed.get_mut(column.field)) {
+            *field = value;
+        }
+    }
+    move_constructed.cancel_options = CancelOptions::parse(&move_constructed.cancel);
+    move_constructed.category = category.map(|category| category.refine(&move_constructed));
     Some(move_constructed)
 }
 
+/// The [`columns`] of a move table's data cells, read from its header row of `th` cells like
+/// `Startup` and `Block Adv`, so reordered or added wiki columns still land in the right fields.
+/// Headers the registry doesn't know are `None`. Tables without a header row are read in
+/// [`columns::wiki_columns`] order.
+fn table_header(block: ElementRef) -> Option<Vec<Option<&'static columns::Column>>> {
+    block.select(&ROW_SELECTOR)
+        .filter(|row| row.child_elements().all(|cell| cell.value().name() == "th"))
+        .map(|row| row.select(&HEADER_CELL_SELECTOR).map(|th| columns::by_wiki_header(&th.text().collect::<String>())).collect::<Vec<_>>())
+        .find(|header| header.iter().any(Option::is_some))
+}
+
 fn get_lowest_child(parent: ElementRef) -> ElementRef {
     match parent.first_element_child() {
         None => parent,
@@ -327,6 +682,18 @@ fn get_lowest_child(parent: ElementRef) -> ElementRef {
     }
 }
 
+static IMAGE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img").unwrap());
+
+/// The url of an image link's `2x` srcset entry, along with what the page says about it
+fn hitbox_image(element: ElementRef) -> Option<(String, MediaInfo)> {
+    let url = hitbox_image_matcher(element.html())?;
+    let mut info = MediaInfo::from_url(&url);
+    if let Some(img) = element.select(&IMAGE_SELECTOR).next() {
+        info = info.with_img_dimensions(img, 2);
+    }
+    Some((url, info))
+}
+
 fn hitbox_image_matcher(element: String) -> Option<String> {
     HITBOX_IMAGE_URL_REGEX.captures(element.as_str())
         .and_then(|caps| caps.get(1))// skip first match: is whole match
@@ -335,23 +702,169 @@ fn hitbox_image_matcher(element: String) -> Option<String> {
 }
 
 static MOVE_CONTAINER_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div.movecontainer").unwrap());
-fn parse_move_containers(html: &Html) -> Vec<ElementRef> {
+fn parse_move_containers(html: &Html) -> Vec<ElementRef<'_>> {
     html.select(&MOVE_CONTAINER_SELECTOR).collect::<Vec<ElementRef>>()
 }
 
 static MOVE_CONTAINER_NAME_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div.movename").unwrap());
 static MOVE_GIF_ELEMENT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div.hitbox > a > img").unwrap());
+static MOVE_GIF_LINK_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("div.hitbox > a").unwrap());
 fn parse_move_container(container: ElementRef) -> Option<MoveGif> {
     let move_name = container.select(&MOVE_CONTAINER_NAME_SELECTOR).next()?.inner_html().trim().to_string();
-    let gif_url = container.select(&MOVE_GIF_ELEMENT_SELECTOR).next()?
-        .value().attr("src")?.to_string();
+    let gif = container.select(&MOVE_GIF_ELEMENT_SELECTOR).next()?;
+    let src = gif.value().attr("src")?;
+    // the link opens the full gif, the image itself is sometimes a smaller preview of it
+    let href = container.select(&MOVE_GIF_LINK_SELECTOR).next()
+        .and_then(|link| link.value().attr("href"))
+        .unwrap_or(src);
+    let url = format!("https://ultimateframedata.com/sf6/{}", href);
+    let thumbnail_url = (src != href).then(|| format!("https://ultimateframedata.com/sf6/{}", src));
+    let mut info = MediaInfo::from_url(&url);
+    if thumbnail_url.is_none() {
+        info = info.with_img_dimensions(gif, 1);
+    }
     Some(MoveGif {
         name: move_name,
-        url: format!("https://ultimateframedata.com/sf6/{}", gif_url),
+        move_identifier: None,
+        url,
+        thumbnail_url,
+        info,
     })
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    const PAGE: &str = r#"<html><body><div><div>
+        <section class="section-collapsible"><h5><span>5LP</span></h5><table class="wikitable">
+            <tr><th><div><p><span>5LP</span></p><div>Standing Light Punch</div></div></th></tr>
+            <tr><td>300</td><td>-</td><td>20% Starter</td><td>LH</td><td>Chn Sp SA</td><td>14</td><td>4</td><td>3</td><td>7</td><td>13</td></tr>
+        </table></section>
+        <section class="section-collapsible"><h5><span>2MK</span></h5><table class="wikitable">
+            <tr><th><div><p><span>2MK</span></p><div>Crouching Medium Kick</div></div></th></tr>
+            <tr><td>500</td><td>-</td><td>-</td><td>L</td><td>Sp SA</td><td>12</td><td>8</td><td>3</td><td>18</td><td>28</td></tr>
+        </table></section>
+    </div></div></body></html>"#;
+
+    #[test]
+    fn parses_pre_fetched_pages() {
+        let data = FrameData::from_html_pages(vec![(character::RYU.clone(), PAGE.to_string())]);
+        let moves = &data.find_character_frame_data(&character::RYU).unwrap().moves;
+        assert_eq!(parse_character_page(PAGE, &character::RYU).moves.len(), moves.len());
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].identifier, "5LP");
+        assert_eq!(moves[0].name, "Standing Light Punch");
+        assert_eq!(moves[0].startup, "4");
+        assert_eq!(moves[1].guard, "L");
+        assert_eq!(moves[1].notes, "-");
+    }
+
+    #[test]
+    fn parses_saved_files() {
+        let dir = crate::testing::TempDir::new("sf6rs-saved-pages-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ryu.html"), PAGE).unwrap();
+        std::fs::write(dir.join("ryu-gifs.html"), crate::testing::gif_page(&[("Standing Light Punch", "/sf6/hitboxes/ryu/Ryu5LP.gif")])).unwrap();
+        let html = std::fs::read_to_string(dir.join("ryu.html")).unwrap();
+        let gif_html = std::fs::read_to_string(dir.join("ryu-gifs.html")).unwrap();
+
+        let ryu = parse_character_pages(&character::RYU, &html, Some(&gif_html));
+        assert_eq!(ryu.character_id, character::RYU);
+        assert_eq!(ryu.moves.iter().map(|m| m.identifier.as_str()).collect::<Vec<_>>(), vec!["5LP", "2MK"]);
+        assert_eq!(ryu.gifs.len(), 1);
+        assert_eq!(ryu.gif_report().matched.len(), 1);
+        assert!(parse_character_page(&html, &character::RYU).gifs.is_empty());
+        assert!(parse_character_page("<html></html>", &character::RYU).moves.is_empty());
+    }
+
+    #[test]
+    fn reads_columns_by_header() {
+        let page = r#"<html><body><div><div>
+            <section class="section-collapsible"><h5><span>5LP</span></h5><table class="wikitable">
+                <tr><th><div><p><span>5LP</span></p><div>Standing Light Punch</div></div></th></tr>
+                <tr><th>Startup</th><th>Block Adv</th><th>Stance</th><th>Damage</th></tr>
+                <tr><td>4</td><td>-1</td><td>Standing</td><td>300</td></tr>
+            </table></section>
+        </div></div></body></html>"#;
+        let moves = parse_moves(&Html::parse_document(page));
+        assert_eq!((moves[0].startup.as_str(), moves[0].block_advantage.as_str(), moves[0].damage.as_str()), ("4", "-1", "300"));
+        assert_eq!(moves[0].recovery, "-");
+        assert!(columns::wiki_columns().all(|column| moves[0].get(column.field) != Some("Standing")));
+    }
 
-async fn request_gif_page(character_id: &CharacterId) -> Result<Html, Box<dyn Error>> {
-    let text = reqwest::get(character_id.gif_data_url()).await?.text().await?;
-    Ok(Html::parse_document(&text))
-}
\ No newline at end of file
+    #[test]
+    fn categorizes_moves_by_section() {
+        let section = |identifier: &str| format!("<section class=\"section-collapsible\"><h5><span>{0}</span></h5><table class=\"wikitable\">\
+            <tr><th><div><p><span>{0}</span></p><div>{0}</div></div></th></tr></table></section>", identifier);
+        let page = format!("<html><body><h2>Frame Data</h2><div><div>{}</div></div><h2>Normals</h2><div><div>{}{}</div></div>\
+            <h2>Special Moves</h2><h3>Shoryuken</h3><div><div>{}{}</div></div></body></html>",
+            section("Drive Parry"), section("5LP"), section("6HP"), section("623HP"), section("623PP"));
+        let moves = parse_moves(&Html::parse_document(&page));
+        let categories = moves.iter().map(|m| m.category).collect::<Vec<Option<MoveCategory>>>();
+        assert_eq!(categories, vec![
+            None, Some(MoveCategory::Normal), Some(MoveCategory::CommandNormal),
+            Some(MoveCategory::Special), Some(MoveCategory::OverDriveSpecial),
+        ]);
+        assert_eq!(moves[0].category(), MoveCategory::DriveSystem);
+    }
+
+    #[test]
+    fn reparses_archived_pages() {
+        let dir = crate::testing::TempDir::new("sf6rs-reparse-test");
+        let archive = archive::HtmlArchive::new(dir.to_path_buf());
+        let terry = character::CharacterId::new("terry", "terry", "Terry_Bogard", "terry", r"terry", 2, "2024-09-24").unwrap();
+        archive.store(&terry, archive::PageKind::FrameData, PAGE).unwrap();
+        archive.store(&character::KEN, archive::PageKind::FrameData, PAGE).unwrap();
+        assert_eq!(archive.ids().unwrap(), vec!["ken", "terry"]);
+        let data = archive::reparse_cache(&archive).unwrap();
+        let ken = data.find_character_frame_data(&character::KEN).unwrap();
+        assert_eq!(ken.moves.len(), 2);
+        assert!(ken.gifs.is_empty());
+        assert_eq!(data.character_frame_data[1].character_id.frame_data_id, "Terry");
+        let mut registry = character::CharacterRegistry::empty();
+        registry.register(terry.clone());
+        let data = archive::reparse_cache_with(&archive, &registry).unwrap();
+        assert_eq!(data.character_frame_data[0].character_id.frame_data_id, terry.frame_data_id);
+        assert_eq!(data.character_frame_data.len(), 2);
+    }
+
+    #[test]
+    fn gets_fields_by_name() {
+        let data = parse_all(vec![(character::RYU.clone(), PAGE.to_string())]);
+        let move_data = data.find_move_character(&character::RYU, "2MK").unwrap();
+        assert!(Move::FIELDS.iter().all(|name| move_data.get(name).is_some()));
+        assert_eq!(move_data.get("guard"), Some("L"));
+        assert_eq!(move_data.get("gifs"), None);
+    }
+
+    #[test]
+    fn scores_completeness() {
+        let data = parse_all(vec![(character::RYU.clone(), PAGE.to_string())]);
+        let ryu = data.find_character_frame_data(&character::RYU).unwrap();
+        // the fixture fills in 9 and 8 of the 33 data columns
+        assert!((ryu.completeness() - 17.0 / 66.0).abs() < f32::EPSILON);
+        assert_eq!(Move::default().completeness(), 0.0);
+    }
+
+    #[test]
+    fn flags_partial_pages() {
+        let data = parse_all(vec![(character::RYU.clone(), PAGE.to_string())]);
+        let ryu = data.find_character_frame_data(&character::RYU).unwrap();
+        assert!(matches!(ryu.status(), DataStatus::PartialData { moves: 2, .. }));
+        let empty = testing::character_frame_data(&character::RYU, Vec::new());
+        assert_eq!(empty.status(), DataStatus::Empty);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trips_through_json() {
+        let data = parse_all(vec![(character::CHUNLI.clone(), PAGE.to_string())]);
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["character_frame_data"][0]["character_id"], "chunli");
+        let parsed: FrameData = serde_json::from_value(json).unwrap();
+        let chunli = parsed.find_character_frame_data(&character::CHUNLI).unwrap();
+        assert_eq!(chunli.moves[1].identifier, "2MK");
+        assert!(serde_json::from_str::<CharacterId>("\"chunlii\"").is_err());
+    }
+}
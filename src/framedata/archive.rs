@@ -0,0 +1,140 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::character::discovery::derive_character;
+use crate::character::{is_safe_file_stem, CharacterId, CharacterRegistry};
+use crate::framedata::version::GameVersion;
+use crate::framedata::{parse_character_pages, FrameData};
+
+/// The kinds of raw pages this library scrapes per character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageKind {
+    /// The supercombo.gg frame data page, see [`CharacterId::frame_data_url`]
+    FrameData,
+    /// The ultimateframedata.com gif page, see [`CharacterId::gif_data_url`]
+    Gifs,
+}
+
+impl PageKind {
+    fn file_suffix(&self) -> &'static str {
+        match self {
+            PageKind::FrameData => "data",
+            PageKind::Gifs => "gifs",
+        }
+    }
+}
+
+/// A directory of raw scraped html, laid out as `<character id>.<data|gifs>.html`. Retaining the
+/// raw pages means parser fixes can be applied retroactively with [`reparse_cache`] instead of
+/// re-downloading everything.
+#[derive(Debug, Clone)]
+pub struct HtmlArchive {
+    dir: PathBuf,
+}
+
+impl HtmlArchive {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        HtmlArchive {
+            dir: dir.into(),
+        }
+    }
+
+    /// An archive in a subdirectory of this one for pages of a single game version, so pages
+    /// from before and after a balance patch are never read back together
+    pub fn for_version(&self, version: &GameVersion) -> HtmlArchive {
+        HtmlArchive::new(self.dir.join(version.key()))
+    }
+
+    /// The directory this archive reads from and writes to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path a given page is stored at, whether or not it exists yet. Pages of characters
+    /// whose id isn't a safe file name, e.g. `../ryu`, are never stored or read.
+    pub fn path(&self, character_id: &CharacterId, kind: PageKind) -> PathBuf {
+        self.path_for_id(&character_id.id, kind)
+    }
+
+    fn path_for_id(&self, id: &str, kind: PageKind) -> PathBuf {
+        self.dir.join(format!("{}.{}.html", id, kind.file_suffix()))
+    }
+
+    /// Stores a raw page, replacing any previously archived copy
+    pub fn store(&self, character_id: &CharacterId, kind: PageKind, html: &str) -> io::Result<()> {
+        let path = self.path_for_id(character_id.file_stem()?, kind);
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, html)
+    }
+
+    /// The ids of every character with an archived frame data page, sorted
+    pub fn ids(&self) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let suffix = format!(".{}.html", PageKind::FrameData.file_suffix());
+        let mut ids = Vec::new();
+        for entry in entries {
+            let file_name = entry?.file_name();
+            let id = file_name.to_str().and_then(|name| name.strip_suffix(&suffix));
+            if let Some(id) = id.filter(|id| is_safe_file_stem(id)) {
+                ids.push(id.to_string());
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Reads a raw page back, returning `None` if it was never archived
+    pub fn read(&self, character_id: &CharacterId, kind: PageKind) -> io::Result<Option<String>> {
+        self.read_id(character_id.file_stem()?, kind)
+    }
+
+    fn read_id(&self, id: &str, kind: PageKind) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path_for_id(id, kind)) {
+            Ok(html) => Ok(Some(html)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Re-runs the current parser over every character page stored in `archive`, in parallel and
+/// without any web-requests, see [`reparse_cache_with`]. Archived characters the crate doesn't
+/// support are described with [`derive_character`].
+pub fn reparse_cache(archive: &HtmlArchive) -> io::Result<FrameData> {
+    reparse_cache_with(archive, &CharacterRegistry::builtin())
+}
+
+/// Re-runs the current parser over every frame data page stored in `archive`, resolving their ids
+/// against `registry` and describing any other character with [`derive_character`]. Characters
+/// are returned in `registry` order, followed by the others by id. A missing gif page only leaves
+/// that character's `gifs` empty.
+pub fn reparse_cache_with(archive: &HtmlArchive, registry: &CharacterRegistry) -> io::Result<FrameData> {
+    let mut pages = Vec::new();
+    for id in archive.ids()? {
+        let character_id = registry.get_by_id(&id).cloned().unwrap_or_else(|| {
+            let mut chars = id.chars();
+            let name = chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default();
+            derive_character(&name)
+        });
+        let Some(html) = archive.read_id(&id, PageKind::FrameData)? else {
+            continue;
+        };
+        let gif_html = archive.read_id(&id, PageKind::Gifs)?;
+        pages.push((character_id, html, gif_html));
+    }
+    pages.sort_by_key(|(character_id, _, _)| registry.iter().position(|c| c == character_id).unwrap_or(usize::MAX));
+    let character_frame_data = pages.into_par_iter()
+        .map(|(character_id, html, gif_html)| parse_character_pages(&character_id, &html, gif_html.as_deref()))
+        .collect();
+    Ok(FrameData {
+        character_frame_data
+    })
+}
@@ -0,0 +1,96 @@
+use scraper::{Html, Selector};
+
+use crate::text::wiki_file_name;
+use crate::LazyLock;
+
+static IMAGE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("img").unwrap());
+
+/// What a piece of character art shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ArtKind {
+    /// The character select portrait
+    Portrait,
+    /// A costume or color, in the order they appear on the page
+    Costume,
+}
+
+/// A character portrait or costume image from the wiki
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArtImage {
+    pub kind: ArtKind,
+    /// The image's file name on the wiki, e.g. `SF6_Ryu_Costume_2.png`
+    pub name: String,
+    pub url: String,
+}
+
+/// Finds the portrait and costume images on a character's overview page (see
+/// [`CharacterId::overview_url`](crate::character::CharacterId::overview_url)) by their file
+/// names. Thumbnails are resolved to their largest listed size.
+pub fn parse_character_art(html: &str) -> Vec<ArtImage> {
+    let html = Html::parse_document(html);
+    let mut art: Vec<ArtImage> = Vec::new();
+    for img in html.select(&IMAGE_SELECTOR) {
+        let Some(path) = largest_source(img.value().attr("srcset")).or(img.value().attr("src")) else {
+            continue;
+        };
+        let name = wiki_file_name(path).to_string();
+        let lower = name.to_ascii_lowercase();
+        let kind = if lower.contains("portrait") {
+            ArtKind::Portrait
+        } else if ["costume", "outfit", "color", "colour"].iter().any(|k| lower.contains(k)) {
+            ArtKind::Costume
+        } else {
+            continue;
+        };
+        if art.iter().any(|a| a.name == name) {
+            continue;
+        }
+        let url = if path.starts_with("http") {
+            path.to_string()
+        } else {
+            format!("https://wiki.supercombo.gg{}", path)
+        };
+        art.push(ArtImage {
+            kind,
+            name,
+            url,
+        });
+    }
+    art
+}
+
+/// The path of the last (largest) candidate in a `srcset`
+fn largest_source(srcset: Option<&str>) -> Option<&str> {
+    srcset?.split(',').next_back()?.split_whitespace().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_portraits_and_costumes() {
+        let page = r#"<html><body>
+            <img src="/images/thumb/a/a1/SF6_Ryu_Portrait.png/300px-SF6_Ryu_Portrait.png" srcset="/images/thumb/a/a1/SF6_Ryu_Portrait.png/450px-SF6_Ryu_Portrait.png 1.5x, /images/a/a1/SF6_Ryu_Portrait.png 2x">
+            <img src="/images/b/b2/SF6_Ryu_Costume_2.png">
+            <img src="/images/b/b2/SF6_Ryu_Costume_2.png">
+            <img src="/images/c/c3/SF6_Ryu_5lp.png">
+        </body></html>"#;
+        let art = parse_character_art(page);
+        assert_eq!(art, vec![
+            ArtImage {
+                kind: ArtKind::Portrait,
+                name: String::from("SF6_Ryu_Portrait.png"),
+                url: String::from("https://wiki.supercombo.gg/images/a/a1/SF6_Ryu_Portrait.png"),
+            },
+            ArtImage {
+                kind: ArtKind::Costume,
+                name: String::from("SF6_Ryu_Costume_2.png"),
+                url: String::from("https://wiki.supercombo.gg/images/b/b2/SF6_Ryu_Costume_2.png"),
+            },
+        ]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Synchronous versions of [`load_all`](super::load_all) and [`load`](super::load) built on
+//! [`reqwest::blocking`], for scripts and applications that don't run an async runtime. Without
+//! the default `async` feature the crate doesn't depend on tokio itself:
+//! ```toml
+//! sf6rs = { version = "0.4", default-features = false, features = ["blocking"] }
+//! ```
+//! Characters are loaded one after another with no retries, rate limiting or media probing, which
+//! need the async [`Loader`](super::Loader). Like every [`reqwest::blocking`] client, these must
+//! not be called from within an async runtime.
+//! ```no_run
+//! let report = sf6rs::framedata::blocking::load_all();
+//! let ryu = sf6rs::framedata::blocking::load(&sf6rs::character::RYU).unwrap();
+//! ```
+
+use reqwest::blocking::Client;
+
+use crate::character::{CharacterId, CHARACTERS};
+use crate::error::SF6Error;
+use crate::framedata::{parse_character_pages, require_moves, CharacterFrameData, FrameData, LoadReport};
+
+/// Loads every character like [`load_all`](super::load_all), blocking until they're all done
+pub fn load_all() -> LoadReport {
+    load_all_with(&Client::new())
+}
+
+/// Loads one character like [`load`](super::load), blocking until it's done
+pub fn load(character_id: &CharacterId) -> Result<CharacterFrameData, SF6Error> {
+    load_with(&Client::new(), character_id)
+}
+
+/// [`load_all`] sending every request through `client`, e.g. one configured with a proxy or a
+/// user agent
+pub fn load_all_with(client: &Client) -> LoadReport {
+    let mut report = LoadReport {
+        data: FrameData {
+            character_frame_data: Vec::new(),
+        },
+        failures: Vec::new(),
+    };
+    for character_id in CHARACTERS.iter() {
+        match load_with(client, character_id) {
+            Ok(character_frame_data) => report.data.character_frame_data.push(character_frame_data),
+            Err(e) => report.failures.push(((*character_id).clone(), e)),
+        }
+    }
+    report
+}
+
+/// [`load`] sending every request through `client`
+pub fn load_with(client: &Client, character_id: &CharacterId) -> Result<CharacterFrameData, SF6Error> {
+    let (html, gif_html) = request_pages(client, character_id).map_err(|e| e.for_character(character_id))?;
+    require_moves(parse_character_pages(character_id, &html, Some(&gif_html)))
+}
+
+/// The character's frame data and gif pages
+fn request_pages(client: &Client, character_id: &CharacterId) -> Result<(String, String), SF6Error> {
+    Ok((request_page(client, &character_id.frame_data_url())?, request_page(client, &character_id.gif_data_url())?))
+}
+
+fn request_page(client: &Client, url: &str) -> Result<String, SF6Error> {
+    let response = client.get(url).send().map_err(|e| SF6Error::from_reqwest(url, e))?;
+    if !response.status().is_success() {
+        return Err(SF6Error::Http { url: url.to_string(), status: response.status().as_u16() });
+    }
+    response.text().map_err(|e| SF6Error::from_reqwest(url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::character;
+    use crate::framedata::Move;
+    use crate::testing;
+
+    /// Serves `bodies` to one connection each from a local port, returning its url
+    fn serve(bodies: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/page", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for (status, body) in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let _ = stream.read(&mut [0; 4096]).unwrap();
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn loads_without_a_runtime() {
+        let jab = Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() };
+        let page = testing::frame_data_page(&[jab]);
+        let url = serve(vec![("200 OK", page), ("200 OK", testing::gif_page(&[])), ("404 Not Found", String::new())]);
+        let ryu = character::RYU.clone().with_frame_data_url(url.clone()).with_gif_data_url(url.clone());
+        let client = Client::new();
+        let loaded = load_with(&client, &ryu).unwrap();
+        assert_eq!(loaded.moves.iter().map(|m| m.identifier.as_str()).collect::<Vec<&str>>(), ["5LP"]);
+
+        assert!(matches!(request_page(&client, &url), Err(SF6Error::Http { status: 404, .. })));
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tokio::task::JoinSet;
+
+use crate::character::{CharacterId, CHARACTERS};
+use crate::error::SF6Error;
+use crate::framedata::archive::{HtmlArchive, PageKind};
+use crate::framedata::telemetry::CharacterTelemetry;
+use crate::framedata::version::{detect_game_version, GameVersion};
+use crate::framedata::{CharacterFrameData, FrameData, LoadReport, Loader};
+
+/// How long cached data is used before it is fetched again unless configured otherwise
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Loads frame data through a directory of parsed results, laid out as `<character id>.json`.
+/// Characters cached within the TTL are read from disk without any web-requests; stale or missing
+/// ones are loaded with the wrapped [`Loader`] and written back, with their raw pages kept in the
+/// cache's [`archive`](CachedLoader::archive). If a refresh fails, the stale
+/// copy is used rather than dropping the character. With a [`CachedLoader::version`], each game
+/// version is cached in its own subdirectory, so data from before and after a patch is never mixed.
+/// ```no_run
+/// # async fn run() {
+/// use std::time::Duration;
+/// use sf6rs::framedata::cache::CachedLoader;
+///
+/// let data = CachedLoader::new("cache/framedata")
+///     .ttl(Duration::from_secs(6 * 60 * 60))
+///     .load_all_cached()
+///     .await;
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedLoader {
+    loader: Loader,
+    root: PathBuf,
+    dir: PathBuf,
+    ttl: Duration,
+    version: Option<GameVersion>,
+}
+
+impl CachedLoader {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let root = dir.into();
+        CachedLoader {
+            loader: Loader::default(),
+            dir: root.clone(),
+            root,
+            ttl: DEFAULT_TTL,
+            version: None,
+        }
+    }
+
+    /// How long a cached character stays fresh
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Caches only data for `version`, in the `<dir>/<version key>` subdirectory. A character whose
+    /// page states another version, or none, fails with [`SF6Error::VersionMismatch`] unless a copy
+    /// for `version` is already cached.
+    pub fn version(mut self, version: GameVersion) -> Self {
+        self.dir = self.root.join(version.key());
+        self.version = Some(version);
+        self
+    }
+
+    /// The loader used for characters that have to be fetched
+    pub fn loader(mut self, loader: Loader) -> Self {
+        self.loader = loader;
+        self
+    }
+
+    /// The directory this cache reads from and writes to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The raw pages of every character fetched through this cache, stored alongside their parsed
+    /// entries so parser fixes can be applied with
+    /// [`reparse_cache`](crate::framedata::archive::reparse_cache)
+    pub fn archive(&self) -> HtmlArchive {
+        HtmlArchive::new(&self.dir)
+    }
+
+    /// Returns the path a character is cached at, whether or not it exists yet. Characters whose id
+    /// isn't a safe file name, e.g. `../ryu`, are never cached or read from disk.
+    pub fn path(&self, character_id: &CharacterId) -> PathBuf {
+        self.dir.join(format!("{}.json", character_id.id))
+    }
+
+    /// Whether `character_id` is cached and younger than the TTL
+    pub fn is_fresh(&self, character_id: &CharacterId) -> bool {
+        character_id.file_stem().is_ok() && fs::metadata(self.path(character_id))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < self.ttl)
+    }
+
+    /// Loads one character, only making web-requests if their cached data is stale
+    pub async fn load_cached(&self, character_id: &CharacterId) -> Result<CharacterFrameData, SF6Error> {
+        if self.is_fresh(character_id) {
+            if let Some(cached) = self.read(character_id)? {
+                return Ok(cached);
+            }
+        }
+        let mut telemetry = CharacterTelemetry::new(character_id);
+        let result = match self.loader.fetch_timed(character_id, &mut telemetry).await {
+            Ok((html, gif_html)) => match (detect_game_version(&html), &self.version) {
+                (found, Some(expected)) if found.as_ref() != Some(expected) => Err(SF6Error::VersionMismatch {
+                    character: character_id.id.to_string(),
+                    expected: expected.clone(),
+                    found,
+                }),
+                _ => {
+                    let archive = self.archive();
+                    archive.store(character_id, PageKind::FrameData, &html)?;
+                    archive.store(character_id, PageKind::Gifs, &gif_html)?;
+                    self.loader.parse_timed(character_id, &html, &gif_html, &mut telemetry).await
+                }
+            },
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(character_frame_data) => {
+                self.write(&character_frame_data)?;
+                Ok(character_frame_data)
+            }
+            Err(e) => match self.read(character_id)? {
+                Some(stale) => Ok(stale),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Loads every character like [`load_all`](super::load_all), only making web-requests for
+    /// characters whose cached data is stale
+    pub async fn load_all_cached(&self) -> LoadReport {
+        let mut report = LoadReport {
+            data: FrameData {
+                character_frame_data: Vec::new()
+            },
+            failures: Vec::new(),
+        };
+        let mut set = JoinSet::new();
+        let mut tasks = HashMap::new();
+        for character_id in (*CHARACTERS).iter().copied() {
+            let cache = self.clone();
+            let handle = set.spawn(async move { cache.load_cached(character_id).await });
+            tasks.insert(handle.id(), character_id);
+        }
+        while let Some(res) = set.join_next_with_id().await {
+            let (id, result) = match res {
+                Ok((id, result)) => (id, result),
+                Err(e) => {
+                    let character = tasks.get(&e.id()).map_or_else(String::new, |character_id| character_id.id.to_string());
+                    (e.id(), Err(SF6Error::Task { character, message: e.to_string() }))
+                }
+            };
+            match (result, tasks.get(&id)) {
+                (Ok(character_frame_data), _) => report.data.character_frame_data.push(character_frame_data),
+                (Err(e), Some(character_id)) => report.failures.push(((*character_id).clone(), e)),
+                (Err(_), None) => {}
+            }
+        }
+        report
+    }
+
+    fn read(&self, character_id: &CharacterId) -> io::Result<Option<CharacterFrameData>> {
+        character_id.file_stem()?;
+        let json = match fs::read_to_string(self.path(character_id)) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        // an unreadable entry, e.g. from an older version of this library, is treated as missing
+        Ok(serde_json::from_str(&json).ok())
+    }
+
+    fn write(&self, character_frame_data: &CharacterFrameData) -> io::Result<()> {
+        character_frame_data.character_id.file_stem()?;
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string(character_frame_data).map_err(io::Error::other)?;
+        let path = self.path(&character_frame_data.character_id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::loader::tests::{response, serve};
+    use crate::framedata::RetryPolicy;
+    use crate::framedata::snapshot::read_versioned_snapshot;
+    use crate::testing::{self, TempDir};
+
+    #[tokio::test]
+    async fn reads_fresh_entries_without_requests() {
+        let dir = TempDir::new("sf6rs-cached-loader-test");
+        let cache = CachedLoader::new(dir.to_path_buf());
+        assert!(!cache.is_fresh(&character::JP));
+        let cached = testing::character_frame_data(&character::JP, vec![crate::framedata::Move {
+            identifier: String::from("5LP"),
+            ..Default::default()
+        }]);
+        cache.write(&cached).unwrap();
+        assert!(cache.is_fresh(&character::JP));
+        assert!(!cache.clone().ttl(Duration::ZERO).is_fresh(&character::JP));
+        let loaded = cache.load_cached(&character::JP).await.unwrap();
+        assert_eq!(loaded.moves[0].identifier, "5LP");
+    }
+
+    #[test]
+    fn never_writes_outside_its_directory() {
+        let dir = TempDir::new("sf6rs-cached-loader-escape-test");
+        let cache = CachedLoader::new(dir.join("cache"));
+        let escaping = CharacterId::new("../escape", "escape", "Escape", "escape", r"escape", 0, "").unwrap();
+        let entry = testing::character_frame_data(&escaping, Vec::new());
+        assert_eq!(cache.write(&entry).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(cache.archive().store(&escaping, PageKind::FrameData, "<html></html>").is_err());
+        assert!(cache.read(&escaping).is_err() && !cache.is_fresh(&escaping));
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn caches_by_game_version() {
+        let dir = TempDir::new("sf6rs-cached-loader-version-test");
+        let page = testing::frame_data_page(&[crate::framedata::Move { identifier: String::from("5LP"), startup: String::from("4"), ..Default::default() }]).replace("<body>", r#"<body><div data-game-version="1.0800"></div>"#);
+        let ok = |body: &str| response("200 OK", "", body);
+        let (url, _) = serve(vec![ok(&page), ok("<html></html>"), ok(&page), ok("<html></html>")]).await;
+        let ryu = character::RYU.clone().with_frame_data_url(url.clone()).with_gif_data_url(url);
+        let loader = Loader::new().retry_policy(RetryPolicy::none());
+
+        let current = CachedLoader::new(dir.to_path_buf()).loader(loader.clone()).version(GameVersion::new("1.0800"));
+        assert_eq!(current.path(&ryu), dir.join("1.0800").join("ryu.json"));
+        assert_eq!(current.load_cached(&ryu).await.unwrap().moves.len(), 1);
+        let snapshot = read_versioned_snapshot(&dir, &GameVersion::new("1.0800")).unwrap();
+        assert_eq!(snapshot.character_frame_data[0].character_id, character::RYU);
+        assert_eq!(current.archive().read(&ryu, PageKind::FrameData).unwrap(), Some(page.clone()));
+
+        let next = CachedLoader::new(dir.to_path_buf()).loader(loader).version(GameVersion::new("1.0900"));
+        assert_eq!(next.load_cached(&ryu).await.unwrap_err(), SF6Error::VersionMismatch {
+            character: String::from("ryu"),
+            expected: GameVersion::new("1.0900"),
+            found: Some(GameVersion::new("1.0800")),
+        });
+        assert!(!next.path(&ryu).exists());
+        assert!(next.archive().ids().unwrap().is_empty());
+    }
+}
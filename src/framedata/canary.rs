@@ -0,0 +1,121 @@
+use scraper::Html;
+
+use crate::character::{self, CharacterId};
+use crate::framedata::{columns, is_placeholder, parse_moves, select_move_blocks, select_move_identifiers, DATA_ROW_SELECTOR};
+
+/// The character whose frame data page [`canary_check`](super::canary_check) fetches. Ryu has been
+/// on the wiki since launch and his page is rarely restructured except by template changes.
+pub static CANARY_CHARACTER: &CharacterId = &character::RYU;
+
+/// The number of frame data columns a move's table has on the current wiki template
+fn expected_columns() -> usize {
+    columns::wiki_columns().count()
+}
+/// Moves every version of the canary page has had, by identifier
+const EXPECTED_MOVES: [&str; 4] = ["5LP", "2MK", "236P", "623HP"];
+/// A fully documented character has far more moves than this
+const MIN_MOVES: usize = 30;
+
+/// The outcome of a [`canary_check`](super::canary_check). Anything other than
+/// [`CanaryStatus::Healthy`] means a full refresh is likely to produce bad data and an operator
+/// should look at the wiki before it runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CanaryStatus {
+    /// The page parsed the way it always has
+    Healthy,
+    /// Moves still parse, but something about the layout changed, e.g. a column was added or
+    /// removed or well-known moves went missing
+    Degraded(Vec<String>),
+    /// The page was fetched but no moves could be parsed from it
+    Broken(Vec<String>),
+    /// The page could not be fetched at all
+    Unreachable(String),
+}
+
+impl CanaryStatus {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, CanaryStatus::Healthy)
+    }
+
+    /// Human readable descriptions of what was wrong with the page
+    pub fn problems(&self) -> &[String] {
+        match self {
+            CanaryStatus::Degraded(problems) | CanaryStatus::Broken(problems) => problems,
+            CanaryStatus::Healthy | CanaryStatus::Unreachable(_) => &[],
+        }
+    }
+}
+
+/// Validates the structure of the canary character's frame data page (see
+/// [`CANARY_CHARACTER`]) without making any web-requests
+pub fn check_page(html: &str) -> CanaryStatus {
+    let html = Html::parse_document(html);
+    let identifiers = select_move_identifiers(&html);
+    let blocks = select_move_blocks(&html);
+    let moves = parse_moves(&html);
+    if moves.is_empty() {
+        return CanaryStatus::Broken(vec![format!(
+            "no moves parsed from {} move headings and {} move tables",
+            identifiers.len(), blocks.len(),
+        )]);
+    }
+
+    let mut problems = Vec::new();
+    if identifiers.len() != blocks.len() {
+        problems.push(format!("{} move headings but {} move tables, some moves may be missing", identifiers.len(), blocks.len()));
+    }
+    if moves.len() < MIN_MOVES {
+        problems.push(format!("only {} moves parsed, expected at least {}", moves.len(), MIN_MOVES));
+    }
+    for expected in EXPECTED_MOVES {
+        if !moves.iter().any(|m| m.identifier.eq_ignore_ascii_case(expected)) {
+            problems.push(format!("move {} is missing", expected));
+        }
+    }
+    let columns = blocks.iter()
+        .map(|block| block.select(&DATA_ROW_SELECTOR).count())
+        .max()
+        .unwrap_or_default();
+    if columns != expected_columns() {
+        problems.push(format!("move tables have {} columns, expected {}", columns, expected_columns()));
+    }
+    if moves.iter().all(|m| is_placeholder(&m.startup)) {
+        problems.push(String::from("no move has a startup value"));
+    }
+
+    if problems.is_empty() {
+        CanaryStatus::Healthy
+    } else {
+        CanaryStatus::Degraded(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(moves: usize, columns: usize) -> String {
+        let mut html = String::from("<html><body><div><div>");
+        for i in 0..moves {
+            let identifier = EXPECTED_MOVES.get(i).map(|m| m.to_string()).unwrap_or_else(|| format!("move{}", i));
+            html.push_str(&format!(
+                "<section class=\"section-collapsible\"><h5><span>{0}</span></h5><table class=\"wikitable\">\
+                <tr><th><div><p><span>{0}</span></p><div>Move</div></div></th></tr><tr>{1}</tr></table></section>",
+                identifier, "<td>5</td>".repeat(columns),
+            ));
+        }
+        html.push_str("</div></div></body></html>");
+        html
+    }
+
+    #[test]
+    fn checks_page_structure() {
+        assert!(check_page(&page(MIN_MOVES, expected_columns())).is_healthy());
+        let added_column = check_page(&page(MIN_MOVES, expected_columns() + 1));
+        assert!(matches!(&added_column, CanaryStatus::Degraded(problems) if problems.len() == 1));
+        let missing_moves = check_page(&page(2, expected_columns()));
+        assert_eq!(missing_moves.problems().len(), 3);
+        assert!(matches!(check_page("<html><body><p>Template changed</p></body></html>"), CanaryStatus::Broken(_)));
+    }
+}
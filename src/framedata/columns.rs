@@ -0,0 +1,140 @@
+//! Metadata for every [`Move`](super::Move) field, shared by the parser, formatters and exporters
+//! so they stay consistent when the wiki adds or reorders columns.
+
+/// What a column describes, for grouping columns in tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnCategory {
+    /// What the move is: identifier, input, name and images
+    Identity,
+    Damage,
+    /// How the move can be blocked and cancelled
+    Properties,
+    /// Startup, active and recovery frames
+    Timing,
+    /// Frame advantage in its various situations
+    Advantage,
+    /// Drive and Super gauge
+    Gauge,
+    /// Invincibility, armor and airborne frames
+    Defense,
+    Juggle,
+    Notes,
+}
+
+/// What a numeric column counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    Frames,
+    /// Health points
+    Damage,
+    /// Drive or Super gauge points
+    Gauge,
+    Percent,
+    /// Juggle counter points
+    Points,
+}
+
+/// Describes one [`Move`](super::Move) field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Column {
+    /// The struct field name, as accepted by [`Move::get`](super::Move::get)
+    pub field: &'static str,
+    /// A short human readable name for table headers, e.g. `Startup`
+    pub label: &'static str,
+    /// The header of the wiki table column this field is scraped from, `None` for fields read from
+    /// elsewhere on the page
+    pub wiki_header: Option<&'static str>,
+    pub category: ColumnCategory,
+    pub unit: Option<Unit>,
+    /// Whether values usually start with a number that can be compared
+    pub numeric: bool,
+}
+
+const fn column(field: &'static str, label: &'static str, wiki_header: Option<&'static str>, category: ColumnCategory, unit: Option<Unit>) -> Column {
+    Column {
+        field,
+        label,
+        wiki_header,
+        category,
+        unit,
+        numeric: unit.is_some(),
+    }
+}
+
+use ColumnCategory::*;
+
+/// Every column in [`Move::FIELDS`](super::Move::FIELDS) order. Columns with a `wiki_header` appear
+/// in the order the wiki's table lists them.
+pub static COLUMNS: [Column; 38] = [
+    column("identifier", "Move", None, Identity, None),
+    column("input", "Input", None, Identity, None),
+    column("name", "Name", None, Identity, None),
+    column("image_link", "Image", None, Identity, None),
+    column("image_alt", "Image Description", None, Identity, None),
+    column("damage", "Damage", Some("Damage"), Damage, Some(Unit::Damage)),
+    column("chip_damage", "Chip Damage", Some("Chip Damage"), Damage, Some(Unit::Damage)),
+    column("damage_scaling", "Scaling", Some("Damage Scaling"), Damage, Some(Unit::Percent)),
+    column("guard", "Guard", Some("Guard"), Properties, None),
+    column("cancel", "Cancel", Some("Cancel"), Properties, None),
+    column("hitconfirm_window", "Hit Confirm", Some("Hit Confirm Window"), Properties, Some(Unit::Frames)),
+    column("startup", "Startup", Some("Startup"), Timing, Some(Unit::Frames)),
+    column("active", "Active", Some("Active"), Timing, Some(Unit::Frames)),
+    column("recovery", "Recovery", Some("Recovery"), Timing, Some(Unit::Frames)),
+    column("total", "Total", Some("Total"), Timing, Some(Unit::Frames)),
+    column("hitstun", "Hitstun", Some("Hitstun"), Timing, Some(Unit::Frames)),
+    column("blockstun", "Blockstun", Some("Blockstun"), Timing, Some(Unit::Frames)),
+    column("drive_damage_block", "Drive Damage (Block)", Some("Drive Dmg Blk"), Gauge, Some(Unit::Gauge)),
+    column("drive_damage_hit", "Drive Damage (Hit)", Some("Drive Dmg Hit"), Gauge, Some(Unit::Gauge)),
+    column("drive_gain", "Drive Gain", Some("Drive Gain"), Gauge, Some(Unit::Gauge)),
+    column("super_gain_hit", "Super Gain (Hit)", Some("Super Gain Hit"), Gauge, Some(Unit::Gauge)),
+    column("super_gain_block", "Super Gain (Block)", Some("Super Gain Blk"), Gauge, Some(Unit::Gauge)),
+    column("projectile_speed", "Projectile Speed", Some("Projectile Speed"), Properties, None),
+    column("invuln", "Invulnerable", Some("Invuln"), Defense, Some(Unit::Frames)),
+    column("armor", "Armor", Some("Armor"), Defense, Some(Unit::Frames)),
+    column("airborne", "Airborne", Some("Airborne"), Defense, Some(Unit::Frames)),
+    column("juggle_start", "Juggle Start", Some("Juggle Start"), Juggle, Some(Unit::Points)),
+    column("juggle_increase", "Juggle Increase", Some("Juggle Increase"), Juggle, Some(Unit::Points)),
+    column("juggle_limit", "Juggle Limit", Some("Juggle Limit"), Juggle, Some(Unit::Points)),
+    column("perfect_parry_advantage", "Perfect Parry", Some("Perfect Parry Adv"), Advantage, Some(Unit::Frames)),
+    column("after_dr_hit", "After Drive Rush (Hit)", Some("After DR Hit"), Advantage, Some(Unit::Frames)),
+    column("after_dr_block", "After Drive Rush (Block)", Some("After DR Blk"), Advantage, Some(Unit::Frames)),
+    column("dr_cancel_hit", "Drive Rush Cancel (Hit)", Some("DR Cancel Hit"), Advantage, Some(Unit::Frames)),
+    column("dr_cancel_block", "Drive Rush Cancel (Block)", Some("DR Cancel Blk"), Advantage, Some(Unit::Frames)),
+    column("punish_advantage", "On Punish", Some("Punish Adv"), Advantage, Some(Unit::Frames)),
+    column("hit_advantage", "On Hit", Some("Hit Adv"), Advantage, Some(Unit::Frames)),
+    column("block_advantage", "On Block", Some("Block Adv"), Advantage, Some(Unit::Frames)),
+    column("notes", "Notes", Some("Notes"), Notes, None),
+];
+
+/// Finds a column by its field name
+pub fn by_field(field: &str) -> Option<&'static Column> {
+    COLUMNS.iter().find(|c| c.field == field)
+}
+
+/// Finds a column by the header the wiki gives it, ignoring case
+pub fn by_wiki_header(header: &str) -> Option<&'static Column> {
+    COLUMNS.iter().find(|c| c.wiki_header.is_some_and(|h| h.eq_ignore_ascii_case(header.trim())))
+}
+
+/// The columns scraped from the wiki's frame data table, in table order
+pub fn wiki_columns() -> impl Iterator<Item = &'static Column> {
+    COLUMNS.iter().filter(|c| c.wiki_header.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framedata::Move;
+
+    #[test]
+    fn covers_every_field() {
+        assert!(COLUMNS.iter().map(|c| c.field).eq(Move::FIELDS));
+        assert_eq!(by_wiki_header("block adv").map(|c| c.label), Some("On Block"));
+        assert_eq!(by_field("identifier").map(|c| c.label), Some("Move"));
+        assert_eq!(wiki_columns().count(), Move::FIELDS.len() - 5);
+        assert!(by_field("startup").unwrap().numeric && !by_field("guard").unwrap().numeric);
+    }
+}
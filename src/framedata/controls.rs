@@ -0,0 +1,107 @@
+//! Classic and Modern control scheme inputs. The wiki lists every move by its Classic input and,
+//! where it has one, the Modern input next to it, which Modern players need to look moves up by
+//! what they actually press.
+
+use std::fmt::Display;
+
+use crate::framedata::{CharacterFrameData, FrameData, Move, SF6FrameDataError};
+use crate::text::normalize;
+
+/// The control scheme an input is written for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlScheme {
+    /// Six buttons and motion inputs, the notation of [`Move::input`]
+    #[default]
+    Classic,
+    /// Light, Medium, Heavy and Special buttons, the notation of [`Move::modern_input`]
+    Modern,
+}
+
+impl ControlScheme {
+    pub const ALL: [ControlScheme; 2] = [ControlScheme::Classic, ControlScheme::Modern];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlScheme::Classic => "classic",
+            ControlScheme::Modern => "modern",
+        }
+    }
+
+    /// Finds a scheme by [`ControlScheme::name`], ignoring case. `c` and `m` are accepted too.
+    pub fn from_name(name: &str) -> Option<ControlScheme> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "classic" | "c" => Some(ControlScheme::Classic),
+            "modern" | "m" => Some(ControlScheme::Modern),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ControlScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Move {
+    /// This move's input on `scheme`, `None` for Modern when the wiki lists no Modern input
+    pub fn input_for(&self, scheme: ControlScheme) -> Option<&str> {
+        match scheme {
+            ControlScheme::Classic => Some(&self.input),
+            ControlScheme::Modern => self.modern_input.as_deref(),
+        }
+    }
+}
+
+impl CharacterFrameData {
+    /// The moves performed with `input` on `scheme`, ignoring case and spaces, e.g. every move
+    /// Modern players perform with `SP`
+    pub fn moves_by_input(&self, scheme: ControlScheme, input: &str) -> Vec<&Move> {
+        let input = normalize(input);
+        self.moves.iter()
+            .filter(|m| m.input_for(scheme).is_some_and(|found| normalize(found) == input))
+            .collect()
+    }
+
+    /// The moves that have a Modern input, paired with it
+    pub fn modern_moves(&self) -> Vec<(&str, &Move)> {
+        self.moves.iter()
+            .filter_map(|m| Some((m.modern_input.as_deref()?, m)))
+            .collect()
+    }
+}
+
+impl FrameData {
+    /// Finds the first move of the character matching `character_query` performed with `input`
+    /// on `scheme`, see [`CharacterFrameData::moves_by_input`]
+    pub fn find_move_by_input(&self, character_query: &str, scheme: ControlScheme, input: &str) -> Result<&Move, SF6FrameDataError> {
+        self.find_character_frame_data_query(character_query)?
+            .moves_by_input(scheme, input)
+            .into_iter()
+            .next()
+            .ok_or(SF6FrameDataError::UnknownMove)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::parse_character_page;
+
+    #[test]
+    fn scrapes_modern_inputs() {
+        let section = |input: &str, modern: &str| format!("<section class=\"section-collapsible\"><h5><span>{0}</span></h5><table class=\"wikitable\">\
+            <tr><th><div><p><span class=\"input-badge\">{0}</span>{1}</p><div>{0}</div></div></th></tr></table></section>", input, modern);
+        let page = format!("<html><body><div><div>{}{}</div></div></body></html>",
+            section("236P", "<span class=\"input-badge modern-input\">SP</span>"), section("5LP", ""));
+        let ryu = parse_character_page(&page, &character::RYU);
+        assert_eq!(ryu.moves[0].input, "236P");
+        assert_eq!(ryu.moves[0].input_for(ControlScheme::Modern), Some("SP"));
+        assert_eq!(ryu.moves[1].modern_input, None);
+        assert_eq!(ryu.moves_by_input(ControlScheme::Modern, "sp")[0].identifier, "236P");
+        assert_eq!(ryu.moves_by_input(ControlScheme::Classic, "5lp")[0].identifier, "5LP");
+        assert_eq!(ControlScheme::from_name("Modern"), Some(ControlScheme::Modern));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::character::CharacterId;
+use crate::framedata::{FrameData, Move};
+use crate::parsed::CancelOptions;
+
+/// A curated value that replaces what the wiki has for one field of a move, e.g. a known wiki
+/// error while an upstream edit is pending
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Correction {
+    pub character_id: CharacterId,
+    pub identifier: String,
+    /// A [`Move`] field name, e.g. `startup`
+    pub field: String,
+    pub value: String,
+    /// Why the wiki is wrong, for whoever reviews the report
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reason: Option<String>,
+}
+
+impl Correction {
+    pub fn new(character_id: &CharacterId, identifier: &str, field: &str, value: &str) -> Self {
+        Correction {
+            character_id: character_id.clone(),
+            identifier: identifier.to_string(),
+            field: field.to_string(),
+            value: value.to_string(),
+            reason: None,
+        }
+    }
+
+    pub fn reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+}
+
+/// A correction that changed a value, with what the wiki had
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Divergence {
+    pub correction: Correction,
+    pub wiki: String,
+}
+
+/// What [`FrameData::apply_corrections`] did with each correction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrectionReport {
+    /// Corrections that replaced a different wiki value
+    pub diverged: Vec<Divergence>,
+    /// Corrections the wiki now agrees with, which can be retired
+    pub redundant: Vec<Correction>,
+    /// Corrections for a move or field that wasn't found, e.g. after a move was renamed
+    pub unmatched: Vec<Correction>,
+}
+
+impl FrameData {
+    /// Applies `corrections` on top of the scraped data, in order, and reports where they diverge
+    /// from the wiki. Characters that aren't loaded are skipped without being reported.
+    pub fn apply_corrections(&mut self, corrections: &[Correction]) -> CorrectionReport {
+        let mut report = CorrectionReport::default();
+        for correction in corrections {
+            let Some(character_frame_data) = self.character_frame_data.iter_mut().find(|c| c.character_id == correction.character_id) else {
+                continue;
+            };
+            let Some(move_data) = character_frame_data.moves.iter_mut().find(|m| m.identifier.eq_ignore_ascii_case(&correction.identifier)) else {
+                report.unmatched.push(correction.clone());
+                continue;
+            };
+            let field = move_data.get_mut(&correction.field)
+                .filter(|_| !Move::IDENTITY_FIELDS.contains(&correction.field.as_str()));
+            match field {
+                None => report.unmatched.push(correction.clone()),
+                Some(value) if value.trim() == correction.value.trim() => report.redundant.push(correction.clone()),
+                Some(value) => {
+                    let wiki = std::mem::replace(value, correction.value.clone());
+                    if correction.field == "cancel" {
+                        move_data.cancel_options = CancelOptions::parse(&move_data.cancel);
+                    }
+                    report.diverged.push(Divergence {
+                        correction: correction.clone(),
+                        wiki,
+                    });
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn applies_and_reports_corrections() {
+        let mut data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![Move { identifier: String::from("5MP"), startup: String::from("7"), guard: String::from("LH"), ..Move::default() }])],
+        };
+        let report = data.apply_corrections(&[
+            Correction::new(&character::RYU, "5mp", "startup", "6").reason("wiki typo"),
+            Correction::new(&character::RYU, "5MP", "guard", "LH"),
+            Correction::new(&character::RYU, "5MP", "identifier", "5HP"),
+            Correction::new(&character::KEN, "5MP", "startup", "6"),
+        ]);
+        assert_eq!(data.find_move_character(&character::RYU, "5MP").unwrap().startup, "6");
+        assert_eq!(report.diverged.len(), 1);
+        assert_eq!(report.diverged[0].wiki, "7");
+        assert_eq!(report.redundant.len(), 1);
+        assert_eq!(report.unmatched.len(), 1);
+    }
+}
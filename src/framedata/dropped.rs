@@ -0,0 +1,119 @@
+//! Move sections of a frame data page that the parser skipped, so a missing move can be traced to
+//! the scrape instead of being discovered by users. The parser pairs each move heading with the
+//! table right after it; a section without one or with an empty heading loses its move.
+
+use std::fmt::Display;
+
+use scraper::{Element, ElementRef, Html};
+
+use crate::framedata::{move_block, parse_move, select_move_blocks, select_move_identifiers};
+
+/// Why a section's move is missing
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DropReason {
+    /// No move table follows the heading
+    NoTable,
+    /// The table doesn't follow a move heading, e.g. because the heading is empty. `heading` is
+    /// the text of the element right before the table, if any.
+    NoIdentifier { heading: Option<String> },
+    /// The table has no input or name in its header row, so no move was parsed from it
+    MissingHeader,
+}
+
+/// A section skipped by the parser, see [`dropped_sections`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DroppedSection {
+    /// The move heading's text, `None` for tables without one
+    pub identifier: Option<String>,
+    pub reason: DropReason,
+}
+
+impl Display for DroppedSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let identifier = self.identifier.as_deref().unwrap_or("<no identifier>");
+        match &self.reason {
+            DropReason::NoTable => write!(f, "{}: no table", identifier),
+            DropReason::NoIdentifier { heading: Some(heading) } => write!(f, "table under `{}`: no identifier", heading),
+            DropReason::NoIdentifier { heading: None } => write!(f, "table: no identifier"),
+            DropReason::MissingHeader => write!(f, "{}: no input or name", identifier),
+        }
+    }
+}
+
+/// Every move section of a frame data page that the parser skipped: headings in page order, then
+/// tables without a heading. Empty for a page that parsed cleanly.
+pub fn dropped_sections(html: &str) -> Vec<DroppedSection> {
+    let html = Html::parse_document(html);
+    let identifiers = select_move_identifiers(&html);
+    let mut paired = Vec::new();
+    let mut dropped = Vec::new();
+    for identifier in &identifiers {
+        let reason = match move_block(*identifier) {
+            None => DropReason::NoTable,
+            Some(block) => {
+                paired.push(block.id());
+                if parse_move(*identifier, None, block).is_some() {
+                    continue;
+                }
+                DropReason::MissingHeader
+            }
+        };
+        dropped.push(DroppedSection { identifier: Some(text(*identifier)), reason });
+    }
+    for block in select_move_blocks(&html).into_iter().filter(|block| !paired.contains(&block.id())) {
+        let heading = block.prev_sibling_element().map(text).filter(|heading| !heading.is_empty());
+        dropped.push(DroppedSection { identifier: None, reason: DropReason::NoIdentifier { heading } });
+    }
+    dropped
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::parse_character_page;
+
+    #[test]
+    fn finds_dropped_sections() {
+        let section = |identifier: &str, table: bool| format!("<section class=\"section-collapsible\"><h5><span>{0}</span></h5>{1}</section>",
+            identifier,
+            if table { format!("<table class=\"wikitable\"><tr><th><div><p><span>{0}</span></p><div>{0}</div></div></th></tr></table>", identifier) } else { String::new() });
+        let clean = format!("<html><body><div><div>{}{}</div></div></body></html>", section("5LP", true), section("2MK", true));
+        assert!(dropped_sections(&clean).is_empty());
+
+        let page = format!("<html><body><div><div>{}{}{}</div></div></body></html>",
+            section("5LP", false), section("2MK", true), section("", true));
+        let dropped = dropped_sections(&page);
+        assert_eq!(dropped, vec![
+            DroppedSection { identifier: Some(String::from("5LP")), reason: DropReason::NoTable },
+            DroppedSection { identifier: None, reason: DropReason::NoIdentifier { heading: None } },
+        ]);
+        assert_eq!(parse_character_page(&page, &character::RYU).moves[0].identifier, "2MK");
+        assert_eq!(dropped[0].to_string(), "5LP: no table");
+    }
+
+    #[test]
+    fn pairs_headings_with_their_own_tables() {
+        let table = |name: &str| format!("<table class=\"wikitable\"><tr><th><div><p><span>{0}</span></p><div>{0} name</div></div></th></tr></table>", name);
+        let section = |heading: &str, body: &str| format!("<section class=\"section-collapsible\"><h5><span>{}</span></h5>{}</section>", heading, body);
+        let page = format!("<html><body><div><div>{}{}{}{}{}</div></div></body></html>",
+            section("5LP", &table("5LP")),
+            section("5MP", "<p>Coming soon</p>"),
+            section("5HP", &table("5HP")),
+            section("2LK", "<table class=\"wikitable\"><tr><th><div></div></th></tr></table>"),
+            section("", &table("6HK")).replace("<h5>", "<h5>Notes"));
+        let moves = parse_character_page(&page, &character::RYU).moves;
+        assert_eq!(moves.iter().map(|m| (m.identifier.as_str(), m.name.as_str())).collect::<Vec<_>>(), [("5LP", "5LP name"), ("5HP", "5HP name")]);
+        assert_eq!(dropped_sections(&page), vec![
+            DroppedSection { identifier: Some(String::from("5MP")), reason: DropReason::NoTable },
+            DroppedSection { identifier: Some(String::from("2LK")), reason: DropReason::MissingHeader },
+            DroppedSection { identifier: None, reason: DropReason::NoIdentifier { heading: Some(String::from("Notes")) } },
+        ]);
+    }
+}
@@ -0,0 +1,263 @@
+//! Gifs of moves scraped from [ultimateframedata.com](https://ultimateframedata.com/sf6), paired
+//! with the wiki's [`Move`]s by name.
+
+use scraper::Html;
+
+use crate::character::{self, CharacterId};
+#[cfg(feature = "async")]
+use crate::error::SF6Error;
+#[cfg(feature = "async")]
+use crate::framedata::Loader;
+use crate::framedata::{parse_gifs, CharacterFrameData, FrameData, Move, MoveGif, SF6FrameDataError};
+use crate::text::normalize;
+
+/// Matches scoring below this are too loose to pair a gif with a move
+const MIN_CONFIDENCE: f32 = 0.35;
+
+/// Loads a character's gifs without their frame data. The gifs aren't paired with any moves, so
+/// their `move_identifier` is `None`; [`load`](super::load) pairs them.
+#[cfg(feature = "async")]
+pub async fn load(character_id: &CharacterId) -> Result<Vec<MoveGif>, SF6Error> {
+    Loader::default().load_gifs(character_id).await
+}
+
+/// Parses a saved copy of a character's gif page (the html of [`CharacterId::gif_data_url`])
+/// without making any web-requests. Like [`load`], the gifs aren't paired with any moves.
+pub fn parse_gif_page(html: &str) -> Vec<MoveGif> {
+    parse_gifs(&Html::parse_document(html))
+}
+
+/// A gif paired with one of a character's moves, see [`GifReport`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GifMatch {
+    /// The move's `identifier`
+    pub identifier: String,
+    /// The gif's `name` on ultimateframedata.com
+    pub gif: String,
+    /// How sure the pairing is, from `1.0` for identical names down to loose word overlap
+    pub confidence: f32,
+}
+
+/// How a character's wiki moves line up with the gifs scraped from ultimateframedata.com. The two
+/// sites name moves independently, so bot maintainers can use this to audit which moves will be
+/// shown without media or with the wrong gif.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GifReport {
+    pub character_id: CharacterId,
+    pub matched: Vec<GifMatch>,
+    /// Identifiers of moves no gif could be paired with
+    pub unmatched_moves: Vec<String>,
+    /// Names of gifs no move could be paired with
+    pub unmatched_gifs: Vec<String>,
+}
+
+impl GifReport {
+    /// The fraction (`0.0..=1.0`) of moves that were paired with a gif
+    pub fn coverage(&self) -> f32 {
+        let total = self.matched.len() + self.unmatched_moves.len();
+        if total == 0 {
+            return 0.0;
+        }
+        self.matched.len() as f32 / total as f32
+    }
+
+    /// The mean confidence of all pairings, `0.0` if there are none
+    pub fn confidence(&self) -> f32 {
+        if self.matched.is_empty() {
+            return 0.0;
+        }
+        self.matched.iter().map(|m| m.confidence).sum::<f32>() / self.matched.len() as f32
+    }
+
+    /// Pairings below `confidence`, the ones most worth checking by hand
+    pub fn uncertain(&self, confidence: f32) -> impl Iterator<Item = &GifMatch> {
+        self.matched.iter().filter(move |m| m.confidence < confidence)
+    }
+}
+
+impl CharacterFrameData {
+    /// Pairs this character's moves with their gifs, see [`GifReport`]. Each move and gif is used
+    /// at most once, best scoring pairs first.
+    pub fn gif_report(&self) -> GifReport {
+        let mut candidates = Vec::new();
+        for (move_index, move_data) in self.moves.iter().enumerate() {
+            for (gif_index, gif) in self.gifs.iter().enumerate() {
+                let confidence = match_confidence(move_data, gif);
+                if confidence >= MIN_CONFIDENCE {
+                    candidates.push((confidence, move_index, gif_index));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+        let mut move_used = vec![false; self.moves.len()];
+        let mut gif_used = vec![false; self.gifs.len()];
+        let mut matched = Vec::new();
+        for (confidence, move_index, gif_index) in candidates {
+            if move_used[move_index] || gif_used[gif_index] {
+                continue;
+            }
+            move_used[move_index] = true;
+            gif_used[gif_index] = true;
+            matched.push((move_index, GifMatch {
+                identifier: self.moves[move_index].identifier.clone(),
+                gif: self.gifs[gif_index].name.clone(),
+                confidence,
+            }));
+        }
+        matched.sort_by_key(|(move_index, _)| *move_index);
+
+        GifReport {
+            character_id: self.character_id.clone(),
+            matched: matched.into_iter().map(|(_, m)| m).collect(),
+            unmatched_moves: zip_unused(&self.moves, &move_used).map(|m| m.identifier.clone()).collect(),
+            unmatched_gifs: zip_unused(&self.gifs, &gif_used).map(|g| g.name.clone()).collect(),
+        }
+    }
+
+    /// Sets every gif's `move_identifier` to the move [`CharacterFrameData::gif_report`] pairs it
+    /// with. Done when pages are parsed, so this only needs calling after editing `moves` or
+    /// `gifs` by hand.
+    pub fn link_gifs(&mut self) {
+        let report = self.gif_report();
+        for gif in &mut self.gifs {
+            gif.move_identifier = report.matched.iter()
+                .find(|m| m.gif == gif.name)
+                .map(|m| m.identifier.clone());
+        }
+    }
+
+    /// The gif of `move_data`, by the gifs' `move_identifier` or, if none are linked, by
+    /// [`CharacterFrameData::gif_report`]
+    pub fn gif_for(&self, move_data: &Move) -> Option<&MoveGif> {
+        if self.gifs.iter().any(|g| g.move_identifier.is_some()) {
+            return self.gifs.iter().find(|g| g.move_identifier.as_ref() == Some(&move_data.identifier));
+        }
+        let report = self.gif_report();
+        let gif_match = report.matched.iter().find(|m| m.identifier == move_data.identifier)?;
+        self.gifs.iter().find(|g| g.name == gif_match.gif)
+    }
+}
+
+impl FrameData {
+    /// Like [`FrameData::find_move`], along with the move's gif if it has one
+    pub fn find_move_with_gif(&self, character_query: &str, move_query: &str) -> Result<(&Move, Option<&MoveGif>), SF6FrameDataError> {
+        let character_id = character::get_character_by_regex(character_query).ok_or(SF6FrameDataError::UnknownCharacter)?;
+        self.find_move_with_gif_character(character_id, move_query)
+    }
+
+    /// Like [`FrameData::find_move_character`], along with the move's gif if it has one
+    pub fn find_move_with_gif_character(&self, character_id: &CharacterId, move_query: &str) -> Result<(&Move, Option<&MoveGif>), SF6FrameDataError> {
+        let move_found = self.find_move_character(character_id, move_query)?;
+        let gif = self.find_character_frame_data(character_id)?.gif_for(move_found);
+        Ok((move_found, gif))
+    }
+
+    /// A [`GifReport`] for every loaded character
+    pub fn gif_reports(&self) -> Vec<GifReport> {
+        self.character_frame_data.iter().map(CharacterFrameData::gif_report).collect()
+    }
+}
+
+fn zip_unused<'a, T>(items: &'a [T], used: &'a [bool]) -> impl Iterator<Item = &'a T> {
+    items.iter().zip(used).filter(|(_, used)| !**used).map(|(item, _)| item)
+}
+
+fn match_confidence(move_data: &Move, gif: &MoveGif) -> f32 {
+    let gif_name = normalize(&gif.name);
+    if gif_name.is_empty() {
+        return 0.0;
+    }
+    if gif_name == normalize(&move_data.name) {
+        return 1.0;
+    }
+    if gif_name == normalize(&move_data.identifier) || gif_name == normalize(&move_data.input) {
+        return 0.9;
+    }
+    let move_words = words(&move_data.name);
+    let gif_words = words(&gif.name);
+    let shared = move_words.iter().filter(|w| gif_words.contains(w)).count();
+    let union = move_words.len() + gif_words.len() - shared;
+    if union == 0 {
+        return 0.0;
+    }
+    0.8 * shared as f32 / union as f32
+}
+
+fn words(name: &str) -> Vec<String> {
+    let mut words = name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<String>>();
+    words.sort();
+    words.dedup();
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::media::MediaInfo;
+    use crate::testing;
+
+    fn gif(name: &str) -> MoveGif {
+        MoveGif {
+            name: name.to_string(),
+            move_identifier: None,
+            url: format!("https://ultimateframedata.com/sf6/{}.gif", name),
+            thumbnail_url: None,
+            info: MediaInfo::default(),
+        }
+    }
+
+    #[test]
+    fn reports_gif_coverage() {
+        let named = |identifier: &str, name: &str| Move {
+            identifier: identifier.to_string(),
+            input: identifier.to_string(),
+            name: name.to_string(),
+            ..Move::default()
+        };
+        let mut ryu = testing::character_frame_data(&character::RYU, vec![
+            named("5LP", "Standing Light Punch"),
+            named("236P", "Hadoken"),
+            named("214P(charged)", "Denjin Hashogeki"),
+            named("22P", "Denjin Renki"),
+        ]);
+        ryu.gifs = vec![gif("Hadoken (LP)"), gif("standing light punch"), gif("Taunt")];
+        let report = ryu.gif_report();
+        assert_eq!(report.matched, vec![
+            GifMatch { identifier: String::from("5LP"), gif: String::from("standing light punch"), confidence: 1.0 },
+            GifMatch { identifier: String::from("236P"), gif: String::from("Hadoken (LP)"), confidence: 0.4 },
+        ]);
+        assert_eq!(report.unmatched_moves, vec!["214P(charged)", "22P"]);
+        assert_eq!(report.unmatched_gifs, vec!["Taunt"]);
+        assert_eq!(report.coverage(), 0.5);
+        assert_eq!(report.uncertain(0.5).count(), 1);
+        assert_eq!(ryu.gif_for(&ryu.moves[1]).unwrap().name, "Hadoken (LP)");
+    }
+
+    #[test]
+    fn links_gifs_to_moves() {
+        let html = "<div class=\"movecontainer\"><div class=\"movename\">Standing Light Punch</div><div class=\"hitbox\">\
+            <a href=\"hitboxes/ryu/Ryu5LP.gif\"><img src=\"hitboxes/ryu/Ryu5LP.png\"></a></div></div>\
+            <div class=\"movecontainer\"><div class=\"movename\">Taunt</div><div class=\"hitbox\">\
+            <a href=\"hitboxes/ryu/RyuTaunt.gif\"><img src=\"hitboxes/ryu/RyuTaunt.gif\"></a></div></div>";
+        let mut ryu = testing::character_frame_data(&character::RYU, vec![Move { identifier: String::from("5LP"), name: String::from("Standing Light Punch"), ..Move::default() }]);
+        ryu.gifs = parse_gif_page(html);
+        assert_eq!(ryu.gifs[0].url, "https://ultimateframedata.com/sf6/hitboxes/ryu/Ryu5LP.gif");
+        assert_eq!(ryu.gifs[0].thumbnail_url.as_deref(), Some("https://ultimateframedata.com/sf6/hitboxes/ryu/Ryu5LP.png"));
+        assert_eq!(ryu.gifs[1].thumbnail_url, None);
+        ryu.link_gifs();
+        assert_eq!(ryu.gifs[0].move_identifier.as_deref(), Some("5LP"));
+        assert_eq!(ryu.gifs[1].move_identifier, None);
+
+        let data = FrameData { character_frame_data: vec![ryu] };
+        let (move_found, gif) = data.find_move_with_gif("ryu", "5lp").unwrap();
+        assert_eq!(move_found.identifier, "5LP");
+        assert_eq!(gif.unwrap().name, "Standing Light Punch");
+    }
+}
@@ -0,0 +1,160 @@
+use std::time::{Duration, SystemTime};
+
+use crate::framedata::LoadReport;
+
+/// How a single refresh went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefreshStatus {
+    /// Every character loaded
+    Complete,
+    /// Some characters loaded, see [`HealthReport::failures`]
+    Partial,
+    /// No character loaded at all
+    Failed,
+}
+
+/// A character that failed to load in the last refresh
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterFailure {
+    pub character: String,
+    pub error: String,
+}
+
+/// A snapshot of [`HealthMonitor`] for a health or readiness endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthReport {
+    /// Whether the data being served was loaded recently enough to take traffic
+    pub ready: bool,
+    /// How long ago the data being served was loaded, `None` if nothing has loaded yet
+    pub data_age: Option<Duration>,
+    /// How the most recent refresh went, `None` if none has finished yet
+    pub last_refresh: Option<RefreshStatus>,
+    /// Characters that failed to load in the most recent refresh
+    pub failures: Vec<CharacterFailure>,
+}
+
+impl HealthReport {
+    /// The HTTP status for a liveness endpoint like `/healthz`. The process is alive whenever it
+    /// can answer, so this is always `200`; failures are in the body for monitoring to read.
+    pub fn liveness_status(&self) -> u16 {
+        200
+    }
+
+    /// The HTTP status for a readiness endpoint like `/readyz`: `503` until data has loaded and
+    /// again once it is older than [`HealthMonitor::max_age`], so an orchestrator like
+    /// Kubernetes only routes traffic to instances serving a loaded dataset
+    pub fn readiness_status(&self) -> u16 {
+        if self.ready { 200 } else { 503 }
+    }
+}
+
+/// Tracks the outcome of each refresh for health and readiness endpoints. Record every
+/// [`LoadReport`], e.g. from the callback of [`refresh::watch`](super::refresh::watch), and
+/// build a [`HealthReport`] for each request.
+#[derive(Debug, Clone)]
+pub struct HealthMonitor {
+    max_age: Duration,
+    loaded_at: Option<SystemTime>,
+    last_refresh: Option<RefreshStatus>,
+    failures: Vec<CharacterFailure>,
+}
+
+impl HealthMonitor {
+    /// A monitor that reports ready while the served data is at most `max_age` old
+    pub fn new(max_age: Duration) -> Self {
+        HealthMonitor {
+            max_age,
+            loaded_at: None,
+            last_refresh: None,
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Records a refresh that finished at `at`. A refresh that loaded no characters leaves the
+    /// previously served data, and so its age, in place.
+    pub fn record(&mut self, report: &LoadReport, at: SystemTime) {
+        let status = if report.data.character_frame_data.is_empty() {
+            RefreshStatus::Failed
+        } else if report.is_complete() {
+            RefreshStatus::Complete
+        } else {
+            RefreshStatus::Partial
+        };
+        if status != RefreshStatus::Failed {
+            self.loaded_at = Some(at);
+        }
+        self.last_refresh = Some(status);
+        self.failures = report.failures.iter()
+            .map(|(character_id, error)| CharacterFailure {
+                character: character_id.id.to_string(),
+                error: error.to_string(),
+            })
+            .collect();
+    }
+
+    /// The health of the served data as of `now`
+    pub fn report(&self, now: SystemTime) -> HealthReport {
+        let data_age = self.loaded_at.map(|loaded_at| now.duration_since(loaded_at).unwrap_or_default());
+        HealthReport {
+            ready: data_age.is_some_and(|age| age <= self.max_age),
+            data_age,
+            last_refresh: self.last_refresh,
+            failures: self.failures.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::error::SF6Error;
+    use crate::framedata::FrameData;
+    use crate::testing;
+
+    fn report(loaded: bool, failed: bool) -> LoadReport {
+        let character_frame_data = if loaded {
+            vec![testing::character_frame_data(&character::RYU, Vec::new())]
+        } else {
+            Vec::new()
+        };
+        let failures = if failed {
+            vec![(character::KEN.clone(), SF6Error::Http { url: character::KEN.frame_data_url(), status: 503 })]
+        } else {
+            Vec::new()
+        };
+        LoadReport { data: FrameData { character_frame_data }, failures }
+    }
+
+    #[test]
+    fn reports_readiness() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut monitor = HealthMonitor::new(Duration::from_secs(60));
+        assert_eq!(monitor.report(start).readiness_status(), 503);
+
+        monitor.record(&report(true, true), start);
+        let health = monitor.report(start + Duration::from_secs(30));
+        assert!(health.ready);
+        assert_eq!(health.last_refresh, Some(RefreshStatus::Partial));
+        assert_eq!(health.failures[0].character, "ken");
+
+        // a failed refresh keeps serving the old data until it goes stale
+        monitor.record(&report(false, true), start + Duration::from_secs(40));
+        let health = monitor.report(start + Duration::from_secs(50));
+        assert_eq!((health.ready, health.last_refresh), (true, Some(RefreshStatus::Failed)));
+        assert_eq!(health.data_age, Some(Duration::from_secs(50)));
+        assert_eq!(monitor.report(start + Duration::from_secs(61)).readiness_status(), 503);
+
+        monitor.record(&report(true, false), start + Duration::from_secs(70));
+        let health = monitor.report(start + Duration::from_secs(70));
+        assert_eq!((health.readiness_status(), health.last_refresh), (200, Some(RefreshStatus::Complete)));
+        assert!(health.failures.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Downloading the wiki's hitbox images of a character's moves into a local directory, e.g. for a
+//! bot that serves them itself instead of hotlinking the wiki.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::task::JoinSet;
+
+use crate::character::CharacterId;
+use crate::framedata::{is_placeholder, CharacterFrameData, Loader};
+use crate::text::wiki_file_name;
+
+/// Downloads the hitbox images of `character`'s moves into `dir` with the default [`Loader`], see
+/// [`download_images_with`]
+pub async fn download_images(character: &CharacterFrameData, dir: &Path) -> io::Result<HashMap<String, PathBuf>> {
+    download_images_with(&Loader::default(), character, dir).await
+}
+
+/// Downloads the hitbox images of `character`'s moves into `dir` concurrently, within `loader`'s
+/// [request limits](Loader::max_concurrent_requests), and returns where each move's image is by
+/// identifier. Files are named `<character id>_<identifier>` with the image's extension. Moves
+/// sharing an image share the file named after the first of them, and files already in `dir`
+/// aren't downloaded again. Images that fail to download are left out of the map.
+pub async fn download_images_with(loader: &Loader, character: &CharacterFrameData, dir: &Path) -> io::Result<HashMap<String, PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut files: HashMap<&str, PathBuf> = HashMap::new();
+    let mut paths = HashMap::new();
+    for move_data in character.moves.iter().filter(|m| !is_placeholder(&m.image_link)) {
+        let path = files.entry(move_data.image_link.trim())
+            .or_insert_with(|| dir.join(local_file_name(&character.character_id, &move_data.identifier, &move_data.image_link)));
+        paths.insert(move_data.identifier.clone(), path.clone());
+    }
+
+    let mut set = JoinSet::new();
+    for (url, path) in files {
+        if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            continue;
+        }
+        let (loader, url) = (loader.clone(), url.to_string());
+        set.spawn(async move { (path, loader.fetch_bytes(&url).await) });
+    }
+    while let Some(res) = set.join_next().await {
+        let Ok((path, bytes)) = res else {
+            continue;
+        };
+        match bytes {
+            Ok(bytes) => fs::write(&path, bytes)?,
+            Err(_) => paths.retain(|_, p| *p != path),
+        }
+    }
+    Ok(paths)
+}
+
+/// `<character id>_<identifier>.<extension>`, with anything but letters, digits, `-`, `(` and `)`
+/// in the identifier replaced by `_`, and `png` for urls without an extension
+fn local_file_name(character_id: &CharacterId, identifier: &str, url: &str) -> String {
+    let identifier = identifier.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '(' | ')') { c } else { '_' })
+        .collect::<String>();
+    let extension = wiki_file_name(url).rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .filter(|extension| !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| String::from("png"));
+    format!("{}_{}.{}", character_id.id, identifier, extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::Move;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    #[tokio::test]
+    async fn skips_downloaded_images() {
+        let dir = TempDir::new("sf6rs-download-images-test");
+        let with = |identifier: &str, image_link: &str| Move {
+            identifier: identifier.to_string(),
+            image_link: image_link.to_string(),
+            ..Move::default()
+        };
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            with("5LP", "https://wiki.supercombo.gg/images/a/ab/Ryu_5LP.png"),
+            with("2LP", "https://wiki.supercombo.gg/images/a/ab/Ryu_5LP.png"),
+            with("236P(charged)", "https://wiki.supercombo.gg/images/c/cd/Ryu_236P.JPG?x=1"),
+            with("5MP", "-"),
+        ]);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ryu_5LP.png"), b"png").unwrap();
+        fs::write(dir.join("ryu_236P(charged).jpg"), b"jpg").unwrap();
+
+        let paths = download_images(&ryu, &dir).await.unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths["2LP"], dir.join("ryu_5LP.png"));
+        assert_eq!(fs::read(&paths["236P(charged)"]).unwrap(), b"jpg");
+        assert_eq!(local_file_name(&character::RYU, "5MP~HP", ""), "ryu_5MP_HP.png");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::framedata::{CharacterFrameData, FrameData};
+use crate::parsed::CancelOptions;
+
+/// The version of the JSON document [`FrameData::to_json`] writes. It goes up whenever a change
+/// would stop an older version of the crate from reading documents correctly.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Why a JSON document couldn't be read or written
+#[derive(Debug)]
+pub enum JsonError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    /// The document was written by a newer version of the crate, with a schema this one doesn't know
+    UnsupportedVersion(u32),
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::Io(e) => write!(f, "Could not access frame data file: {}", e),
+            JsonError::Parse(e) => write!(f, "Invalid frame data JSON: {}", e),
+            JsonError::UnsupportedVersion(v) => write!(f, "Unsupported frame data schema version {}, expected at most {}", v, SCHEMA_VERSION),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+impl From<io::Error> for JsonError {
+    fn from(e: io::Error) -> Self {
+        JsonError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(e: serde_json::Error) -> Self {
+        JsonError::Parse(e)
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Document<'a> {
+    schema_version: u32,
+    /// The version of the crate that wrote the document, for debugging only
+    generator: &'a str,
+    character_frame_data: &'a [CharacterFrameData],
+}
+
+#[derive(serde::Deserialize)]
+struct OwnedDocument {
+    /// Missing from plain [`FrameData`] JSON, which reads the same as version 1
+    #[serde(default)]
+    schema_version: Option<u32>,
+    character_frame_data: Vec<CharacterFrameData>,
+}
+
+impl FrameData {
+    /// Exports the whole dataset as a versioned JSON document, see [`SCHEMA_VERSION`].
+    /// Characters are written sorted by id, so scraping the same data twice gives the same file
+    /// and committed snapshots only change where the data did.
+    pub fn to_json(&self) -> String {
+        let mut character_frame_data = self.character_frame_data.clone();
+        character_frame_data.sort_by(|a, b| a.character_id.id.cmp(&b.character_id.id));
+        let document = Document {
+            schema_version: SCHEMA_VERSION,
+            generator: concat!("sf6rs ", env!("CARGO_PKG_VERSION")),
+            character_frame_data: &character_frame_data,
+        };
+        serde_json::to_string_pretty(&document).expect("frame data serializes to JSON")
+    }
+
+    /// Reads a document written by [`FrameData::to_json`], or plain [`FrameData`] JSON, without
+    /// making any web-requests
+    pub fn from_json(json: &str) -> Result<FrameData, JsonError> {
+        let document: OwnedDocument = serde_json::from_str(json)?;
+        match document.schema_version {
+            Some(version) if version > SCHEMA_VERSION => Err(JsonError::UnsupportedVersion(version)),
+            _ => {
+                let mut character_frame_data = document.character_frame_data;
+                // documents written before cancels were parsed while scraping have none
+                for move_data in character_frame_data.iter_mut().flat_map(|c| &mut c.moves).filter(|m| m.cancel_options.is_empty()) {
+                    move_data.cancel_options = CancelOptions::parse(&move_data.cancel);
+                }
+                Ok(FrameData { character_frame_data })
+            }
+        }
+    }
+
+    /// Writes [`FrameData::to_json`] to a file. The file is written to a temporary path and
+    /// renamed into place, so a process reading it never sees half a document.
+    pub fn save_json(&self, path: impl AsRef<Path>) -> Result<(), JsonError> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, self.to_json() + "\n")?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Reads a file written by [`FrameData::save_json`], see [`FrameData::from_json`]
+    pub fn load_json(path: impl AsRef<Path>) -> Result<FrameData, JsonError> {
+        FrameData::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::Move;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    #[test]
+    fn round_trips_versioned_documents() {
+        let character = |character_id: &character::CharacterId| testing::character_frame_data(character_id, vec![Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() }]);
+        let data = FrameData { character_frame_data: vec![character(&character::RYU), character(&character::KEN)] };
+        let json = data.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["character_frame_data"][0]["character_id"], "ken");
+
+        let path = TempDir::new("sf6rs-json-test.json");
+        data.save_json(&path).unwrap();
+        let loaded = FrameData::load_json(&path).unwrap();
+        assert_eq!(loaded.find_move("ryu", "5lp").unwrap().startup, "4");
+        assert_eq!(fs::read_to_string(&path).unwrap(), json + "\n");
+
+        let plain = serde_json::to_string(&data).unwrap();
+        assert_eq!(FrameData::from_json(&plain).unwrap().character_frame_data.len(), 2);
+        let newer = r#"{"schema_version": 99, "character_frame_data": []}"#;
+        assert!(matches!(FrameData::from_json(newer), Err(JsonError::UnsupportedVersion(99))));
+        assert!(matches!(FrameData::load_json("/nonexistent/sf6rs.json"), Err(JsonError::Io(_))));
+    }
+}
@@ -0,0 +1,708 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::task::JoinSet;
+
+use crate::character::{CharacterId, CharacterRegistry, CHARACTERS};
+use crate::error::SF6Error;
+use crate::framedata::archive::{HtmlArchive, PageKind};
+use crate::framedata::art::{parse_character_art, ArtImage};
+use crate::framedata::canary::{self, CanaryStatus, CANARY_CHARACTER};
+use crate::framedata::dropped::dropped_sections;
+use crate::framedata::media::store::MediaStore;
+use crate::framedata::media::{self, MediaInfo};
+use crate::framedata::report::LoadReport;
+use crate::framedata::retry::RetryPolicy;
+use crate::framedata::version::{detect_game_version, GameVersion};
+use crate::framedata::mechanics::{parse_unique_mechanics, UniqueMechanic};
+use crate::framedata::telemetry::{CharacterTelemetry, LoadTelemetry};
+use crate::framedata::gifs::parse_gif_page;
+use crate::framedata::{parse_character_pages, require_moves, CharacterFrameData, FrameData, Move, MoveGif};
+
+/// How many requests a [`Loader`] and its clones make at once unless configured otherwise, see
+/// [`Loader::max_concurrent_requests`]
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Configures how frame data is requested and parsed. The free functions [`load`](super::load)
+/// and [`load_all`](super::load_all) use [`Loader::default`].
+/// ```no_run
+/// # async fn run() {
+/// use sf6rs::framedata::{Loader, UrlRewrite};
+///
+/// let data = Loader::new()
+///     .url_rewrite(UrlRewrite::Template(String::from("https://imgproxy.example/rs:fit:300/plain/{url_encoded}")))
+///     .load_all()
+///     .await
+///     .data;
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Loader {
+    url_rewrite: Option<UrlRewrite>,
+    conditional: Option<Arc<ConditionalCache>>,
+    probe_media: bool,
+    media_store: Option<Arc<MediaStore>>,
+    retry: RetryPolicy,
+    limit: Arc<RequestLimit>,
+    client: reqwest::Client,
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewrites every hitbox image and gif url as it is parsed, e.g. to route media through a
+    /// caching proxy instead of hotlinking the source sites
+    pub fn url_rewrite(mut self, url_rewrite: UrlRewrite) -> Self {
+        self.url_rewrite = Some(url_rewrite);
+        self
+    }
+
+    /// Remembers the `ETag`/`Last-Modified` validators and body of every page this loader (and its
+    /// clones) fetches, and sends conditional requests on later loads. Pages the server reports as
+    /// unchanged are re-parsed from the remembered body instead of being downloaded again, which
+    /// is what makes repeated refreshes cheap for the wiki.
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.conditional = enabled.then(Arc::default);
+        self
+    }
+
+    /// Requests the first bytes of every hitbox image and gif after parsing to fill in their
+    /// [`MediaInfo`] with the file size, content type and dimensions. This makes one extra request
+    /// per file, so it is off by default.
+    pub fn probe_media(mut self, enabled: bool) -> Self {
+        self.probe_media = enabled;
+        self
+    }
+
+    /// Downloads media through `store`, so files already in it are read from disk instead of
+    /// being requested again
+    pub fn media_store(mut self, store: Arc<MediaStore>) -> Self {
+        self.media_store = Some(store);
+        self
+    }
+
+    /// Sends every request through `client`, e.g. one configured with a proxy, custom headers or a
+    /// user agent. Clones of the loader share the client's connection pool.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// How page requests that fail transiently are retried, [`RetryPolicy::default`] unless set
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The most requests this loader and its clones have in flight at once,
+    /// [`DEFAULT_MAX_CONCURRENT_REQUESTS`] unless set. Characters are still loaded concurrently,
+    /// but wait their turn for each request, so a full load doesn't hit the wiki with every page
+    /// at the same time. `0` is treated as `1`.
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        self.limit = Arc::new(RequestLimit::new(max, self.limit.delay));
+        self
+    }
+
+    /// Waits this long after each request before its slot is given to the next one, none unless
+    /// set. With [`Loader::max_concurrent_requests`] at `1` this spaces every request out by
+    /// `delay`.
+    pub fn request_delay(mut self, delay: Duration) -> Self {
+        self.limit = Arc::new(RequestLimit::new(self.limit.max_concurrent, delay));
+        self
+    }
+
+    /// Loads all frame data, see [`load_all`](super::load_all)
+    pub async fn load_all(&self) -> LoadReport {
+        self.load_all_timed().await.0
+    }
+
+    /// Loads all frame data like [`Loader::load_all`], additionally returning how long each
+    /// character took to fetch and parse, how many bytes it downloaded, and whether it failed.
+    pub async fn load_all_timed(&self) -> (LoadReport, LoadTelemetry) {
+        self.load_roster_timed(&CHARACTERS).await
+    }
+
+    /// Loads the frame data of every character in `roster` like [`Loader::load_all`], e.g. a
+    /// roster from [`discover_roster`](crate::character::discover_roster) that includes
+    /// characters released after this version of the crate
+    pub async fn load_roster(&self, roster: &[&'static CharacterId]) -> LoadReport {
+        self.load_roster_timed(roster).await.0
+    }
+
+    /// Loads `roster` like [`Loader::load_roster`], with telemetry like [`Loader::load_all_timed`]
+    pub async fn load_roster_timed(&self, roster: &[&'static CharacterId]) -> (LoadReport, LoadTelemetry) {
+        self.load_characters_timed(roster.iter().map(|character_id| (*character_id).clone())).await
+    }
+
+    /// Loads the frame data of every character in `registry` like [`Loader::load_all`], including
+    /// registered characters and overridden urls, see [`CharacterRegistry`]
+    pub async fn load_registry(&self, registry: &CharacterRegistry) -> LoadReport {
+        self.load_registry_timed(registry).await.0
+    }
+
+    /// Loads `registry` like [`Loader::load_registry`], with telemetry like
+    /// [`Loader::load_all_timed`]
+    pub async fn load_registry_timed(&self, registry: &CharacterRegistry) -> (LoadReport, LoadTelemetry) {
+        self.load_characters_timed(registry.iter().cloned()).await
+    }
+
+    async fn load_characters_timed(&self, characters: impl IntoIterator<Item = CharacterId>) -> (LoadReport, LoadTelemetry) {
+        let started = Instant::now();
+        let mut report = LoadReport {
+            data: FrameData {
+                character_frame_data: Vec::new()
+            },
+            failures: Vec::new(),
+        };
+        let mut telemetry = LoadTelemetry::default();
+        let mut set = JoinSet::new();
+        let mut tasks = HashMap::new();
+        for character_id in characters {
+            let loader = self.clone();
+            let task_character_id = character_id.clone();
+            let handle = set.spawn(async move { loader.load_timed(&task_character_id).await });
+            tasks.insert(handle.id(), character_id);
+        }
+        while let Some(res) = set.join_next().await {
+            let (character_frame_data, character_telemetry) = match res {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    if let Some(character_id) = tasks.get(&e.id()) {
+                        let mut character_telemetry = CharacterTelemetry::new(character_id);
+                        character_telemetry.error = Some(e.to_string());
+                        telemetry.characters.push(character_telemetry);
+                        report.failures.push((character_id.clone(), SF6Error::Task { character: character_id.id.to_string(), message: e.to_string() }));
+                    }
+                    continue;
+                }
+            };
+            match character_frame_data {
+                Ok(character_frame_data) => report.data.character_frame_data.push(character_frame_data),
+                Err(e) => report.failures.push((character_telemetry.character_id.clone(), e)),
+            }
+            telemetry.characters.push(character_telemetry);
+        }
+        telemetry.total = started.elapsed();
+        (report, telemetry)
+    }
+
+    /// Loads all frame data like [`Loader::load_all`], but only if every character's page states
+    /// it is for `version`. Any page that fails to load, doesn't state a version or states another
+    /// one ([`SF6Error::VersionMismatch`]) fails the whole load, so data from before and after a
+    /// patch is never mixed. Pages are stored in `archive`'s
+    /// [`for_version`](HtmlArchive::for_version) subdirectory if given.
+    pub async fn load_for_version(&self, version: &GameVersion, archive: Option<&HtmlArchive>) -> Result<FrameData, SF6Error> {
+        let mut set = JoinSet::new();
+        let mut tasks = HashMap::new();
+        for character_id in (*CHARACTERS).iter().copied() {
+            let loader = self.clone();
+            let handle = set.spawn(async move {
+                let pages = match loader.request_character_page(character_id, character_id.frame_data_url()).await {
+                    Ok(html) => loader.request_character_page(character_id, character_id.gif_data_url()).await.map(|gif_html| (html, gif_html)),
+                    Err(e) => Err(e),
+                };
+                (character_id, pages)
+            });
+            tasks.insert(handle.id(), character_id);
+        }
+        let mut pages = Vec::new();
+        while let Some(res) = set.join_next().await {
+            let (character_id, result) = res.map_err(|e| SF6Error::Task {
+                character: tasks.get(&e.id()).map_or_else(String::new, |character_id| character_id.id.to_string()),
+                message: e.to_string(),
+            })?;
+            let (html, gif_html) = result?;
+            match detect_game_version(&html) {
+                Some(found) if &found == version => pages.push((character_id, html, gif_html)),
+                found => return Err(SF6Error::VersionMismatch { character: character_id.id.to_string(), expected: version.clone(), found }),
+            }
+        }
+
+        let archive = archive.map(|archive| archive.for_version(version));
+        let mut frame_data = FrameData {
+            character_frame_data: Vec::new()
+        };
+        for (character_id, html, gif_html) in pages {
+            if let Some(archive) = &archive {
+                archive.store(character_id, PageKind::FrameData, &html)?;
+                archive.store(character_id, PageKind::Gifs, &gif_html)?;
+            }
+            let mut character_frame_data = require_moves(self.parse(character_id, &html, Some(&gif_html)))?;
+            self.probe(&mut character_frame_data).await;
+            frame_data.character_frame_data.push(character_frame_data);
+        }
+        Ok(frame_data)
+    }
+
+    /// Loads one character's frame data, see [`load`](super::load)
+    pub async fn load(&self, character_id: &CharacterId) -> Result<CharacterFrameData, SF6Error> {
+        let html = self.request_character_page(character_id, character_id.frame_data_url()).await?;
+        let gif_html = self.request_character_page(character_id, character_id.gif_data_url()).await?;
+        let mut character_frame_data = require_moves(self.parse(character_id, &html, Some(&gif_html)))?;
+        self.probe(&mut character_frame_data).await;
+        Ok(character_frame_data)
+    }
+
+    /// Loads one character's gifs without their frame data, see [`gifs::load`](super::gifs::load)
+    pub async fn load_gifs(&self, character_id: &CharacterId) -> Result<Vec<MoveGif>, SF6Error> {
+        let gif_html = self.request_character_page(character_id, character_id.gif_data_url()).await?;
+        let mut gifs = parse_gif_page(&gif_html);
+        if let Some(url_rewrite) = &self.url_rewrite {
+            for gif in &mut gifs {
+                url_rewrite.apply_gif(gif);
+            }
+        }
+        Ok(gifs)
+    }
+
+    pub(crate) async fn load_timed(&self, character_id: &CharacterId) -> (Result<CharacterFrameData, SF6Error>, CharacterTelemetry) {
+        let mut telemetry = CharacterTelemetry::new(character_id);
+        let result = match self.fetch_timed(character_id, &mut telemetry).await {
+            Ok((html, gif_html)) => self.parse_timed(character_id, &html, &gif_html, &mut telemetry).await,
+            Err(e) => Err(e),
+        };
+        (result, telemetry)
+    }
+
+    /// Fetches a character's frame data and gif pages, recording the time, size and retries taken
+    /// in `telemetry`
+    pub(crate) async fn fetch_timed(&self, character_id: &CharacterId, telemetry: &mut CharacterTelemetry) -> Result<(String, String), SF6Error> {
+        let fetch_started = Instant::now();
+        let pages = match self.request_page_counted(character_id.frame_data_url(), &mut telemetry.retries).await {
+            Ok(html) => self.request_page_counted(character_id.gif_data_url(), &mut telemetry.retries).await.map(|gif_html| (html, gif_html)),
+            Err(e) => Err(e),
+        };
+        telemetry.fetch = fetch_started.elapsed();
+        match pages {
+            Ok((html, gif_html)) => {
+                telemetry.bytes = html.len() + gif_html.len();
+                Ok((html, gif_html))
+            }
+            Err(e) => {
+                let e = e.for_character(character_id);
+                telemetry.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Parses pages fetched with [`Loader::fetch_timed`] and probes their media, recording the
+    /// time taken and what was skipped in `telemetry`
+    pub(crate) async fn parse_timed(&self, character_id: &CharacterId, html: &str, gif_html: &str, telemetry: &mut CharacterTelemetry) -> Result<CharacterFrameData, SF6Error> {
+        let parse_started = Instant::now();
+        let parsed = require_moves(self.parse(character_id, html, Some(gif_html)));
+        telemetry.parse = parse_started.elapsed();
+        telemetry.dropped_sections = dropped_sections(html);
+        telemetry.game_version = detect_game_version(html);
+        let mut character_frame_data = parsed.inspect_err(|e| telemetry.error = Some(e.to_string()))?;
+        self.probe(&mut character_frame_data).await;
+        Ok(character_frame_data)
+    }
+
+    /// Loads one character's frame data and stores the raw pages, see
+    /// [`load_archived`](super::load_archived)
+    pub async fn load_archived(&self, character_id: &CharacterId, archive: &HtmlArchive) -> Result<CharacterFrameData, SF6Error> {
+        let html = self.request_character_page(character_id, character_id.frame_data_url()).await?;
+        let gif_html = self.request_character_page(character_id, character_id.gif_data_url()).await?;
+        archive.store(character_id, PageKind::FrameData, &html)?;
+        archive.store(character_id, PageKind::Gifs, &gif_html)?;
+        let mut character_frame_data = require_moves(self.parse(character_id, &html, Some(&gif_html)))?;
+        self.probe(&mut character_frame_data).await;
+        Ok(character_frame_data)
+    }
+
+    /// Loads the unique mechanics described on a character's wiki overview page, relating them to
+    /// the character's `moves`, see [`parse_unique_mechanics`](super::mechanics::parse_unique_mechanics)
+    pub async fn load_unique_mechanics(&self, character_id: &CharacterId, moves: &[Move]) -> Result<Vec<UniqueMechanic>, SF6Error> {
+        let html = self.request_character_page(character_id, character_id.overview_url()).await?;
+        Ok(parse_unique_mechanics(&html, moves))
+    }
+
+    /// Loads the portrait and costume images linked from a character's wiki overview page, see
+    /// [`parse_character_art`](super::art::parse_character_art)
+    pub async fn load_character_art(&self, character_id: &CharacterId) -> Result<Vec<ArtImage>, SF6Error> {
+        let html = self.request_character_page(character_id, character_id.overview_url()).await?;
+        Ok(parse_character_art(&html))
+    }
+
+    /// Downloads a file, e.g. an image found by this loader, going through the
+    /// [`media_store`](Loader::media_store) if there is one
+    pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>, SF6Error> {
+        if let Some(bytes) = self.media_store.as_ref().map(|store| store.get(url)).transpose()?.flatten() {
+            return Ok(bytes);
+        }
+        let bytes = self.limited(async { self.client.get(url).send().await?.error_for_status()?.bytes().await }).await
+            .map_err(|e| SF6Error::from_reqwest(url, e))?
+            .to_vec();
+        if let Some(store) = &self.media_store {
+            store.put(url, &bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Fetches the canary page and validates its structure, see [`canary_check`](super::canary_check)
+    pub async fn canary_check(&self) -> CanaryStatus {
+        match self.request_page(CANARY_CHARACTER.frame_data_url()).await {
+            Ok(html) => canary::check_page(&html),
+            Err(e) => CanaryStatus::Unreachable(e.to_string()),
+        }
+    }
+
+    /// Requests the first bytes of the file at `url` and reads its size, content type and
+    /// dimensions from the response. Only GIF and PNG dimensions are recognized.
+    pub async fn fetch_media_info(&self, url: &str) -> Result<MediaInfo, SF6Error> {
+        self.limited(self.request_media_info(url)).await.map_err(|e| SF6Error::from_reqwest(url, e))
+    }
+
+    async fn request_media_info(&self, url: &str) -> Result<MediaInfo, reqwest::Error> {
+        let response = self.client.get(url)
+            .header(RANGE, "bytes=0-63")
+            .send().await?
+            .error_for_status()?;
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let content_type = header(CONTENT_TYPE);
+        let bytes = match response.status() {
+            StatusCode::PARTIAL_CONTENT => header(CONTENT_RANGE).as_deref().and_then(media::content_range_total),
+            _ => header(CONTENT_LENGTH).and_then(|v| v.parse().ok()),
+        };
+        let dimensions = media::sniff_dimensions(&response.bytes().await?);
+        Ok(MediaInfo {
+            content_type,
+            width: dimensions.map(|(width, _)| width),
+            height: dimensions.map(|(_, height)| height),
+            bytes,
+        })
+    }
+
+    /// Downloads a gif and extracts its first frame as a PNG, see
+    /// [`first_frame_png`](super::media::frames::first_frame_png)
+    #[cfg(feature = "media-processing")]
+    pub async fn fetch_first_frame_png(&self, gif: &MoveGif) -> Result<Vec<u8>, SF6Error> {
+        let bytes = self.fetch_bytes(&gif.url).await?;
+        media::frames::first_frame_png(&bytes).map_err(|e| SF6Error::Media(e.to_string()))
+    }
+
+    async fn probe(&self, character_frame_data: &mut CharacterFrameData) {
+        if !self.probe_media {
+            return;
+        }
+        let mut set = JoinSet::new();
+        let image_urls = character_frame_data.moves.iter().enumerate()
+            .filter(|(_, m)| m.image_info.is_some())
+            .map(|(i, m)| (true, i, m.image_link.clone()));
+        let gif_urls = character_frame_data.gifs.iter().enumerate()
+            .map(|(i, g)| (false, i, g.url.clone()));
+        for (is_image, index, url) in image_urls.chain(gif_urls).collect::<Vec<_>>() {
+            let loader = self.clone();
+            set.spawn(async move { (is_image, index, loader.fetch_media_info(&url).await) });
+        }
+        while let Some(res) = set.join_next().await {
+            let Ok((is_image, index, Ok(info))) = res else {
+                continue;
+            };
+            if is_image {
+                if let Some(image_info) = &mut character_frame_data.moves[index].image_info {
+                    image_info.merge(info);
+                }
+            } else {
+                character_frame_data.gifs[index].info.merge(info);
+            }
+        }
+    }
+
+    pub(crate) async fn request_page(&self, url: String) -> Result<String, SF6Error> {
+        self.request_page_counted(url, &mut 0).await
+    }
+
+    /// Requests one of `character_id`'s pages, see [`SF6Error::for_character`]
+    async fn request_character_page(&self, character_id: &CharacterId, url: String) -> Result<String, SF6Error> {
+        self.request_page(url).await.map_err(|e| e.for_character(character_id))
+    }
+
+    /// Requests a page, retrying transient failures according to the [`RetryPolicy`] and adding
+    /// the number of retries made to `retries`
+    async fn request_page_counted(&self, url: String, retries: &mut u32) -> Result<String, SF6Error> {
+        let mut attempt = 1;
+        loop {
+            match self.limited(self.request_page_once(&url)).await {
+                Err(e) if e.is_transient() && attempt < self.retry.max_attempts => {
+                    let backoff = self.retry.backoff(attempt, &mut rand::thread_rng());
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    *retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn request_page_once(&self, url: &str) -> Result<String, SF6Error> {
+        let remembered = self.conditional.as_ref().and_then(|cache| cache.pages.lock().unwrap().get(url).cloned());
+        let mut request = self.client.get(url);
+        if let Some(etag) = remembered.as_ref().and_then(|page| page.etag.as_ref()) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = remembered.as_ref().and_then(|page| page.last_modified.as_ref()) {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.send().await.map_err(|e| SF6Error::from_reqwest(url, e))?;
+        if let (StatusCode::NOT_MODIFIED, Some(page)) = (response.status(), remembered) {
+            return Ok(page.body);
+        }
+        if !response.status().is_success() {
+            return Err(SF6Error::Http { url: url.to_string(), status: response.status().as_u16() });
+        }
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await.map_err(|e| SF6Error::from_reqwest(url, e))?;
+        if let Some(cache) = &self.conditional {
+            cache.pages.lock().unwrap().insert(url.to_string(), RememberedPage {
+                etag,
+                last_modified,
+                body: body.clone(),
+            });
+        }
+        Ok(body)
+    }
+
+    /// Makes a request once there's a free slot for it under the [`RequestLimit`]
+    async fn limited<T>(&self, request: impl Future<Output = T>) -> T {
+        let _permit = self.limit.acquire().await;
+        let result = request.await;
+        if !self.limit.delay.is_zero() {
+            tokio::time::sleep(self.limit.delay).await;
+        }
+        result
+    }
+
+    fn parse(&self, character_id: &CharacterId, html: &str, gif_html: Option<&str>) -> CharacterFrameData {
+        let mut character_frame_data = parse_character_pages(character_id, html, gif_html);
+        if let Some(url_rewrite) = &self.url_rewrite {
+            for move_data in &mut character_frame_data.moves {
+                move_data.image_link = url_rewrite.apply(&move_data.image_link);
+            }
+            for gif in &mut character_frame_data.gifs {
+                url_rewrite.apply_gif(gif);
+            }
+        }
+        character_frame_data
+    }
+}
+
+/// The slots requests wait for, shared by a [`Loader`] and its clones so the limit holds across
+/// everything they request
+#[derive(Debug)]
+struct RequestLimit {
+    permits: Semaphore,
+    max_concurrent: usize,
+    delay: Duration,
+}
+
+impl RequestLimit {
+    fn new(max_concurrent: usize, delay: Duration) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        RequestLimit {
+            permits: Semaphore::new(max_concurrent),
+            max_concurrent,
+            delay,
+        }
+    }
+
+    async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.permits.acquire().await.expect("request limit is never closed")
+    }
+}
+
+impl Default for RequestLimit {
+    fn default() -> Self {
+        RequestLimit::new(DEFAULT_MAX_CONCURRENT_REQUESTS, Duration::ZERO)
+    }
+}
+
+/// A rule for rewriting media urls at parse time
+#[derive(Clone)]
+pub enum UrlRewrite {
+    /// Replaces a leading `from` with `to`, leaving urls that don't start with `from` untouched
+    Prefix { from: String, to: String },
+    /// Substitutes the original url into a template. `{url}` is replaced verbatim and
+    /// `{url_encoded}` percent-encoded, which suits services like imgproxy or thumbor.
+    Template(String),
+    /// Any other rewrite
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl UrlRewrite {
+    pub fn apply(&self, url: &str) -> String {
+        match self {
+            UrlRewrite::Prefix { from, to } => match url.strip_prefix(from.as_str()) {
+                Some(rest) => format!("{}{}", to, rest),
+                None => url.to_string(),
+            },
+            UrlRewrite::Template(template) => template
+                .replace("{url_encoded}", &percent_encode(url))
+                .replace("{url}", url),
+            UrlRewrite::Custom(rewrite) => rewrite(url),
+        }
+    }
+
+    fn apply_gif(&self, gif: &mut MoveGif) {
+        gif.url = self.apply(&gif.url);
+        gif.thumbnail_url = gif.thumbnail_url.as_deref().map(|url| self.apply(url));
+    }
+}
+
+impl Debug for UrlRewrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UrlRewrite::Prefix { from, to } => f.debug_struct("Prefix").field("from", from).field("to", to).finish(),
+            UrlRewrite::Template(template) => f.debug_tuple("Template").field(template).finish(),
+            UrlRewrite::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Validators and bodies of previously fetched pages, keyed by url
+#[derive(Debug, Default)]
+struct ConditionalCache {
+    pages: Mutex<HashMap<String, RememberedPage>>,
+}
+
+#[derive(Debug, Clone)]
+struct RememberedPage {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_urls() {
+        let url = "https://wiki.supercombo.gg/images/a.png";
+        let prefix = UrlRewrite::Prefix {
+            from: String::from("https://wiki.supercombo.gg/"),
+            to: String::from("https://cache.example/"),
+        };
+        assert_eq!(prefix.apply(url), "https://cache.example/images/a.png");
+        assert_eq!(prefix.apply("https://other.example/a.gif"), "https://other.example/a.gif");
+        let template = UrlRewrite::Template(String::from("https://img.example/?src={url_encoded}"));
+        assert_eq!(template.apply(url), "https://img.example/?src=https%3A%2F%2Fwiki.supercombo.gg%2Fimages%2Fa.png");
+    }
+
+    /// A raw HTTP response closing its connection, `headers` being `\r\n` terminated lines
+    pub(crate) fn response(status: &str, headers: &str, body: &str) -> String {
+        format!("HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}", status, headers, body.len(), body)
+    }
+
+    /// Serves `responses` to one connection each from a local port, returning its url and the
+    /// requests received
+    pub(crate) async fn serve(responses: Vec<String>) -> (String, Arc<Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/page", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 4096];
+                let read = stream.read(&mut buffer).await.unwrap();
+                received.lock().unwrap().push(String::from_utf8_lossy(&buffer[..read]).to_ascii_lowercase());
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn only_remembers_successful_pages() {
+        let (url, requests) = serve(vec![
+            response("404 Not Found", "ETag: \"missing\"\r\n", "gone"),
+            response("200 OK", "ETag: \"v1\"\r\n", "page"),
+            response("304 Not Modified", "", ""),
+        ]).await;
+        let loader = Loader::new().conditional_requests(true);
+        assert!(matches!(loader.request_page_once(&url).await, Err(SF6Error::Http { status: 404, .. })));
+        assert_eq!(loader.request_page_once(&url).await.unwrap(), "page");
+        assert_eq!(loader.request_page_once(&url).await.unwrap(), "page");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[1].contains("if-none-match"));
+        assert!(requests[2].contains("if-none-match: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_requests() {
+        let loader = Loader::new().max_concurrent_requests(2).request_delay(Duration::from_millis(20));
+        let in_flight = Arc::new(Mutex::new((0, 0)));
+        let mut set = JoinSet::new();
+        for _ in 0..6 {
+            let (loader, in_flight) = (loader.clone(), in_flight.clone());
+            set.spawn(async move {
+                loader.limited(async {
+                    {
+                        let mut counts = in_flight.lock().unwrap();
+                        counts.0 += 1;
+                        counts.1 = counts.1.max(counts.0);
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.lock().unwrap().0 -= 1;
+                }).await
+            });
+        }
+        let started = Instant::now();
+        set.join_all().await;
+        assert_eq!(in_flight.lock().unwrap().1, 2);
+        assert!(started.elapsed() >= Duration::from_millis(90));
+        assert_eq!(Loader::new().max_concurrent_requests(0).limit.max_concurrent, 1);
+    }
+
+    #[tokio::test]
+    async fn sends_requests_through_the_given_client() {
+        let page = crate::testing::frame_data_page(&[Move { identifier: String::from("5LP"), ..Move::default() }]);
+        let (url, requests) = serve(vec![response("200 OK", "", &page), response("200 OK", "", "<html></html>")]).await;
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-proxy-team", reqwest::header::HeaderValue::from_static("fgc"));
+        let client = reqwest::Client::builder().user_agent("sf6rs-test-agent").default_headers(headers).build().unwrap();
+        let ryu = crate::character::RYU.clone().with_frame_data_url(url.clone()).with_gif_data_url(url);
+        let loaded = Loader::new().http_client(client).retry_policy(RetryPolicy::none()).load(&ryu).await.unwrap();
+        assert_eq!(loaded.moves.len(), 1);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|request| request.contains("user-agent: sf6rs-test-agent") && request.contains("x-proxy-team: fgc")));
+    }
+
+    #[tokio::test]
+    async fn fails_empty_pages_on_every_path() {
+        let empty = crate::testing::frame_data_page(&[]);
+        let (url, _) = serve(vec![response("200 OK", "", &empty), response("200 OK", "", "<html></html>")]).await;
+        let ryu = crate::character::RYU.clone().with_frame_data_url(url.clone()).with_gif_data_url(url.clone());
+        let mut registry = CharacterRegistry::empty();
+        registry.register(ryu);
+        let report = Loader::new().retry_policy(RetryPolicy::none()).load_registry(&registry).await;
+        assert!(report.data.character_frame_data.is_empty());
+        assert_eq!(report.failures[0].1, SF6Error::ParseStructureChanged { url });
+    }
+}
@@ -0,0 +1,108 @@
+use scraper::{ElementRef, Html, Selector};
+
+use crate::framedata::Move;
+use crate::LazyLock;
+
+/// A character-specific system described on the wiki, like JP's Amnesia, Manon's Medals or Jamie's
+/// drink levels, so bots can explain a character's gimmick alongside their frame data
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniqueMechanic {
+    pub name: String,
+    pub description: String,
+    /// Identifiers of the character's moves the description mentions by name
+    pub related_moves: Vec<String>,
+}
+
+static SECTION_ELEMENT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("h2, h3, h4, h5, p, ul > li").unwrap());
+
+/// Headings the wiki uses for the section describing a character's mechanics
+const MECHANICS_HEADINGS: [&str; 3] = ["unique mechanic", "character specific mechanic", "character mechanic"];
+
+/// Parses the unique mechanics section of a character's overview page (see
+/// [`CharacterId::overview_url`](crate::character::CharacterId::overview_url)). Each subheading
+/// of the section becomes one [`UniqueMechanic`], with `moves` used to fill in `related_moves`.
+pub fn parse_unique_mechanics(html: &str, moves: &[Move]) -> Vec<UniqueMechanic> {
+    let html = Html::parse_document(html);
+    let mut elements = html.select(&SECTION_ELEMENT_SELECTOR);
+    let Some(section_level) = elements.by_ref()
+        .filter_map(|e| heading_level(e).map(|level| (level, text(e).to_ascii_lowercase())))
+        .find(|(_, heading)| MECHANICS_HEADINGS.iter().any(|h| heading.contains(h)))
+        .map(|(level, _)| level) else {
+        return Vec::new();
+    };
+
+    let mut mechanics: Vec<UniqueMechanic> = Vec::new();
+    for element in elements {
+        match heading_level(element) {
+            Some(level) if level <= section_level => break,
+            Some(_) => mechanics.push(UniqueMechanic {
+                name: text(element),
+                description: String::new(),
+                related_moves: Vec::new(),
+            }),
+            None => if let Some(mechanic) = mechanics.last_mut() {
+                let paragraph = text(element);
+                if !paragraph.is_empty() {
+                    if !mechanic.description.is_empty() {
+                        mechanic.description.push('\n');
+                    }
+                    mechanic.description.push_str(&paragraph);
+                }
+            },
+        }
+    }
+
+    for mechanic in &mut mechanics {
+        let description = mechanic.description.to_ascii_lowercase();
+        mechanic.related_moves = moves.iter()
+            .filter(|m| m.name.len() > 3 && description.contains(&m.name.to_ascii_lowercase()))
+            .map(|m| m.identifier.clone())
+            .collect();
+        mechanic.related_moves.dedup();
+    }
+    mechanics
+}
+
+fn heading_level(element: ElementRef) -> Option<u8> {
+    match element.value().name() {
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        _ => None,
+    }
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<String>().split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body>
+        <h2><span class="mw-headline">Overview</span></h2><p>A rushdown character.</p>
+        <h2><span class="mw-headline">Unique Mechanics</span></h2>
+        <h3><span class="mw-headline">Drink Level</span></h3>
+        <p>Jamie gains drink levels by using The Devil's Song.</p>
+        <p>Each level unlocks new moves.</p>
+        <h2><span class="mw-headline">Normals</span></h2><p>Unrelated.</p>
+    </body></html>"#;
+
+    #[test]
+    fn parses_mechanics_section() {
+        let drink = Move {
+            identifier: String::from("2MP"),
+            name: String::from("The Devil's Song"),
+            ..Move::default()
+        };
+        let mechanics = parse_unique_mechanics(PAGE, &[drink]);
+        assert_eq!(mechanics, vec![UniqueMechanic {
+            name: String::from("Drink Level"),
+            description: String::from("Jamie gains drink levels by using The Devil's Song.\nEach level unlocks new moves."),
+            related_moves: vec![String::from("2MP")],
+        }]);
+    }
+}
@@ -0,0 +1,203 @@
+use scraper::ElementRef;
+
+use crate::character::CharacterId;
+use crate::framedata::{FrameData, DEFAULT_IMAGE};
+
+#[cfg(feature = "media-processing")]
+pub mod frames;
+pub mod labels;
+#[cfg(any(feature = "media-processing", feature = "render"))]
+pub(crate) mod png;
+pub mod store;
+
+/// What is known about a hitbox image or gif without displaying it. Dimensions and content type
+/// are read from the page at parse time where it describes them; loaders with
+/// [`Loader::probe_media`](super::Loader::probe_media) enabled fill in the rest, including the
+/// file size, by requesting the first bytes of each file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MediaInfo {
+    /// e.g. `image/png` or `image/gif`
+    pub content_type: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// File size in bytes
+    pub bytes: Option<u64>,
+}
+
+impl MediaInfo {
+    /// Whether the media is at most `max_bytes` large and `max_dimension` pixels on its longest
+    /// side, e.g. to skip gifs too large for a chat embed. Unknown values are assumed to fit.
+    pub fn fits(&self, max_bytes: u64, max_dimension: u32) -> bool {
+        self.bytes.is_none_or(|bytes| bytes <= max_bytes)
+            && self.width.is_none_or(|width| width <= max_dimension)
+            && self.height.is_none_or(|height| height <= max_dimension)
+    }
+
+    /// Guesses the content type from a url's file extension
+    pub(crate) fn from_url(url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or_default().to_ascii_lowercase();
+        let content_type = match path.rsplit('.').next() {
+            Some("png") => Some("image/png"),
+            Some("gif") => Some("image/gif"),
+            Some("jpg" | "jpeg") => Some("image/jpeg"),
+            Some("webp") => Some("image/webp"),
+            _ => None,
+        };
+        MediaInfo {
+            content_type: content_type.map(str::to_string),
+            ..MediaInfo::default()
+        }
+    }
+
+    /// Reads the `width` and `height` attributes of an `img`, multiplied by `scale` for urls taken
+    /// from a `2x` srcset entry
+    pub(crate) fn with_img_dimensions(mut self, img: ElementRef, scale: u32) -> Self {
+        let attr = |name| img.value().attr(name).and_then(|v| v.trim().parse::<u32>().ok()).map(|v| v * scale);
+        self.width = attr("width");
+        self.height = attr("height");
+        self
+    }
+
+    /// Takes every value `other` knows, keeping this one's where `other` has none. Probed values
+    /// describe the actual file, so they win over what the page claims.
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn merge(&mut self, other: MediaInfo) {
+        self.content_type = other.content_type.or(self.content_type.take());
+        self.width = other.width.or(self.width);
+        self.height = other.height.or(self.height);
+        self.bytes = other.bytes.or(self.bytes);
+    }
+}
+
+/// How many of a character's moves have media, see [`FrameData::media_coverage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MediaCoverage {
+    pub character_id: CharacterId,
+    pub moves: usize,
+    /// Moves with their own image on the wiki rather than the default logo
+    pub with_image: usize,
+    /// Moves a gif was paired with, see [`CharacterFrameData::gif_report`](super::CharacterFrameData::gif_report)
+    pub with_gif: usize,
+}
+
+impl MediaCoverage {
+    /// The fraction (`0.0..=1.0`) of moves with their own image
+    pub fn image_coverage(&self) -> f32 {
+        fraction(self.with_image, self.moves)
+    }
+
+    /// The fraction (`0.0..=1.0`) of moves with a gif
+    pub fn gif_coverage(&self) -> f32 {
+        fraction(self.with_gif, self.moves)
+    }
+}
+
+fn fraction(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f32 / total as f32
+}
+
+impl FrameData {
+    /// Summarizes, per loaded character, how many moves have hitbox images and gifs, so bots can
+    /// decide per character whether media embeds are worth enabling
+    pub fn media_coverage(&self) -> Vec<MediaCoverage> {
+        self.character_frame_data.iter()
+            .map(|c| MediaCoverage {
+                character_id: c.character_id.clone(),
+                moves: c.moves.len(),
+                with_image: c.moves.iter().filter(|m| m.image_link != DEFAULT_IMAGE).count(),
+                with_gif: c.gif_report().matched.len(),
+            })
+            .collect()
+    }
+}
+
+/// Reads the pixel dimensions from the start of a GIF or PNG file
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub(crate) fn sniff_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"GIF8") && bytes.len() >= 10 {
+        let width = u16::from_le_bytes([bytes[6], bytes[7]]);
+        let height = u16::from_le_bytes([bytes[8], bytes[9]]);
+        return Some((width as u32, height as u32));
+    }
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") && bytes.len() >= 24 && &bytes[12..16] == b"IHDR" {
+        let width = u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+        let height = u32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]);
+        return Some((width, height));
+    }
+    None
+}
+
+/// Reads the total size out of a `Content-Range: bytes 0-63/12345` header
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub(crate) fn content_range_total(content_range: &str) -> Option<u64> {
+    content_range.rsplit('/').next()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framedata::Move;
+    use crate::testing;
+
+    #[test]
+    fn merges_probed_values_over_page_values() {
+        let mut info = MediaInfo {
+            content_type: Some(String::from("image/png")),
+            width: Some(175),
+            height: Some(200),
+            bytes: None,
+        };
+        info.merge(MediaInfo {
+            content_type: None,
+            width: Some(350),
+            height: None,
+            bytes: Some(4096),
+        });
+        assert_eq!(info, MediaInfo {
+            content_type: Some(String::from("image/png")),
+            width: Some(350),
+            height: Some(200),
+            bytes: Some(4096),
+        });
+    }
+
+    #[test]
+    fn sniffs_dimensions() {
+        let gif = b"GIF89a\x40\x01\xf0\x00\x00";
+        assert_eq!(sniff_dimensions(gif), Some((320, 240)));
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x01\x5e\x00\x00\x01\x5e";
+        assert_eq!(sniff_dimensions(png), Some((350, 350)));
+        assert_eq!(sniff_dimensions(b"<html>"), None);
+        assert_eq!(content_range_total("bytes 0-63/2048576"), Some(2048576));
+    }
+
+    #[test]
+    fn summarizes_coverage() {
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&crate::character::RYU, vec![
+                Move { image_link: String::from("https://wiki.supercombo.gg/images/a.png"), ..Move::default() },
+                Move { image_link: DEFAULT_IMAGE.to_string(), ..Move::default() },
+            ])],
+        };
+        let coverage = data.media_coverage();
+        assert_eq!((coverage[0].moves, coverage[0].with_image, coverage[0].with_gif), (2, 1, 0));
+        assert_eq!(coverage[0].image_coverage(), 0.5);
+        assert_eq!(coverage[0].gif_coverage(), 0.0);
+    }
+
+    #[test]
+    fn checks_limits() {
+        let info = MediaInfo {
+            bytes: Some(12 * 1024 * 1024),
+            ..MediaInfo::from_url("https://ultimateframedata.com/sf6/hitboxes/ryu/Ryu5LP.gif")
+        };
+        assert_eq!(info.content_type.as_deref(), Some("image/gif"));
+        assert!(!info.fits(10 * 1024 * 1024, 4096));
+        assert!(MediaInfo::default().fits(0, 0));
+    }
+}
@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::num::NonZeroU64;
+use std::time::Duration;
+
+use gif::{ColorOutput, DecodeOptions, DecodingError, DisposalMethod, MemoryLimit};
+
+use crate::framedata::media::png;
+
+/// The most pixels a gif's canvas may have. Hitbox gifs are a few hundred pixels wide; anything
+/// past this is broken or hostile, and is rejected before any of it is allocated.
+pub const MAX_CANVAS_PIXELS: u64 = 4096 * 4096;
+/// The most bytes all decoded frames of one gif may take up together
+pub const MAX_DECODED_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaError {
+    /// The bytes do not start with a GIF header
+    NotAGif,
+    /// The file ended before its first image
+    Truncated,
+    /// The file has no frames
+    NoFrames,
+    /// The gif, or the image built from it, is larger than [`MAX_CANVAS_PIXELS`] or
+    /// [`MAX_DECODED_BYTES`] allow
+    TooLarge { width: u64, height: u64 },
+    /// The file is structurally broken
+    Corrupt(String),
+}
+
+impl Display for MediaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaError::NotAGif => write!(f, "Not a gif"),
+            MediaError::Truncated => write!(f, "Gif is truncated"),
+            MediaError::NoFrames => write!(f, "Gif has no frames"),
+            MediaError::TooLarge { width, height } => write!(f, "Gif is too large: {}x{}", width, height),
+            MediaError::Corrupt(reason) => write!(f, "Corrupt gif: {}", reason),
+        }
+    }
+}
+
+impl Error for MediaError {}
+
+impl From<DecodingError> for MediaError {
+    fn from(error: DecodingError) -> Self {
+        match error {
+            DecodingError::Io(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => MediaError::Truncated,
+            e => MediaError::Corrupt(e.to_string()),
+        }
+    }
+}
+
+/// One fully composited frame of a gif, the way a viewer would display it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    /// `width * height` pixels of red, green, blue and alpha bytes
+    pub rgba: Vec<u8>,
+    /// How long the frame is shown for
+    pub delay: Duration,
+}
+
+impl Frame {
+    /// Encodes this frame as a PNG file
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode_rgba(self.width, self.height, &self.rgba)
+    }
+}
+
+/// Decodes the first frame of a gif and encodes it as a PNG, e.g. for a static preview thumbnail
+/// in a list of moves that only plays the gif when opened
+pub fn first_frame_png(gif: &[u8]) -> Result<Vec<u8>, MediaError> {
+    let frame = decode_frames(gif, Some(1))?.into_iter().next().ok_or(MediaError::NoFrames)?;
+    Ok(frame.to_png())
+}
+
+/// Lays every frame of a gif out left to right, top to bottom in a grid `columns` frames wide and
+/// encodes it as one PNG, for CSS sprite animations or scrubbing through a move frame by frame
+pub fn sprite_sheet_png(gif: &[u8], columns: u32) -> Result<Vec<u8>, MediaError> {
+    let frames = decode_frames(gif, None)?;
+    let Some(first) = frames.first() else {
+        return Err(MediaError::NoFrames);
+    };
+    let (frame_width, frame_height) = (first.width as usize, first.height as usize);
+    let columns = (columns.max(1) as usize).min(frames.len());
+    let rows = frames.len().div_ceil(columns);
+    let (width, height) = (frame_width * columns, frame_height * rows);
+    check_size(width as u64, height as u64, 1)?;
+    let mut rgba = vec![0; width * height * 4];
+    for (i, frame) in frames.iter().enumerate() {
+        let (left, top) = ((i % columns) * frame_width, (i / columns) * frame_height);
+        for (y, row) in frame.rgba.chunks(frame_width * 4).enumerate() {
+            let start = ((top + y) * width + left) * 4;
+            rgba[start..start + row.len()].copy_from_slice(row);
+        }
+    }
+    Ok(png::encode_rgba(width as u32, height as u32, &rgba))
+}
+
+/// Decodes a gif into composited frames, stopping after `limit` frames if given. Frames after the
+/// first that are cut off by the end of the file are dropped rather than failing the decode.
+/// Gifs whose canvas is larger than [`MAX_CANVAS_PIXELS`] are rejected before decoding, and
+/// decoding stops with [`MediaError::TooLarge`] once the frames exceed [`MAX_DECODED_BYTES`].
+pub fn decode_frames(gif: &[u8], limit: Option<usize>) -> Result<Vec<Frame>, MediaError> {
+    if !gif.starts_with(b"GIF87a") && !gif.starts_with(b"GIF89a") {
+        return Err(MediaError::NotAGif);
+    }
+    let (width, height) = match gif.get(6..10) {
+        Some(size) => (u16::from_le_bytes([size[0], size[1]]) as usize, u16::from_le_bytes([size[2], size[3]]) as usize),
+        None => return Err(MediaError::Truncated),
+    };
+    check_size(width as u64, height as u64, 1)?;
+
+    let mut options = DecodeOptions::new();
+    options.set_color_output(ColorOutput::RGBA);
+    options.set_memory_limit(MemoryLimit::Bytes(NonZeroU64::new(MAX_CANVAS_PIXELS * 4).unwrap()));
+    let mut decoder = options.read_info(gif)?;
+    let mut canvas = vec![0u8; width * height * 4];
+    let mut frames: Vec<Frame> = Vec::new();
+    loop {
+        if limit.is_some_and(|limit| frames.len() >= limit) {
+            break;
+        }
+        let frame = match decoder.read_next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(_) if !frames.is_empty() => break,
+            Err(e) => return Err(e.into()),
+        };
+        check_size(width as u64, height as u64, frames.len() as u64 + 1)?;
+        let area = Area::of(frame, width, height);
+        let previous = (frame.dispose == DisposalMethod::Previous).then(|| canvas.clone());
+        area.draw(&mut canvas, width, &frame.buffer, frame.width as usize);
+        frames.push(Frame {
+            width: width as u32,
+            height: height as u32,
+            rgba: canvas.clone(),
+            delay: Duration::from_millis(frame.delay as u64 * 10),
+        });
+        match (frame.dispose, previous) {
+            (DisposalMethod::Background, _) => area.clear(&mut canvas, width),
+            (DisposalMethod::Previous, Some(previous)) => canvas = previous,
+            _ => {}
+        }
+    }
+    if frames.is_empty() {
+        return Err(MediaError::NoFrames);
+    }
+    Ok(frames)
+}
+
+/// Fails if `frames` RGBA images of `width` by `height` exceed the decoding limits
+fn check_size(width: u64, height: u64, frames: u64) -> Result<(), MediaError> {
+    let pixels = width * height;
+    if pixels > MAX_CANVAS_PIXELS * frames.max(1) || pixels * 4 * frames > MAX_DECODED_BYTES {
+        return Err(MediaError::TooLarge { width, height });
+    }
+    Ok(())
+}
+
+/// The part of the canvas a frame covers, clipped to the canvas
+struct Area {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+impl Area {
+    fn of(frame: &gif::Frame, canvas_width: usize, canvas_height: usize) -> Self {
+        let (left, top) = (frame.left as usize, frame.top as usize);
+        Area {
+            left: left.min(canvas_width),
+            top: top.min(canvas_height),
+            right: (left + frame.width as usize).min(canvas_width),
+            bottom: (top + frame.height as usize).min(canvas_height),
+        }
+    }
+
+    /// Draws a frame's opaque pixels over the canvas; transparent ones let earlier frames show
+    fn draw(&self, canvas: &mut [u8], canvas_width: usize, buffer: &[u8], frame_width: usize) {
+        for y in self.top..self.bottom {
+            for x in self.left..self.right {
+                let source = ((y - self.top) * frame_width + (x - self.left)) * 4;
+                let Some(pixel) = buffer.get(source..source + 4) else {
+                    continue;
+                };
+                if pixel[3] != 0 {
+                    let target = (y * canvas_width + x) * 4;
+                    canvas[target..target + 4].copy_from_slice(pixel);
+                }
+            }
+        }
+    }
+
+    fn clear(&self, canvas: &mut [u8], canvas_width: usize) {
+        for y in self.top..self.bottom {
+            canvas[(y * canvas_width + self.left) * 4..(y * canvas_width + self.right) * 4].fill(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framedata::media::png::decode_rgba;
+
+    /// A 2x1 gif with two frames: red then blue over a transparent right pixel
+    const GIF: &[u8] = &[
+        b'G', b'I', b'F', b'8', b'9', b'a', 2, 0, 1, 0, 0x80, 0, 0,
+        255, 0, 0, 0, 0, 255,
+        0x21, 0xf9, 4, 0x00, 10, 0, 0, 0,
+        0x2c, 0, 0, 0, 0, 2, 0, 1, 0, 0x00, 2, 2, 0x04, 0x0a, 0,
+        0x21, 0xf9, 4, 0x01, 20, 0, 0, 0,
+        0x2c, 0, 0, 0, 0, 2, 0, 1, 0, 0x00, 2, 2, 0x0c, 0x0a, 0,
+        0x3b,
+    ];
+
+    #[test]
+    fn decodes_frames() {
+        let frames = decode_frames(GIF, None).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].rgba, vec![255, 0, 0, 255, 255, 0, 0, 255]);
+        assert_eq!(frames[0].delay, Duration::from_millis(100));
+        // index 0 is transparent in the second frame, so the first frame shows through on the right
+        assert_eq!(frames[1].rgba, vec![0, 0, 255, 255, 255, 0, 0, 255]);
+        assert_eq!(decode_frames(GIF, Some(1)).unwrap().len(), 1);
+        assert_eq!(decode_frames(b"<html>", None), Err(MediaError::NotAGif));
+    }
+
+    #[test]
+    fn extracts_pngs() {
+        let (width, height, rgba) = decode_rgba(&first_frame_png(GIF).unwrap());
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(rgba, vec![255, 0, 0, 255, 255, 0, 0, 255]);
+        let (width, height, _) = decode_rgba(&sprite_sheet_png(GIF, 1).unwrap());
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(first_frame_png(&GIF[..20]), Err(MediaError::Truncated));
+    }
+
+    #[test]
+    fn rejects_huge_canvases_before_allocating() {
+        let mut huge = GIF.to_vec();
+        huge[6..10].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(decode_frames(&huge, None), Err(MediaError::TooLarge { width: 65535, height: 65535 }));
+    }
+}
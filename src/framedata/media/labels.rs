@@ -0,0 +1,158 @@
+use crate::character::{CharacterId, CHARACTERS};
+use crate::framedata::{Move, DEFAULT_IMAGE};
+use crate::text::{normalize, wiki_file_name};
+
+/// What a wiki image shows, read from its file name, e.g. `SF6_Ryu_5hp_hitbox2.png` is the second
+/// hitbox image of Ryu's 5HP. Moves with several hits or phases have several images, and these
+/// labels let viewers group and order them without looking at the images.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageLabel {
+    /// `None` when the name doesn't start with a known character
+    pub character: Option<&'static CharacterId>,
+    /// The move part of the name as written, e.g. `5hp` or `Denjin Hashogeki`
+    pub move_name: String,
+    /// Whether this is the hitbox overlay rather than the plain animation frame
+    pub hitbox: bool,
+    /// Which hit or phase of the move the image shows, for moves with several images
+    pub hit: Option<u32>,
+}
+
+impl ImageLabel {
+    /// Parses a url, file name or caption like `SF6 Ryu 5lp hitbox.png`. Thumbnail urls are read by
+    /// their last path segment with the `350px-` size prefix removed.
+    pub fn parse(name: &str) -> Option<ImageLabel> {
+        let file = percent_decode(wiki_file_name(name));
+        let stem = file.rsplit_once('.')
+            .filter(|(_, extension)| extension.len() <= 4)
+            .map_or(file.as_str(), |(stem, _)| stem);
+        let mut tokens = stem.split(['_', ' ']).filter(|t| !t.is_empty()).collect::<Vec<&str>>();
+        if !tokens.first().is_some_and(|t| t.eq_ignore_ascii_case("sf6")) {
+            return None;
+        }
+        tokens.remove(0);
+
+        let character = CHARACTERS.iter().copied()
+            .filter_map(|c| {
+                let parts = c.frame_data_id.split('_').count();
+                let candidate = tokens.get(..parts)?.join("_");
+                (candidate.eq_ignore_ascii_case(&c.frame_data_id) || candidate.eq_ignore_ascii_case(&c.id)).then_some((c, parts))
+            })
+            .max_by_key(|(_, parts)| *parts);
+        if let Some((_, parts)) = character {
+            tokens.drain(..parts);
+        }
+
+        let mut hitbox = false;
+        let mut hit = None;
+        while let Some(last) = tokens.last().map(|t| t.to_ascii_lowercase()) {
+            if let Some(number) = last.strip_prefix("hitbox") {
+                hitbox = true;
+                hit = hit.or(number.parse().ok());
+            } else if let Some(number) = last.strip_prefix("hit").filter(|n| !n.is_empty()) {
+                let Ok(number) = number.parse() else {
+                    break;
+                };
+                hit = hit.or(Some(number));
+            } else if let Ok(number) = last.parse() {
+                if tokens.len() == 1 {
+                    break;
+                }
+                hit = hit.or(Some(number));
+            } else {
+                break;
+            }
+            tokens.pop();
+        }
+        if tokens.is_empty() {
+            return None;
+        }
+        Some(ImageLabel {
+            character: character.map(|(c, _)| c),
+            move_name: tokens.join(" "),
+            hitbox,
+            hit,
+        })
+    }
+
+    /// Whether this image is of `move_data`, comparing the move part of the name against the
+    /// move's input, identifier and name while ignoring case, spaces and punctuation
+    pub fn matches(&self, move_data: &Move) -> bool {
+        let label = normalize(&self.move_name);
+        !label.is_empty() && [&move_data.input, &move_data.identifier, &move_data.name].iter()
+            .any(|value| normalize(value) == label)
+    }
+
+    /// Orders a move's images: animation frames before hitboxes, then by hit
+    pub fn sort_key(&self) -> (bool, u32) {
+        (self.hitbox, self.hit.unwrap_or(1))
+    }
+}
+
+impl Move {
+    /// The [`ImageLabel`] of this move's `image_link`, `None` for the default image
+    pub fn image_label(&self) -> Option<ImageLabel> {
+        if self.image_link == DEFAULT_IMAGE {
+            return None;
+        }
+        ImageLabel::parse(&self.image_link)
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+
+    #[test]
+    fn parses_image_names() {
+        let url = "https://wiki.supercombo.gg//images/thumb/6/65/SF6_Ryu_5hp_hitbox2.png/350px-SF6_Ryu_5hp_hitbox2.png";
+        let label = ImageLabel::parse(url).unwrap();
+        assert_eq!(label, ImageLabel {
+            character: Some(&character::RYU),
+            move_name: String::from("5hp"),
+            hitbox: true,
+            hit: Some(2),
+        });
+        let deejay = ImageLabel::parse("SF6 Dee Jay 236KK 2.png").unwrap();
+        assert_eq!(deejay.character, Some(&character::DEEJAY));
+        assert_eq!((deejay.move_name.as_str(), deejay.hitbox, deejay.hit), ("236KK", false, Some(2)));
+        let bison = ImageLabel::parse("SF6_M.Bison_Psycho_Crusher%28OD%29_hitbox.png").unwrap();
+        assert_eq!(bison.move_name, "Psycho Crusher(OD)");
+        assert!(bison.sort_key() > deejay.sort_key());
+        assert_eq!(ImageLabel::parse("Ryu_5hp.png"), None);
+    }
+
+    #[test]
+    fn matches_moves() {
+        let heavy_punch = Move {
+            identifier: String::from("5HP"),
+            input: String::from("5HP"),
+            ..Move::default()
+        };
+        assert!(ImageLabel::parse("SF6_Ryu_5hp_hitbox.png").unwrap().matches(&heavy_punch));
+        assert!(!ImageLabel::parse("SF6_Ryu_2hp_hitbox.png").unwrap().matches(&heavy_punch));
+        assert_eq!(Move { image_link: DEFAULT_IMAGE.to_string(), ..heavy_punch }.image_label(), None);
+    }
+}
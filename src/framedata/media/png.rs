@@ -0,0 +1,39 @@
+//! PNG encoding for RGBA images, shared by gif frame extraction and table rendering
+
+/// Encodes `rgba`, `width * height` pixels of 4 bytes each, as a compressed PNG file
+pub(crate) fn encode_rgba(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = ::png::Encoder::new(&mut png, width, height);
+    encoder.set_color(::png::ColorType::Rgba);
+    encoder.set_depth(::png::BitDepth::Eight);
+    encoder.set_compression(::png::Compression::Best);
+    encoder.set_adaptive_filter(::png::AdaptiveFilterType::Adaptive);
+    // writing to a Vec can't fail, and callers always pass width * height pixels
+    let mut writer = encoder.write_header().expect("png header for a Vec");
+    writer.write_image_data(rgba).expect("rgba holds width * height pixels");
+    writer.finish().expect("png written to a Vec");
+    png
+}
+
+#[cfg(test)]
+pub(crate) fn decode_rgba(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    let mut reader = ::png::Decoder::new(png).read_info().unwrap();
+    let mut rgba = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut rgba).unwrap();
+    rgba.truncate(info.buffer_size());
+    (info.width, info.height, rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_pixels() {
+        let rgba = [255, 0, 0, 255, 0, 0, 255, 128];
+        let png = encode_rgba(2, 1, &rgba);
+        assert_eq!(decode_rgba(&png), (2, 1, rgba.to_vec()));
+        let blank = encode_rgba(512, 512, &vec![0; 512 * 512 * 4]);
+        assert!(blank.len() < 4096);
+    }
+}
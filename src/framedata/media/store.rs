@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use sha2::{Digest, Sha256};
+
+/// A directory of downloaded media named by the SHA-256 of their contents, plus an `index.tsv`
+/// mapping each source url to its hash. Characters that share an image (universal throws, the
+/// fallback logo...) store it once, and checking whether a url is already downloaded is a lookup
+/// in the index rather than a request. Loaders given a store with
+/// [`Loader::media_store`](crate::framedata::Loader::media_store) download media through it.
+/// ```text
+/// index.tsv
+/// objects/<first two hex digits>/<full hash>
+/// ```
+#[derive(Debug)]
+pub struct MediaStore {
+    dir: PathBuf,
+    index: Mutex<HashMap<String, String>>,
+}
+
+impl MediaStore {
+    /// Opens the store in `dir`, reading its index if it has one
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        let index = match fs::read_to_string(dir.join("index.tsv")) {
+            Ok(index) => index.lines()
+                .filter_map(|line| line.split_once('\t'))
+                .map(|(url, hash)| (unescape(url), hash.to_string()))
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(MediaStore {
+            dir,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// The directory this store reads from and writes to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The hash `url` was stored under, if it was downloaded before and its object still exists
+    pub fn hash_of(&self, url: &str) -> Option<String> {
+        let hash = self.index.lock().unwrap().get(url).cloned()?;
+        self.object_path(&hash).exists().then_some(hash)
+    }
+
+    /// Whether `url` has already been downloaded into this store
+    pub fn contains_url(&self, url: &str) -> bool {
+        self.hash_of(url).is_some()
+    }
+
+    /// Where the object with `hash` is stored, whether or not it exists
+    pub fn object_path(&self, hash: &str) -> PathBuf {
+        self.dir.join("objects").join(hash.get(..2).unwrap_or(hash)).join(hash)
+    }
+
+    /// Stores `bytes` as downloaded from `url` and returns their hash. Contents already in the
+    /// store are not written again.
+    pub fn put(&self, url: &str, bytes: &[u8]) -> io::Result<String> {
+        let hash = sha256_hex(bytes);
+        let path = self.object_path(&hash);
+        if !path.exists() {
+            let parent = path.parent().unwrap_or(&self.dir);
+            fs::create_dir_all(parent)?;
+            let tmp = parent.join(format!(".{}.tmp", hash));
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)?;
+        }
+        let mut index = self.index.lock().unwrap();
+        if index.get(url) != Some(&hash) {
+            index.insert(url.to_string(), hash.clone());
+            write_index(&self.dir, &index)?;
+        }
+        Ok(hash)
+    }
+
+    /// Reads the contents previously downloaded from `url`
+    pub fn get(&self, url: &str) -> io::Result<Option<Vec<u8>>> {
+        let Some(hash) = self.hash_of(url) else {
+            return Ok(None);
+        };
+        fs::read(self.object_path(&hash)).map(Some)
+    }
+}
+
+fn write_index(dir: &Path, index: &HashMap<String, String>) -> io::Result<()> {
+    let mut entries = index.iter().collect::<Vec<_>>();
+    entries.sort();
+    let contents = entries.into_iter()
+        .map(|(url, hash)| format!("{}\t{}\n", escape(url), hash))
+        .collect::<String>();
+    fs::create_dir_all(dir)?;
+    let tmp = dir.join(".index.tsv.tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, dir.join("index.tsv"))
+}
+
+/// Escapes the characters that would break an `index.tsv` line, so a url with a tab or newline in
+/// it can't split into a corrupt entry
+fn escape(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses [`escape`]
+fn unescape(url: &str) -> String {
+    let mut unescaped = String::with_capacity(url.len());
+    let mut chars = url.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// The lowercase hex SHA-256 digest of `data`
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn hashes_contents() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn deduplicates_by_contents() {
+        let dir = TempDir::new("sf6rs-media-store-test");
+        let store = MediaStore::open(dir.to_path_buf()).unwrap();
+        let ryu = store.put("https://example.com/ryu/throw.png", b"throw").unwrap();
+        let ken = store.put("https://example.com/ken/throw.png", b"throw").unwrap();
+        assert_eq!(ryu, ken);
+        assert_eq!(fs::read_dir(store.object_path(&ryu).parent().unwrap()).unwrap().count(), 1);
+
+        let reopened = MediaStore::open(dir.to_path_buf()).unwrap();
+        assert!(reopened.contains_url("https://example.com/ken/throw.png"));
+        assert!(!reopened.contains_url("https://example.com/ken/5lp.png"));
+        assert_eq!(reopened.get("https://example.com/ryu/throw.png").unwrap(), Some(b"throw".to_vec()));
+    }
+
+    #[test]
+    fn escapes_urls_in_index() {
+        let dir = TempDir::new("sf6rs-media-store-escape-test");
+        let url = "https://example.com/a\tb\nc\\d.png";
+        let store = MediaStore::open(dir.to_path_buf()).unwrap();
+        store.put(url, b"image").unwrap();
+        store.put("https://example.com/plain.png", b"plain").unwrap();
+        assert_eq!(fs::read_to_string(dir.join("index.tsv")).unwrap().lines().count(), 2);
+
+        let reopened = MediaStore::open(dir.to_path_buf()).unwrap();
+        assert_eq!(reopened.get(url).unwrap(), Some(b"image".to_vec()));
+        assert!(reopened.contains_url("https://example.com/plain.png"));
+    }
+}
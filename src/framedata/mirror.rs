@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::error::SF6Error;
+use crate::export::publish::{MirrorIndex, MirrorManifest, DATA_FILE, INDEX_FILE, MANIFEST_FILE};
+use crate::framedata::json::JsonError;
+use crate::framedata::media::store::sha256_hex;
+use crate::framedata::{FrameData, Loader};
+
+/// Why frame data couldn't be read from a mirror
+#[derive(Debug)]
+pub enum MirrorError {
+    /// A file of the mirror could not be downloaded
+    Fetch(SF6Error),
+    /// `index.json` or `manifest.json` is not what [`publish`](crate::export::publish::publish)
+    /// writes
+    InvalidMetadata(serde_json::Error),
+    /// The manifest doesn't list `framedata.json`
+    MissingFile(String),
+    /// A downloaded file doesn't match the checksum in the manifest
+    ChecksumMismatch { path: String, expected: String, actual: String },
+    /// `framedata.json` couldn't be read, e.g. because it has a newer schema
+    Data(JsonError),
+}
+
+impl Display for MirrorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MirrorError::Fetch(e) => write!(f, "Could not download from mirror: {}", e),
+            MirrorError::InvalidMetadata(e) => write!(f, "Invalid mirror metadata: {}", e),
+            MirrorError::MissingFile(path) => write!(f, "Mirror manifest doesn't list {}", path),
+            MirrorError::ChecksumMismatch { path, expected, actual } => write!(f, "Checksum mismatch for {}: expected {}, got {}", path, expected, actual),
+            MirrorError::Data(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for MirrorError {}
+
+impl From<serde_json::Error> for MirrorError {
+    fn from(e: serde_json::Error) -> Self {
+        MirrorError::InvalidMetadata(e)
+    }
+}
+
+impl From<JsonError> for MirrorError {
+    fn from(e: JsonError) -> Self {
+        MirrorError::Data(e)
+    }
+}
+
+impl FrameData {
+    /// Downloads the latest version from a mirror written by
+    /// [`publish`](crate::export::publish::publish) at `base_url`, e.g.
+    /// `https://example.github.io/sf6-mirror`, instead of scraping the wiki. The data is checked
+    /// against the manifest's SHA-256 before it's read.
+    pub async fn from_remote_mirror(base_url: &str) -> Result<FrameData, MirrorError> {
+        FrameData::from_remote_mirror_with(&Loader::default(), base_url).await
+    }
+
+    /// Like [`FrameData::from_remote_mirror`], downloading with `loader`
+    pub async fn from_remote_mirror_with(loader: &Loader, base_url: &str) -> Result<FrameData, MirrorError> {
+        let base_url = base_url.trim_end_matches('/');
+        let fetch = |path: String| async move {
+            loader.fetch_bytes(&format!("{}/{}", base_url, path)).await.map_err(MirrorError::Fetch)
+        };
+        let index: MirrorIndex = serde_json::from_slice(&fetch(INDEX_FILE.to_string()).await?)?;
+        let manifest: MirrorManifest = serde_json::from_slice(&fetch(format!("{}/{}", index.latest, MANIFEST_FILE)).await?)?;
+        let data = fetch(format!("{}/{}", index.latest, DATA_FILE)).await?;
+        read_verified(&manifest, &data)
+    }
+}
+
+/// Reads `framedata.json`'s bytes once they match `manifest`'s checksum
+fn read_verified(manifest: &MirrorManifest, data: &[u8]) -> Result<FrameData, MirrorError> {
+    let file = manifest.files.iter()
+        .find(|file| file.path == DATA_FILE)
+        .ok_or_else(|| MirrorError::MissingFile(DATA_FILE.to_string()))?;
+    let actual = sha256_hex(data);
+    if !actual.eq_ignore_ascii_case(&file.sha256) {
+        return Err(MirrorError::ChecksumMismatch { path: file.path.clone(), expected: file.sha256.clone(), actual });
+    }
+    Ok(FrameData::from_json(&String::from_utf8_lossy(data))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::export::publish::{publish, MirrorManifest};
+    use crate::framedata::version::GameVersion;
+    use crate::framedata::Move;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    #[test]
+    fn verifies_mirror_checksums() {
+        let dir = TempDir::new("sf6rs-mirror-test");
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::JP, vec![Move { identifier: String::from("5LP"), ..Move::default() }])],
+        };
+        let manifest = publish(&data, &dir, &GameVersion::new("1.0")).unwrap();
+        let bytes = std::fs::read(dir.join("1.0").join(DATA_FILE)).unwrap();
+        let read = read_verified(&manifest, &bytes).unwrap();
+        assert_eq!(read.character_frame_data[0].moves[0].identifier, "5LP");
+
+        let mut tampered = bytes.clone();
+        tampered.push(b' ');
+        assert!(matches!(read_verified(&manifest, &tampered), Err(MirrorError::ChecksumMismatch { .. })));
+        let unlisted = MirrorManifest { files: Vec::new(), ..manifest };
+        assert!(matches!(read_verified(&unlisted, &bytes), Err(MirrorError::MissingFile(_))));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::character::CharacterId;
+use crate::framedata::corrections::{Correction, CorrectionReport};
+use crate::framedata::{FrameData, Move};
+
+/// A move to add to a character, e.g. one the wiki is missing. Fields left out of an overrides
+/// file are empty.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddedMove {
+    pub character_id: CharacterId,
+    #[cfg_attr(feature = "serde", serde(rename = "move"))]
+    pub move_data: Move,
+}
+
+/// A move to remove from a character, e.g. a broken entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HiddenMove {
+    pub character_id: CharacterId,
+    pub identifier: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reason: Option<String>,
+}
+
+/// A set of hotfixes applied after loading. With the `serde` feature they can be read from a JSON
+/// file, so data issues can be fixed in production without a release:
+/// ```json
+/// {
+///   "hidden": [{ "character_id": "ryu", "identifier": "5LP(broken)" }],
+///   "added": [{ "character_id": "ken", "move": { "identifier": "SA4", ... } }],
+///   "corrections": [{ "character_id": "ryu", "identifier": "5MP", "field": "startup", "value": "6" }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Overrides {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hidden: Vec<HiddenMove>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub added: Vec<AddedMove>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub corrections: Vec<Correction>,
+}
+
+#[cfg(feature = "serde")]
+impl Overrides {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Reads overrides from a JSON file
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Overrides::from_json(&json).map_err(std::io::Error::other)
+    }
+}
+
+/// Where a move's data came from after [`FrameData::apply_overrides`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Provenance {
+    /// Scraped from the wiki unchanged
+    Wiki,
+    /// Scraped from the wiki with these fields corrected
+    Corrected(Vec<String>),
+    /// Added by an override
+    Added,
+    /// Hidden by an override
+    Hidden,
+}
+
+/// What [`FrameData::apply_overrides`] did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverrideReport {
+    /// `(character id, identifier)` of every move that was hidden
+    pub hidden: Vec<(String, String)>,
+    /// `(character id, identifier)` of every move that was added
+    pub added: Vec<(String, String)>,
+    /// Hidden moves that weren't found and added moves that already existed, which were skipped
+    pub skipped: Vec<(String, String)>,
+    pub corrections: CorrectionReport,
+}
+
+impl OverrideReport {
+    /// Where a move's data came from
+    pub fn provenance(&self, character_id: &CharacterId, identifier: &str) -> Provenance {
+        let matches = |(id, ident): &(String, String)| *id == character_id.id && ident.eq_ignore_ascii_case(identifier);
+        if self.hidden.iter().any(matches) {
+            return Provenance::Hidden;
+        }
+        if self.added.iter().any(matches) {
+            return Provenance::Added;
+        }
+        let fields = self.corrections.diverged.iter()
+            .map(|divergence| &divergence.correction)
+            .filter(|c| &c.character_id == character_id && c.identifier.eq_ignore_ascii_case(identifier))
+            .map(|c| c.field.clone())
+            .collect::<Vec<String>>();
+        if fields.is_empty() {
+            Provenance::Wiki
+        } else {
+            Provenance::Corrected(fields)
+        }
+    }
+}
+
+impl FrameData {
+    /// Applies `overrides`: hidden moves are removed first, then added moves appended, then
+    /// corrections applied (so added moves can be corrected too). Applying the same overrides to
+    /// the same scrape always gives the same result. Characters that aren't loaded are skipped.
+    pub fn apply_overrides(&mut self, overrides: &Overrides) -> OverrideReport {
+        let mut report = OverrideReport::default();
+        for hidden in &overrides.hidden {
+            let key = (hidden.character_id.id.to_string(), hidden.identifier.clone());
+            let Some(character_frame_data) = self.character_frame_data.iter_mut().find(|c| c.character_id == hidden.character_id) else {
+                continue;
+            };
+            let before = character_frame_data.moves.len();
+            character_frame_data.moves.retain(|m| !m.identifier.eq_ignore_ascii_case(&hidden.identifier));
+            if character_frame_data.moves.len() < before {
+                report.hidden.push(key);
+            } else {
+                report.skipped.push(key);
+            }
+        }
+        for added in &overrides.added {
+            let key = (added.character_id.id.to_string(), added.move_data.identifier.clone());
+            let Some(character_frame_data) = self.character_frame_data.iter_mut().find(|c| c.character_id == added.character_id) else {
+                continue;
+            };
+            if character_frame_data.moves.iter().any(|m| m.identifier.eq_ignore_ascii_case(&added.move_data.identifier)) {
+                report.skipped.push(key);
+            } else {
+                character_frame_data.moves.push(added.move_data.clone());
+                report.added.push(key);
+            }
+        }
+        report.corrections = self.apply_corrections(&overrides.corrections);
+        report
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn applies_overrides_file() {
+        let mut data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![
+                Move { identifier: String::from("5MP"), startup: String::from("7"), ..Move::default() },
+                Move { identifier: String::from("5LP(broken)"), ..Move::default() },
+            ])],
+        };
+        let overrides = Overrides::from_json(r#"{
+            "hidden": [{ "character_id": "ryu", "identifier": "5LP(broken)" }, { "character_id": "ryu", "identifier": "6HK" }],
+            "added": [{ "character_id": "ryu", "move": { "identifier": "5LP", "name": "Standing Light Punch", "startup": "4" } }],
+            "corrections": [{ "character_id": "ryu", "identifier": "5MP", "field": "startup", "value": "6" }]
+        }"#).unwrap();
+        let report = data.apply_overrides(&overrides);
+        let ryu = data.find_character_frame_data(&character::RYU).unwrap();
+        assert_eq!(ryu.moves.iter().map(|m| m.identifier.as_str()).collect::<Vec<&str>>(), vec!["5MP", "5LP"]);
+        assert_eq!(report.skipped, vec![(String::from("ryu"), String::from("6HK"))]);
+        assert_eq!(report.provenance(&character::RYU, "5mp"), Provenance::Corrected(vec![String::from("startup")]));
+        assert_eq!(report.provenance(&character::RYU, "5LP"), Provenance::Added);
+        assert_eq!(report.provenance(&character::RYU, "5LP(broken)"), Provenance::Hidden);
+        assert_eq!(report.provenance(&character::RYU, "2MK"), Provenance::Wiki);
+    }
+}
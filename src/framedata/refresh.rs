@@ -0,0 +1,186 @@
+use std::ops::Range;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+
+#[cfg(feature = "async")]
+use crate::character::CharacterId;
+#[cfg(feature = "async")]
+use crate::error::SF6Error;
+use crate::framedata::{CharacterFrameData, FrameData};
+#[cfg(feature = "async")]
+use crate::framedata::{LoadReport, Loader};
+
+/// Controls how often [`watch`] re-scrapes. Many bots are built on this crate; if they all refresh
+/// on the same fixed interval they synchronize into bursts against the wiki after every patch.
+/// [`RefreshProfile::polite`] spreads them out.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefreshProfile {
+    /// The nominal time between refreshes
+    pub interval: Duration,
+    /// How far (as a fraction of `interval`) each refresh is randomly moved earlier or later
+    pub jitter: f32,
+    /// Preferred UTC hours to refresh in. A refresh that would land outside this window is
+    /// pushed to a random point inside the next one.
+    pub off_peak_hours_utc: Option<Range<u8>>,
+    /// Whether the loader should send conditional requests, see [`Loader::conditional_requests`]
+    pub conditional_requests: bool,
+}
+
+impl RefreshProfile {
+    /// Refreshes exactly every `interval`, with no jitter or scheduling preferences
+    pub fn fixed(interval: Duration) -> Self {
+        RefreshProfile {
+            interval,
+            jitter: 0.0,
+            off_peak_hours_utc: None,
+            conditional_requests: false,
+        }
+    }
+
+    /// A considerate default: roughly every 12 hours with ±25% jitter, during the hours the wiki
+    /// sees the least traffic (08:00-14:00 UTC, night in the Americas), using conditional requests
+    pub fn polite() -> Self {
+        RefreshProfile {
+            interval: Duration::from_secs(12 * 60 * 60),
+            jitter: 0.25,
+            off_peak_hours_utc: Some(8..14),
+            conditional_requests: true,
+        }
+    }
+
+    /// Computes how long to wait before the next refresh, starting from `now`
+    pub fn next_delay(&self, now: SystemTime) -> Duration {
+        let mut rng = rand::thread_rng();
+        let jitter = self.jitter.clamp(0.0, 1.0) as f64;
+        let factor = if jitter > 0.0 { rng.gen_range(1.0 - jitter..=1.0 + jitter) } else { 1.0 };
+        let delay = self.interval.mul_f64(factor);
+        let Some(window) = self.off_peak_hours_utc.as_ref().filter(|w| w.start < w.end && w.end <= 24) else {
+            return delay;
+        };
+        let target = now + delay;
+        let seconds = target.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let hour = ((seconds / 3600) % 24) as u8;
+        if window.contains(&hour) {
+            return delay;
+        }
+        let hours_until_window = (window.start as u64 + 24 - hour as u64) % 24;
+        let window_start = seconds - seconds % 3600 + hours_until_window * 3600;
+        let window_len = (window.end - window.start) as u64 * 3600;
+        let at = window_start + rng.gen_range(0..window_len);
+        let now_seconds = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Duration::from_secs(at.saturating_sub(now_seconds))
+    }
+}
+
+impl Default for RefreshProfile {
+    fn default() -> Self {
+        RefreshProfile::polite()
+    }
+}
+
+/// Loads all frame data with `loader` and hands it to `on_refresh`, then keeps re-loading on the
+/// schedule described by `profile` until `on_refresh` returns `false`.
+#[cfg(feature = "async")]
+pub async fn watch<F>(loader: Loader, profile: RefreshProfile, mut on_refresh: F)
+where
+    F: FnMut(LoadReport) -> bool,
+{
+    let loader = if profile.conditional_requests { loader.conditional_requests(true) } else { loader };
+    loop {
+        if !on_refresh(loader.load_all().await) {
+            return;
+        }
+        tokio::time::sleep(profile.next_delay(SystemTime::now())).await;
+    }
+}
+
+#[cfg(feature = "async")]
+impl FrameData {
+    /// Re-scrapes only `character_id`'s pages and swaps its entry in place, e.g. after a balance
+    /// patch touched one character. The old entry is kept if the load fails.
+    pub async fn refresh_character(&mut self, character_id: &CharacterId) -> Result<(), SF6Error> {
+        self.refresh_character_with(&Loader::default(), character_id).await
+    }
+
+    /// Like [`FrameData::refresh_character`], loading with `loader`
+    pub async fn refresh_character_with(&mut self, loader: &Loader, character_id: &CharacterId) -> Result<(), SF6Error> {
+        self.replace_character(loader.load(character_id).await?);
+        Ok(())
+    }
+
+    /// Re-scrapes every character and swaps each entry that loaded in place. Characters that
+    /// fail keep their old entry and are returned with why they failed.
+    pub async fn refresh_all(&mut self) -> Vec<(CharacterId, SF6Error)> {
+        self.refresh_all_with(&Loader::default()).await
+    }
+
+    /// Like [`FrameData::refresh_all`], loading with `loader`
+    pub async fn refresh_all_with(&mut self, loader: &Loader) -> Vec<(CharacterId, SF6Error)> {
+        let report = loader.load_all().await;
+        for character_frame_data in report.data.character_frame_data {
+            self.replace_character(character_frame_data);
+        }
+        report.failures
+    }
+}
+
+impl FrameData {
+    /// Swaps the entry for `character_frame_data`'s character in place, or adds it if there is
+    /// none, returning the entry it replaced
+    pub fn replace_character(&mut self, character_frame_data: CharacterFrameData) -> Option<CharacterFrameData> {
+        let existing = self.character_frame_data.iter_mut()
+            .find(|c| c.character_id == character_frame_data.character_id);
+        match existing {
+            Some(existing) => Some(std::mem::replace(existing, character_frame_data)),
+            None => {
+                self.character_frame_data.push(character_frame_data);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::{self, CharacterId};
+    use crate::testing;
+
+    #[test]
+    fn jitters_within_bounds() {
+        let profile = RefreshProfile {
+            off_peak_hours_utc: None,
+            ..RefreshProfile::polite()
+        };
+        for _ in 0..100 {
+            let delay = profile.next_delay(SystemTime::now());
+            assert!(delay >= Duration::from_secs(9 * 60 * 60) && delay <= Duration::from_secs(15 * 60 * 60));
+        }
+    }
+
+    #[test]
+    fn replaces_characters_in_place() {
+        let character = |character_id: &CharacterId, identifier: &str| testing::character_frame_data(character_id, vec![crate::framedata::Move { identifier: identifier.to_string(), ..Default::default() }]);
+        let mut data = FrameData {
+            character_frame_data: vec![character(&character::RYU, "5LP"), character(&character::KEN, "5LP")],
+        };
+        let old = data.replace_character(character(&character::RYU, "5MP")).unwrap();
+        assert_eq!(old.moves[0].identifier, "5LP");
+        assert_eq!(data.character_frame_data[0].moves[0].identifier, "5MP");
+        assert!(data.replace_character(character(&character::JP, "5LP")).is_none());
+        assert_eq!(data.character_frame_data.len(), 3);
+    }
+
+    #[test]
+    fn schedules_into_off_peak_window() {
+        let profile = RefreshProfile::polite();
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        for _ in 0..100 {
+            let at = now + profile.next_delay(now);
+            let hour = (at.duration_since(UNIX_EPOCH).unwrap().as_secs() / 3600) % 24;
+            assert!((8..14).contains(&hour), "refresh scheduled at hour {}", hour);
+        }
+    }
+}
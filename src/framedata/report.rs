@@ -0,0 +1,76 @@
+use crate::character::CharacterId;
+use crate::error::SF6Error;
+use crate::framedata::{DataStatus, FrameData};
+
+/// The result of loading every character, see [`load_all`](super::load_all). Characters that
+/// failed are left out of `data` and listed in `failures`, so they can be retried individually.
+/// Characters that loaded but whose pages are still being filled in are listed by
+/// [`LoadReport::partial_characters`].
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    pub data: FrameData,
+    pub failures: Vec<(CharacterId, SF6Error)>,
+}
+
+impl LoadReport {
+    /// Whether every character loaded
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The characters that failed to load
+    pub fn failed_characters(&self) -> impl Iterator<Item = &CharacterId> {
+        self.failures.iter().map(|(character_id, _)| character_id)
+    }
+
+    /// How usable each loaded character's data is, see [`CharacterFrameData::status`](super::CharacterFrameData::status)
+    pub fn statuses(&self) -> impl Iterator<Item = (&CharacterId, DataStatus)> {
+        self.data.character_frame_data.iter().map(|c| (&c.character_id, c.status()))
+    }
+
+    /// The loaded characters whose data isn't [`DataStatus::Complete`] yet, e.g. a character
+    /// released days ago
+    pub fn partial_characters(&self) -> impl Iterator<Item = &CharacterId> {
+        self.statuses().filter(|(_, status)| !status.is_complete()).map(|(character_id, _)| character_id)
+    }
+
+    /// The loaded data if every character loaded, otherwise the first failure's error
+    pub fn into_result(self) -> Result<FrameData, SF6Error> {
+        match self.failures.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(self.data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::{parse_character_pages, Move};
+    use crate::testing;
+
+    #[test]
+    fn reports_failed_characters() {
+        let error = SF6Error::Http { url: character::RYU.frame_data_url(), status: 503 };
+        let report = LoadReport {
+            data: FrameData { character_frame_data: Vec::new() },
+            failures: vec![(character::RYU.clone(), error.clone())],
+        };
+        assert!(!report.is_complete());
+        assert_eq!(report.failed_characters().map(|c| c.id.as_ref()).collect::<Vec<&str>>(), vec!["ryu"]);
+        assert_eq!(report.into_result().unwrap_err(), error);
+    }
+
+    #[test]
+    fn reports_partial_characters() {
+        let page = testing::frame_data_page(&[Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() }]);
+        let report = LoadReport {
+            data: FrameData { character_frame_data: vec![parse_character_pages(&character::MBISON, &page, None)] },
+            failures: Vec::new(),
+        };
+        assert!(report.is_complete());
+        assert!(matches!(report.statuses().next(), Some((_, DataStatus::PartialData { moves: 1, .. }))));
+        assert_eq!(report.partial_characters().collect::<Vec<_>>(), vec![&character::MBISON]);
+    }
+}
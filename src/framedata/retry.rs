@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// How a [`Loader`](super::Loader) retries page requests that fail transiently: connection
+/// errors, timeouts, `429 Too Many Requests` and 5xx responses. Other failures like a 404 are
+/// returned immediately.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first. `1` disables retrying.
+    pub max_attempts: u32,
+    /// The wait before the first retry, doubled for every retry after it
+    pub initial_backoff: Duration,
+    /// The longest wait between attempts
+    pub max_backoff: Duration,
+    /// How far (as a fraction of the backoff) each wait is randomly shortened or lengthened, so
+    /// concurrent requests don't retry in lockstep
+    pub jitter: f32,
+}
+
+impl RetryPolicy {
+    /// A policy that makes a single attempt
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// How long to wait before retry number `retry` (starting at 1)
+    pub fn backoff(&self, retry: u32, rng: &mut impl Rng) -> Duration {
+        let exponential = self.initial_backoff.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let backoff = exponential.min(self.max_backoff);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+        backoff.mul_f32(rng.gen_range(1.0 - jitter..=1.0 + jitter))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            jitter: 0.2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn backs_off_exponentially() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let policy = RetryPolicy { jitter: 0.0, ..RetryPolicy::default() };
+        assert_eq!(policy.backoff(1, &mut rng), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3, &mut rng), Duration::from_secs(2));
+        assert_eq!(policy.backoff(40, &mut rng), Duration::from_secs(10));
+        let jittered = RetryPolicy::default().backoff(2, &mut rng);
+        assert!(jittered >= Duration::from_millis(800) && jittered <= Duration::from_millis(1200));
+    }
+}
@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use crate::framedata::version::GameVersion;
+use crate::framedata::{CharacterFrameData, FrameData};
+
+/// How often [`SnapshotWatcher`] checks for changes unless configured otherwise
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Why a snapshot couldn't be read
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    /// The file at the path isn't valid frame data JSON
+    Parse(PathBuf, serde_json::Error),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "Could not read snapshot: {}", e),
+            SnapshotError::Parse(path, e) => write!(f, "Invalid snapshot {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+/// Reads a snapshot published by a separate scraping job. `path` is either a single [`FrameData`]
+/// JSON file, as written by `sf6rs-cli export`, or a directory of `<character id>.json` files, as
+/// written by [`CachedLoader`](super::cache::CachedLoader).
+pub fn read_snapshot(path: &Path) -> Result<FrameData, SnapshotError> {
+    if !path.is_dir() {
+        return parse(path);
+    }
+    let mut character_frame_data = Vec::new();
+    for file in json_files(path)? {
+        character_frame_data.push(parse::<CharacterFrameData>(&file)?);
+    }
+    character_frame_data.sort_by(|a, b| a.character_id.id.cmp(&b.character_id.id));
+    Ok(FrameData {
+        character_frame_data
+    })
+}
+
+/// Reads the snapshot for `version` from `dir`, laid out as `<version key>/<character id>.json` as
+/// written by a [`CachedLoader`](super::cache::CachedLoader) with a
+/// [`version`](super::cache::CachedLoader::version)
+pub fn read_versioned_snapshot(dir: &Path, version: &GameVersion) -> Result<FrameData, SnapshotError> {
+    read_snapshot(&dir.join(version.key()))
+}
+
+/// The frame data currently being served, shared between request handlers and a
+/// [`SnapshotWatcher`]. A reload swaps the whole dataset at once, so readers see either the old
+/// snapshot or the new one, never a mix of both.
+#[derive(Debug, Clone)]
+pub struct SharedFrameData {
+    current: Arc<RwLock<Arc<FrameData>>>,
+}
+
+impl SharedFrameData {
+    pub fn new(frame_data: FrameData) -> Self {
+        SharedFrameData {
+            current: Arc::new(RwLock::new(Arc::new(frame_data))),
+        }
+    }
+
+    /// The current snapshot. It stays valid, and unchanged, for as long as it is held, even if a
+    /// reload happens meanwhile.
+    pub fn current(&self) -> Arc<FrameData> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replaces the current snapshot
+    pub fn replace(&self, frame_data: FrameData) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(frame_data);
+    }
+}
+
+/// Reloads a [`SharedFrameData`] whenever the snapshot at a path changes, so new data can be
+/// published without restarting the process serving it. Changes are detected by polling
+/// modification times; a snapshot that fails to read leaves the current data in place.
+/// ```no_run
+/// # async fn run() {
+/// use sf6rs::framedata::snapshot::{read_snapshot, SharedFrameData, SnapshotWatcher};
+///
+/// let data = SharedFrameData::new(read_snapshot("snapshot.json".as_ref()).unwrap());
+/// let watcher = SnapshotWatcher::new("snapshot.json", data.clone());
+/// tokio::spawn(watcher.watch(|result| {
+///     if let Err(e) = result {
+///         eprintln!("{}", e);
+///     }
+///     true
+/// }));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotWatcher {
+    path: PathBuf,
+    shared: SharedFrameData,
+    poll_interval: Duration,
+    last_modified: Option<SystemTime>,
+}
+
+impl SnapshotWatcher {
+    /// Watches `path` for changes after the snapshot `shared` currently holds, which is assumed
+    /// to have been read from it
+    pub fn new(path: impl Into<PathBuf>, shared: SharedFrameData) -> Self {
+        let path = path.into();
+        let last_modified = last_modified(&path).ok();
+        SnapshotWatcher {
+            path,
+            shared,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_modified,
+        }
+    }
+
+    /// Watches the snapshot for `version` in `dir`, see [`read_versioned_snapshot`]
+    pub fn versioned(dir: &Path, version: &GameVersion, shared: SharedFrameData) -> Self {
+        SnapshotWatcher::new(dir.join(version.key()), shared)
+    }
+
+    /// How often to check the snapshot for changes
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Reloads the snapshot if it changed since the last check, returning whether it did. An
+    /// unreadable snapshot is retried on the next check.
+    pub fn check(&mut self) -> Result<bool, SnapshotError> {
+        let modified = last_modified(&self.path)?;
+        if self.last_modified.is_some_and(|last| last >= modified) {
+            return Ok(false);
+        }
+        let frame_data = read_snapshot(&self.path)?;
+        self.shared.replace(frame_data);
+        self.last_modified = Some(modified);
+        Ok(true)
+    }
+
+    /// Checks for changes every poll interval, handing the result of each reload (or failed
+    /// attempt) to `on_reload` until it returns `false`. Without the `async` feature, call
+    /// [`SnapshotWatcher::check`] on a schedule of your own instead.
+    #[cfg(feature = "async")]
+    pub async fn watch<F>(mut self, mut on_reload: F)
+    where
+        F: FnMut(Result<(), SnapshotError>) -> bool,
+    {
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+            let result = match self.check() {
+                Ok(false) => continue,
+                Ok(true) => Ok(()),
+                Err(e) => Err(e),
+            };
+            if !on_reload(result) {
+                return;
+            }
+        }
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, SnapshotError> {
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| SnapshotError::Parse(path.to_path_buf(), e))
+}
+
+fn json_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "json") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// The latest modification time of the snapshot file or any file in the snapshot directory
+fn last_modified(path: &Path) -> io::Result<SystemTime> {
+    let mut modified = fs::metadata(path)?.modified()?;
+    if path.is_dir() {
+        for file in json_files(path)? {
+            modified = modified.max(fs::metadata(file)?.modified()?);
+        }
+    }
+    Ok(modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    fn empty(character_id: &character::CharacterId) -> CharacterFrameData {
+        testing::character_frame_data(character_id, Vec::new())
+    }
+
+    /// Pushes a file's modification time forward so changes register on coarse filesystem clocks
+    fn touch(path: &Path, seconds: u64) {
+        fs::File::options().append(true).open(path).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(seconds)).unwrap();
+    }
+
+    #[test]
+    fn reloads_changed_snapshots() {
+        let dir = TempDir::new("sf6rs-snapshot-test");
+        fs::create_dir_all(&dir).unwrap();
+        let write = |c: &CharacterFrameData| fs::write(dir.join(format!("{}.json", c.character_id.id)), serde_json::to_string(c).unwrap()).unwrap();
+        write(&empty(&character::RYU));
+
+        let shared = SharedFrameData::new(read_snapshot(&dir).unwrap());
+        let mut watcher = SnapshotWatcher::new(dir.to_path_buf(), shared.clone());
+        assert!(!watcher.check().unwrap());
+        let before = shared.current();
+
+        write(&empty(&character::KEN));
+        touch(&dir.join("ken.json"), 2);
+        assert!(watcher.check().unwrap());
+        assert_eq!(before.character_frame_data.len(), 1);
+        assert_eq!(shared.current().character_frame_data.iter().map(|c| c.character_id.id.as_ref()).collect::<Vec<_>>(), vec!["ken", "ryu"]);
+
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        touch(&dir.join("broken.json"), 4);
+        assert!(matches!(watcher.check(), Err(SnapshotError::Parse(..))));
+        assert_eq!(shared.current().character_frame_data.len(), 2);
+    }
+}
@@ -0,0 +1,194 @@
+use scraper::{ElementRef, Html, Selector};
+
+use crate::character::CharacterId;
+use crate::framedata::{is_placeholder, FrameData};
+use crate::LazyLock;
+
+/// A character's movement and defensive stats from the stats table of their frame data page.
+/// Values are kept as the wiki writes them, like [`Move`](super::Move) fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CharacterStats {
+    pub vitality: String,
+    pub forward_walk_speed: String,
+    pub back_walk_speed: String,
+    /// Total frames of a forward dash
+    pub forward_dash: String,
+    /// Total frames of a back dash
+    pub back_dash: String,
+    pub forward_dash_distance: String,
+    pub back_dash_distance: String,
+    /// Frames before a jump leaves the ground
+    pub jump_startup: String,
+    /// Frames a jump is airborne
+    pub jump_active: String,
+    /// Landing recovery frames of a jump
+    pub jump_recovery: String,
+    pub throw_range: String,
+}
+
+/// Each field, with the words a stats table label has to contain to be read into it. Labels are
+/// matched in this order, so the more specific dash distances come before dash frames.
+const LABELS: [(&str, &[&str]); 13] = [
+    ("vitality", &["vitality"]),
+    ("vitality", &["health"]),
+    ("forward_walk_speed", &["forward", "walk"]),
+    ("back_walk_speed", &["back", "walk"]),
+    ("forward_dash_distance", &["forward", "dash", "distance"]),
+    ("back_dash_distance", &["back", "dash", "distance"]),
+    ("forward_dash", &["forward", "dash"]),
+    ("back_dash", &["back", "dash"]),
+    ("jump_startup", &["jump", "startup"]),
+    ("jump_startup", &["prejump"]),
+    ("jump_active", &["jump", "duration"]),
+    ("jump_recovery", &["jump", "recovery"]),
+    ("throw_range", &["throw", "range"]),
+];
+
+impl CharacterStats {
+    pub const FIELDS: [&'static str; 11] = [
+        "vitality", "forward_walk_speed", "back_walk_speed", "forward_dash", "back_dash",
+        "forward_dash_distance", "back_dash_distance", "jump_startup", "jump_active", "jump_recovery",
+        "throw_range",
+    ];
+
+    /// A stat by its field name, see [`CharacterStats::FIELDS`]
+    pub fn get(&self, field: &str) -> Option<&str> {
+        let value = match field {
+            "vitality" => &self.vitality,
+            "forward_walk_speed" => &self.forward_walk_speed,
+            "back_walk_speed" => &self.back_walk_speed,
+            "forward_dash" => &self.forward_dash,
+            "back_dash" => &self.back_dash,
+            "forward_dash_distance" => &self.forward_dash_distance,
+            "back_dash_distance" => &self.back_dash_distance,
+            "jump_startup" => &self.jump_startup,
+            "jump_active" => &self.jump_active,
+            "jump_recovery" => &self.jump_recovery,
+            "throw_range" => &self.throw_range,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn get_mut(&mut self, field: &str) -> Option<&mut String> {
+        let value = match field {
+            "vitality" => &mut self.vitality,
+            "forward_walk_speed" => &mut self.forward_walk_speed,
+            "back_walk_speed" => &mut self.back_walk_speed,
+            "forward_dash" => &mut self.forward_dash,
+            "back_dash" => &mut self.back_dash,
+            "forward_dash_distance" => &mut self.forward_dash_distance,
+            "back_dash_distance" => &mut self.back_dash_distance,
+            "jump_startup" => &mut self.jump_startup,
+            "jump_active" => &mut self.jump_active,
+            "jump_recovery" => &mut self.jump_recovery,
+            "throw_range" => &mut self.throw_range,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// A stat's leading number, e.g. `0.032` for a walk speed of `0.032 px/f`
+    pub fn number(&self, field: &str) -> Option<f64> {
+        let value = self.get(field)?.trim();
+        let end = value.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-')).unwrap_or(value.len());
+        value[..end].parse().ok()
+    }
+
+    /// Records `value` under the field `label` describes, if it describes one that is still empty
+    fn set_labeled(&mut self, label: &str, value: &str) -> bool {
+        let label = label.to_lowercase();
+        let Some((field, _)) = LABELS.iter().find(|(_, words)| words.iter().all(|w| label.contains(w))) else {
+            return false;
+        };
+        let Some(slot) = self.get_mut(field).filter(|slot| slot.is_empty()) else {
+            return false;
+        };
+        if is_placeholder(value) {
+            return false;
+        }
+        *slot = value.trim().to_string();
+        true
+    }
+}
+
+static TABLE_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("table").unwrap());
+static ROW_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr").unwrap());
+static HEADER_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("th").unwrap());
+static CELL_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("td").unwrap());
+
+/// Reads a character's stats from their frame data page, `None` if it has no stats table. Both
+/// layouts the wiki uses are understood: a label and its value side by side in each row, or a
+/// row of labels above a row of values.
+pub(crate) fn parse_stats(html: &Html) -> Option<CharacterStats> {
+    let mut stats = CharacterStats::default();
+    let mut found = false;
+    for table in html.select(&TABLE_SELECTOR) {
+        let rows = table.select(&ROW_SELECTOR)
+            .map(|row| (texts(row, &HEADER_SELECTOR), texts(row, &CELL_SELECTOR)))
+            .collect::<Vec<(Vec<String>, Vec<String>)>>();
+        for (index, (headers, cells)) in rows.iter().enumerate() {
+            if let ([label], [value, ..]) = (headers.as_slice(), cells.as_slice()) {
+                found |= stats.set_labeled(label, value);
+            } else if headers.len() > 1 && cells.is_empty() {
+                let Some((_, values)) = rows.get(index + 1) else {
+                    continue;
+                };
+                for (label, value) in headers.iter().zip(values) {
+                    found |= stats.set_labeled(label, value);
+                }
+            }
+        }
+    }
+    found.then_some(stats)
+}
+
+fn texts(row: ElementRef, selector: &Selector) -> Vec<String> {
+    row.select(selector)
+        .map(|cell| cell.text().collect::<String>().trim().to_string())
+        .collect()
+}
+
+impl FrameData {
+    /// Every loaded character with a numeric `field` stat (see [`CharacterStats::FIELDS`]),
+    /// highest first, e.g. the cast's walk speeds
+    pub fn rank_stats(&self, field: &str) -> Vec<(&CharacterId, f64)> {
+        let mut ranked = self.character_frame_data.iter()
+            .filter_map(|c| c.stats.as_ref().and_then(|s| s.number(field)).map(|n| (&c.character_id, n)))
+            .collect::<Vec<(&CharacterId, f64)>>();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stats_tables() {
+        let side_by_side = Html::parse_document("<table>\
+            <tr><th>Vitality</th><td>10000</td></tr>\
+            <tr><th>Forward Walk Speed</th><td>0.032</td></tr>\
+            <tr><th>Forward Dash</th><td>19</td></tr>\
+            <tr><th>Forward Dash Distance</th><td>1.203</td></tr>\
+            <tr><th>Throw Range</th><td>-</td></tr></table>");
+        let stats = parse_stats(&side_by_side).unwrap();
+        assert_eq!(stats.vitality, "10000");
+        assert_eq!(stats.forward_dash, "19");
+        assert_eq!(stats.forward_dash_distance, "1.203");
+        assert_eq!(stats.number("forward_walk_speed"), Some(0.032));
+        assert_eq!(stats.throw_range, "");
+
+        let stacked = Html::parse_document("<table>\
+            <tr><th>Health</th><th>Back Walk Speed</th><th>Prejump</th><th>Jump Duration</th></tr>\
+            <tr><td>10000</td><td>0.025</td><td>4</td><td>38</td></tr></table>");
+        let stats = parse_stats(&stacked).unwrap();
+        assert_eq!((stats.back_walk_speed.as_str(), stats.jump_startup.as_str(), stats.jump_active.as_str()), ("0.025", "4", "38"));
+
+        assert_eq!(parse_stats(&Html::parse_document("<table><tr><th>Startup</th><td>4</td></tr></table>")), None);
+        assert!(CharacterStats::FIELDS.iter().all(|f| stats.get(f).is_some()));
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use crate::character::CharacterId;
+use crate::framedata::dropped::DroppedSection;
+use crate::framedata::version::GameVersion;
+
+/// Timing and outcome of loading a single character, see [`Loader::load_all_timed`](super::Loader::load_all_timed)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterTelemetry {
+    pub character_id: CharacterId,
+    /// Time spent waiting on the network for both of the character's pages
+    pub fetch: Duration,
+    /// Time spent parsing both pages
+    pub parse: Duration,
+    /// Size of both page bodies
+    pub bytes: usize,
+    /// How many requests had to be retried
+    pub retries: u32,
+    /// Why the character failed to load, if it did
+    pub error: Option<String>,
+    /// Move sections of the frame data page that were skipped while parsing, see
+    /// [`dropped_sections`](super::dropped::dropped_sections)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dropped_sections: Vec<DroppedSection>,
+    /// The game version the frame data page states it is for, see
+    /// [`detect_game_version`](super::version::detect_game_version)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub game_version: Option<GameVersion>,
+}
+
+impl CharacterTelemetry {
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn new(character_id: &CharacterId) -> Self {
+        CharacterTelemetry {
+            character_id: character_id.clone(),
+            fetch: Duration::ZERO,
+            parse: Duration::ZERO,
+            bytes: 0,
+            retries: 0,
+            error: None,
+            dropped_sections: Vec::new(),
+            game_version: None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Timing and outcome of a whole [`Loader::load_all_timed`](super::Loader::load_all_timed) run.
+/// Useful for logging slow characters or noticing when the wiki starts throttling requests
+/// without enabling full tracing.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadTelemetry {
+    pub characters: Vec<CharacterTelemetry>,
+    /// Wall-clock time of the whole run. Characters load concurrently, so this is usually far
+    /// less than the sum of per-character times.
+    pub total: Duration,
+}
+
+impl LoadTelemetry {
+    /// The character that took longest to fetch and parse
+    pub fn slowest(&self) -> Option<&CharacterTelemetry> {
+        self.characters.iter().max_by_key(|c| c.fetch + c.parse)
+    }
+
+    /// Characters that failed to load
+    pub fn failures(&self) -> impl Iterator<Item = &CharacterTelemetry> {
+        self.characters.iter().filter(|c| c.is_failure())
+    }
+
+    /// Total bytes downloaded across all characters
+    pub fn bytes(&self) -> usize {
+        self.characters.iter().map(|c| c.bytes).sum()
+    }
+
+    /// Characters with move sections that were skipped while parsing
+    pub fn dropped_sections(&self) -> impl Iterator<Item = &CharacterTelemetry> {
+        self.characters.iter().filter(|c| !c.dropped_sections.is_empty())
+    }
+
+    /// Total retried requests across all characters
+    pub fn retries(&self) -> u32 {
+        self.characters.iter().map(|c| c.retries).sum()
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use crate::character::{self, CharacterRegistry};
+    use crate::framedata::loader::tests::{response, serve};
+    use crate::framedata::{Loader, Move, RetryPolicy};
+    use crate::testing;
+
+    #[tokio::test]
+    async fn times_characters() {
+        let page = testing::frame_data_page(&[Move { identifier: String::from("5LP"), ..Move::default() }]);
+        let (ryu_url, _) = serve(vec![response("200 OK", "", &page), response("200 OK", "", "<html></html>")]).await;
+        let (ken_url, _) = serve(vec![response("404 Not Found", "", ""), response("404 Not Found", "", "")]).await;
+        let mut registry = CharacterRegistry::empty();
+        registry.register(character::RYU.clone().with_frame_data_url(ryu_url.clone()).with_gif_data_url(ryu_url));
+        registry.register(character::KEN.clone().with_frame_data_url(ken_url.clone()).with_gif_data_url(ken_url));
+        let (report, telemetry) = Loader::new().retry_policy(RetryPolicy::none()).load_registry_timed(&registry).await;
+        assert_eq!(report.data.character_frame_data.len(), 1);
+        assert_eq!(telemetry.characters.len(), 2);
+        let failures = telemetry.failures().collect::<Vec<_>>();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].character_id.id, "ken");
+        assert_eq!(telemetry.bytes(), page.len() + "<html></html>".len());
+        assert_eq!(telemetry.retries(), 0);
+        assert!(telemetry.total >= telemetry.characters.iter().map(|c| c.fetch).max().unwrap());
+    }
+}
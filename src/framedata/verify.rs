@@ -0,0 +1,101 @@
+use crate::character::{self, CharacterId};
+use crate::format::signed_number;
+use crate::framedata::FrameData;
+
+/// A value the wiki is known to have for a move. A scrape that disagrees means the parser
+/// regressed or the page was vandalised; genuine balance patches should update the pin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedValue {
+    pub character_id: &'static CharacterId,
+    pub identifier: &'static str,
+    /// A [`Move`](super::Move) field name, e.g. `startup`
+    pub field: &'static str,
+    pub expected: &'static str,
+}
+
+impl PinnedValue {
+    pub const fn new(character_id: &'static CharacterId, identifier: &'static str, field: &'static str, expected: &'static str) -> Self {
+        PinnedValue {
+            character_id,
+            identifier,
+            field,
+            expected,
+        }
+    }
+
+    /// Whether `actual` agrees with the pinned value. Values are compared by their leading number
+    /// when both have one, so `+1` matches `1` and `4(5)` matches `4`.
+    fn matches(&self, actual: &str) -> bool {
+        match (signed_number(self.expected), signed_number(actual)) {
+            (Some(expected), Some(actual)) => expected == actual,
+            _ => self.expected.trim().eq_ignore_ascii_case(actual.trim()),
+        }
+    }
+}
+
+/// Well-known values that have been stable across patches, used by
+/// [`FrameData::verify_against_corpus`] when no corpus of its own is given
+pub static REGRESSION_CORPUS: [PinnedValue; 8] = [
+    PinnedValue::new(&character::RYU, "5LP", "startup", "4"),
+    PinnedValue::new(&character::RYU, "5MP", "startup", "6"),
+    PinnedValue::new(&character::RYU, "5MP", "block_advantage", "+1"),
+    PinnedValue::new(&character::RYU, "2MK", "startup", "8"),
+    PinnedValue::new(&character::RYU, "2MK", "guard", "L"),
+    PinnedValue::new(&character::RYU, "LPLK", "damage", "1200"),
+    PinnedValue::new(&character::KEN, "5LP", "startup", "4"),
+    PinnedValue::new(&character::LUKE, "5LP", "startup", "4"),
+];
+
+/// A scraped value that disagrees with its [`PinnedValue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deviation {
+    pub pinned: PinnedValue,
+    /// What was scraped instead, `None` if the move or field is missing entirely
+    pub actual: Option<String>,
+}
+
+impl FrameData {
+    /// Checks scraped data against `corpus` (or [`REGRESSION_CORPUS`] if `None`) and returns every
+    /// disagreement. Characters that aren't loaded are skipped, so partial loads only verify what
+    /// they have.
+    pub fn verify_against_corpus(&self, corpus: Option<&[PinnedValue]>) -> Vec<Deviation> {
+        corpus.unwrap_or(&REGRESSION_CORPUS).iter()
+            .filter(|pinned| self.find_character_frame_data(pinned.character_id).is_ok())
+            .filter_map(|pinned| {
+                let actual = self.find_move_character(pinned.character_id, pinned.identifier).ok()
+                    .and_then(|m| m.get(pinned.field));
+                match actual {
+                    Some(actual) if pinned.matches(actual) => None,
+                    actual => Some(Deviation {
+                        pinned: pinned.clone(),
+                        actual: actual.map(str::to_string),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framedata::Move;
+    use crate::testing;
+
+    #[test]
+    fn flags_deviations() {
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![
+                Move { identifier: String::from("5LP"), startup: String::from("4"), ..Move::default() },
+                Move { identifier: String::from("5MP"), startup: String::from("7"), block_advantage: String::from("1"), ..Move::default() },
+            ])],
+        };
+        let deviations = data.verify_against_corpus(Some(&REGRESSION_CORPUS[..3]));
+        assert_eq!(deviations, vec![Deviation {
+            pinned: REGRESSION_CORPUS[1].clone(),
+            actual: Some(String::from("7")),
+        }]);
+        // 2MK and the throw are missing from the fixture, and Ken and Luke aren't loaded
+        assert_eq!(data.verify_against_corpus(None).len(), 4);
+    }
+}
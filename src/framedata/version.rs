@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use regex::Regex;
+use scraper::{Html, Selector};
+
+use crate::LazyLock;
+
+static VERSION_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("[data-game-version], .game-version").unwrap());
+static VERSION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+(?:[./-]\d+)+").unwrap());
+
+/// A game version as written on the wiki, e.g. `2024.06.26` or `1.0800`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameVersion(pub String);
+
+impl GameVersion {
+    pub fn new(version: impl Into<String>) -> Self {
+        GameVersion(version.into())
+    }
+
+    /// A form of the version safe to use as a file or directory name
+    pub fn key(&self) -> String {
+        self.0.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' }).collect()
+    }
+}
+
+impl Display for GameVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Finds the game version a frame data page was last updated for, if the page states one in a
+/// version element, e.g. `<div data-game-version="1.0800">` or
+/// `<span class="game-version">Ver. 2024.06.26</span>`. Versions mentioned elsewhere, like a move's
+/// notes on what a patch changed, are ignored.
+pub fn detect_game_version(html: &str) -> Option<GameVersion> {
+    let html = Html::parse_document(html);
+    html.select(&VERSION_SELECTOR).find_map(|element| {
+        let text = match element.value().attr("data-game-version") {
+            Some(version) => version.to_string(),
+            None => element.text().collect::<String>(),
+        };
+        VERSION_REGEX.find(&text).map(|m| GameVersion::new(m.as_str()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_versions() {
+        assert_eq!(detect_game_version(r#"<p class="game-version">Frame data as of Ver. 2024.06.26</p>"#), Some(GameVersion::new("2024.06.26")));
+        assert_eq!(detect_game_version(r#"<div data-game-version="1.0800"></div>"#), Some(GameVersion::new("1.0800")));
+        assert_eq!(detect_game_version(r#"<span class="game-version">Patch: 2024-06-26</span>"#).unwrap().key(), "2024-06-26");
+        assert_eq!(detect_game_version("<p>Universal version of the move</p>"), None);
+    }
+
+    #[test]
+    fn ignores_versions_outside_the_version_element() {
+        let notes = "<table><tr><td>Ver. 1.0500: recovery 20 → 22</td></tr></table>";
+        assert_eq!(detect_game_version(notes), None);
+        let page = format!(r#"{}<div data-game-version="1.0800"></div>"#, notes);
+        assert_eq!(detect_game_version(&page), Some(GameVersion::new("1.0800")));
+    }
+}
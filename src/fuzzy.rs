@@ -0,0 +1,248 @@
+//! Forgiving move lookup for queries typed the way players talk about moves rather than the way
+//! the wiki names them: `qcf+k`, `2 3 6 K`, `cr.mk`, `fireball kick` or `dp`.
+//! ```
+//! # let data = sf6rs::framedata::FrameData { character_frame_data: Vec::new() };
+//! assert_eq!(sf6rs::fuzzy::normalize_input("qcf+k").as_deref(), Some("236K"));
+//! for found in data.find_move_fuzzy("ryu", "fireball kick").unwrap_or_default() {
+//!     println!("{} ({:.2})", found.move_data.identifier, found.score);
+//! }
+//! ```
+
+use crate::framedata::{CharacterFrameData, FrameData, Move, SF6FrameDataError};
+use crate::popularity::{PopularityProvider, POPULARITY_WEIGHT};
+
+/// Scores of the ways a query can match a move, from most to least certain
+const EXACT_SCORE: f32 = 1.0;
+const INPUT_SCORE: f32 = 0.95;
+const NAME_SCORE: f32 = 0.9;
+const ANY_BUTTON_SCORE: f32 = 0.8;
+const NAME_CONTAINS_SCORE: f32 = 0.7;
+const MOTION_SCORE: f32 = 0.6;
+/// The most a name sharing only some of the query's words can score
+const NAME_WORDS_SCORE: f32 = 0.5;
+
+/// Words for directions and motions, as numpad notation
+const MOTIONS: [(&str, &str); 29] = [
+    ("qcf", "236"), ("qcb", "214"), ("dp", "623"), ("rdp", "421"), ("hcf", "41236"), ("hcb", "63214"),
+    ("spd", "360"), ("fireball", "236"), ("hadoken", "236"), ("shoryu", "623"), ("shoryuken", "623"),
+    ("tatsu", "214"), ("cr", "2"), ("c", "2"), ("crouch", "2"), ("crouching", "2"), ("down", "2"),
+    ("st", "5"), ("s", "5"), ("stand", "5"), ("standing", "5"), ("f", "6"), ("fwd", "6"),
+    ("forward", "6"), ("b", "4"), ("back", "4"), ("df", "3"), ("db", "1"), ("n", "5"),
+];
+/// Words for a button's strength, as the first letter of its notation
+const STRENGTHS: [(&str, char); 6] = [
+    ("l", 'L'), ("light", 'L'), ("m", 'M'), ("medium", 'M'), ("h", 'H'), ("heavy", 'H'),
+];
+const BUTTONS: [&str; 12] = ["LP", "MP", "HP", "LK", "MK", "HK", "PPP", "KKK", "PP", "KK", "P", "K"];
+
+/// A move found by [`CharacterFrameData::find_move_fuzzy`]
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch<'a> {
+    pub move_data: &'a Move,
+    /// How well the move matched, from `1.0` for its exact identifier down towards `0.0`
+    pub score: f32,
+}
+
+/// A query or identifier read as numpad notation, e.g. `j.236HP`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Notation {
+    jump: bool,
+    motion: String,
+    /// `None` when only a motion was given, e.g. `qcf`
+    button: Option<String>,
+}
+
+impl Notation {
+    fn parse(text: &str) -> Option<Self> {
+        let mut jump = false;
+        let mut motion = String::new();
+        let mut strength = None;
+        let mut button = None;
+        let tokens = text.split(|c: char| c.is_whitespace() || matches!(c, '+' | '.' | ',' | '/'))
+            .filter(|token| !token.is_empty())
+            .map(str::to_ascii_lowercase);
+        for token in tokens {
+            if let Some((_, direction)) = MOTIONS.iter().find(|(word, _)| *word == token) {
+                motion.push_str(direction);
+                continue;
+            }
+            if matches!(token.as_str(), "j" | "jump" | "jumping" | "air") {
+                jump = true;
+                continue;
+            }
+            if let Some((_, letter)) = STRENGTHS.iter().find(|(word, _)| *word == token) {
+                strength = Some(*letter);
+                continue;
+            }
+            let letter = match token.as_str() {
+                "punch" => Some("P"),
+                "kick" => Some("K"),
+                _ => None,
+            };
+            if let Some(letter) = letter {
+                button = Some(strength.take().map(|s| format!("{}{}", s, letter)).unwrap_or_else(|| letter.to_string()));
+                continue;
+            }
+            // tokens like `236`, `2mk` or `mk`
+            let digits = token.chars().take_while(char::is_ascii_digit).count();
+            motion.push_str(&token[..digits]);
+            let mut rest = token[digits..].to_ascii_uppercase();
+            if rest.is_empty() {
+                continue;
+            }
+            if rest == "P" || rest == "K" {
+                if let Some(letter) = strength.take() {
+                    rest.insert(0, letter);
+                }
+            }
+            button = Some(BUTTONS.iter().find(|b| **b == rest)?.to_string());
+        }
+        if strength.is_some() || (motion.is_empty() && button.is_none()) {
+            return None;
+        }
+        if motion.is_empty() && !jump {
+            motion.push('5');
+        }
+        Some(Notation { jump, motion, button })
+    }
+
+    /// How well this query notation matches a move's notation
+    fn score(&self, identifier: &Notation) -> Option<f32> {
+        if self.jump != identifier.jump || self.motion != identifier.motion {
+            return None;
+        }
+        match (&self.button, &identifier.button) {
+            (Some(query), Some(button)) if query == button => Some(INPUT_SCORE),
+            // a bare `P` or `K` means any strength of it
+            (Some(query), Some(button)) if query.len() == 1 && button.len() == 2 && button.ends_with(query.as_str()) => Some(ANY_BUTTON_SCORE),
+            (None, _) => Some(MOTION_SCORE),
+            _ => None,
+        }
+    }
+
+    fn to_numpad(&self) -> String {
+        let jump = if self.jump { "j." } else { "" };
+        format!("{}{}{}", jump, self.motion, self.button.as_deref().unwrap_or_default())
+    }
+}
+
+/// Rewrites a query in numpad notation, e.g. `qcf+k` and `2 3 6 K` both become `236K` and
+/// `cr.mk` becomes `2MK`. `None` if the query isn't a recognisable input.
+pub fn normalize_input(query: &str) -> Option<String> {
+    Notation::parse(query).map(|notation| notation.to_numpad())
+}
+
+impl CharacterFrameData {
+    /// Every move `query` could mean, best first. Unlike [`FrameData::find_move`], which only
+    /// accepts a move's exact identifier, this understands motion names (`qcf`, `dp`), common
+    /// aliases (`tatsu`, `spd`), spaced out or stance-prefixed inputs (`2 3 6 K`, `cr.mk`) and
+    /// words of a move's name.
+    pub fn find_move_fuzzy(&self, query: &str) -> Vec<FuzzyMatch<'_>> {
+        let notation = Notation::parse(query);
+        let query_words = words(query);
+        let mut matches = self.moves.iter()
+            .filter_map(|move_data| {
+                let mut score = 0.0f32;
+                if move_data.identifier.eq_ignore_ascii_case(query.trim()) {
+                    score = EXACT_SCORE;
+                }
+                if let Some(found) = notation.as_ref().zip(Notation::parse(&move_data.identifier)).and_then(|(q, m)| q.score(&m)) {
+                    score = score.max(found);
+                }
+                score = score.max(name_score(&query_words, &move_data.name));
+                (score > 0.0).then_some(FuzzyMatch { move_data, score })
+            })
+            .collect::<Vec<FuzzyMatch>>();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches
+    }
+
+    /// Like [`CharacterFrameData::find_move_fuzzy`], with each match's score raised by up to
+    /// [`POPULARITY_WEIGHT`] by how commonly `popularity` says the move is asked about, so `kick`
+    /// suggests 2MK before a taunt
+    pub fn find_move_fuzzy_with(&self, query: &str, popularity: &impl PopularityProvider) -> Vec<FuzzyMatch<'_>> {
+        let mut matches = self.find_move_fuzzy(query);
+        for found in &mut matches {
+            found.score += POPULARITY_WEIGHT * popularity.popularity(&self.character_id, found.move_data).clamp(0.0, 1.0);
+        }
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches
+    }
+}
+
+impl FrameData {
+    /// Finds moves for the character matching `character_query`, see
+    /// [`CharacterFrameData::find_move_fuzzy`]
+    pub fn find_move_fuzzy(&self, character_query: &str, move_query: &str) -> Result<Vec<FuzzyMatch<'_>>, SF6FrameDataError> {
+        Ok(self.find_character_frame_data_query(character_query)?.find_move_fuzzy(move_query))
+    }
+}
+
+fn name_score(query_words: &[String], name: &str) -> f32 {
+    if query_words.is_empty() {
+        return 0.0;
+    }
+    let name_words = words(name);
+    if name_words == query_words {
+        return NAME_SCORE;
+    }
+    if name_words.windows(query_words.len()).any(|window| window == query_words) {
+        return NAME_CONTAINS_SCORE;
+    }
+    let shared = query_words.iter().filter(|w| name_words.contains(w)).count();
+    NAME_WORDS_SCORE * shared as f32 / query_words.len() as f32
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize_input("qcf+k").as_deref(), Some("236K"));
+        assert_eq!(normalize_input("2 3 6 K").as_deref(), Some("236K"));
+        assert_eq!(normalize_input("cr.mk").as_deref(), Some("2MK"));
+        assert_eq!(normalize_input("fireball kick").as_deref(), Some("236K"));
+        assert_eq!(normalize_input("dp+hp").as_deref(), Some("623HP"));
+        assert_eq!(normalize_input("j.hp").as_deref(), Some("j.HP"));
+        assert_eq!(normalize_input("light punch").as_deref(), Some("5LP"));
+        assert_eq!(normalize_input("st l p").as_deref(), Some("5LP"));
+        assert_eq!(normalize_input("spd").as_deref(), Some("360"));
+        assert_eq!(normalize_input("denjin renki"), None);
+    }
+
+    #[test]
+    fn ranks_fuzzy_matches() {
+        let named = |identifier: &str, name: &str| Move {
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+            ..Move::default()
+        };
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            named("2MK", "Crouching Medium Kick"),
+            named("236LP", "Hadoken"),
+            named("214LK", "Tatsumaki Senpu-kyaku"),
+            named("623HP", "Shoryuken"),
+        ]);
+        let identifiers = |query| ryu.find_move_fuzzy(query).iter().map(|m| m.move_data.identifier.clone()).collect::<Vec<String>>();
+        assert_eq!(identifiers("214K"), vec!["214LK"]);
+        assert_eq!(identifiers("qcb+k"), vec!["214LK"]);
+        assert_eq!(identifiers("l p"), Vec::<String>::new());
+        assert_eq!(identifiers("cr.mk"), vec!["2MK"]);
+        assert_eq!(identifiers("DP"), vec!["623HP"]);
+        assert_eq!(identifiers("tatsu"), vec!["214LK"]);
+        assert_eq!(identifiers("shoryuken"), vec!["623HP"]);
+        assert_eq!(identifiers("fireball"), vec!["236LP"]);
+        assert_eq!(ryu.find_move_fuzzy("2mk")[0].score, EXACT_SCORE);
+        assert!(ryu.find_move_fuzzy("taunt").is_empty());
+    }
+}
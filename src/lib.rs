@@ -1,35 +1,56 @@
-use crate::framedata::load_all;
-
+pub mod error;
 pub mod framedata;
 pub mod character;
+pub mod format;
+pub mod export;
+pub mod view;
+pub mod tags;
+pub mod parsed;
+pub mod sort;
+pub mod search;
+pub mod disambiguation;
+pub mod fuzzy;
+pub mod aliases;
+pub mod popularity;
+pub mod analysis;
+pub mod system;
+pub mod compat;
+pub mod viz;
+pub mod query;
+pub mod drills;
+pub mod diff;
+mod text;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
 
-pub struct LazyLock<T, F = fn() -> T> {
-    data: std::sync::OnceLock<T>,
-    f: F,
-}
-
-impl<T, F> LazyLock<T, F> {
-    pub const fn new(f: F) -> LazyLock<T, F> {
-        Self {
-            data: std::sync::OnceLock::new(),
-            f,
-        }
-    }
-}
-
-impl<T> std::ops::Deref for LazyLock<T> {
-    type Target = T;
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
-    fn deref(&self) -> &Self::Target {
-        self.data.get_or_init(self.f)
-    }
-}
+/// [`std::sync::LazyLock`], which the crate's statics like
+/// [`CHARACTERS`](character::CHARACTERS) are built with
+/// ```
+/// let characters: &std::sync::LazyLock<Vec<&sf6rs::character::CharacterId>> = &sf6rs::character::CHARACTERS;
+/// static NAMES: sf6rs::LazyLock<Vec<&str>> = std::sync::LazyLock::new(|| vec!["Ryu"]);
+/// assert_eq!((characters.is_empty(), NAMES.len()), (false, 1));
+/// ```
+pub use std::sync::LazyLock;
 
+#[cfg(feature = "async")]
 #[tokio::test]
 async fn test() {
-    let data = load_all().await;
+    let data = framedata::load_all().await.data;
     let x = data.find_character_frame_data(&character::MBISON).unwrap();
     println!("{:?}", data.find_move("mbison", "5lp"));
-    println!("{:?}", x.gifs.iter().next().unwrap());
-    println!("{:?}", x.moves.iter().next().unwrap());
+    println!("{:?}", x.gifs.first().unwrap());
+    println!("{:?}", x.moves.first().unwrap());
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+//! Kotlin and Swift bindings generated by UniFFI, enabled with the `uniffi` feature. Mobile apps
+//! ship a JSON snapshot (or download one) and use the same query logic as every other consumer:
+//! ```sh
+//! cargo rustc --release --features uniffi --crate-type cdylib
+//! cargo run --features uniffi --bin uniffi-bindgen -- generate --library target/release/libsf6rs.so --language kotlin --out-dir out
+//! ```
+//! Moves are exposed as [`MoveView`] records.
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::framedata::FrameData;
+use crate::view::{CharacterView, MoveView};
+
+/// Errors surfaced to Kotlin/Swift as exceptions
+#[derive(Debug, uniffi::Error)]
+pub enum MobileError {
+    InvalidSnapshot { reason: String },
+    UnknownCharacter,
+}
+
+impl Display for MobileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MobileError::InvalidSnapshot { reason } => write!(f, "Invalid snapshot: {}", reason),
+            MobileError::UnknownCharacter => write!(f, "Unknown character"),
+        }
+    }
+}
+
+impl std::error::Error for MobileError {}
+
+/// A loaded frame data snapshot
+#[derive(uniffi::Object)]
+pub struct FrameDataSnapshot {
+    frame_data: FrameData,
+}
+
+#[uniffi::export]
+impl FrameDataSnapshot {
+    /// Loads a JSON snapshot of [`FrameData`]
+    #[uniffi::constructor]
+    pub fn from_json(json: String) -> Result<Arc<Self>, MobileError> {
+        FrameData::from_json(&json)
+            .map(|frame_data| Arc::new(FrameDataSnapshot { frame_data }))
+            .map_err(|e| MobileError::InvalidSnapshot { reason: e.to_string() })
+    }
+
+    /// Returns the ids of all characters in this snapshot
+    pub fn characters(&self) -> Vec<String> {
+        self.frame_data.character_frame_data.iter()
+            .map(|c| c.character_id.id.to_string())
+            .collect()
+    }
+
+    /// Returns every move of a character by query
+    pub fn moves(&self, character: String) -> Result<Vec<MoveView>, MobileError> {
+        self.frame_data.find_character_frame_data_query(&character)
+            .map(|c| CharacterView::new(c).moves)
+            .map_err(|_| MobileError::UnknownCharacter)
+    }
+
+    /// Finds a move by character query and move identifier
+    pub fn find_move(&self, character: String, move_query: String) -> Option<MoveView> {
+        let character_frame_data = self.frame_data.find_character_frame_data_query(&character).ok()?;
+        let move_data = self.frame_data.find_move_character(&character_frame_data.character_id, &move_query).ok()?;
+        Some(MoveView::new(&character_frame_data.character_id, move_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn queries_snapshot() {
+        let json = testing::ryu_jab().to_json();
+        let snapshot = FrameDataSnapshot::from_json(json).unwrap();
+        assert_eq!(snapshot.characters(), vec!["ryu"]);
+        let found = snapshot.find_move(String::from("ryu"), String::from("5lp")).unwrap();
+        assert_eq!(found.startup_frames, Some(4));
+        assert!(matches!(snapshot.moves(String::from("nobody")), Err(MobileError::UnknownCharacter)));
+        assert!(matches!(FrameDataSnapshot::from_json(String::from("{")), Err(MobileError::InvalidSnapshot { .. })));
+    }
+}
@@ -0,0 +1,93 @@
+//! Node.js bindings built with napi-rs, enabled with the `node` feature. Build the addon with
+//! `napi build --release --features node` (from `@napi-rs/cli`), then:
+//! ```js
+//! const sf6rs = require('./sf6rs.node');
+//! const data = await sf6rs.loadAll();
+//! console.log(data.findMove('ryu', '5lp').startup);
+//! ```
+//! Moves are returned as plain JavaScript objects using the crate's serde field names.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::character;
+use crate::framedata;
+
+/// JavaScript view of [`framedata::FrameData`]
+#[napi]
+pub struct FrameData(framedata::FrameData);
+
+#[napi]
+impl FrameData {
+    /// Loads a snapshot previously produced by [`FrameData::to_json`]
+    #[napi(factory)]
+    pub fn from_json(json: String) -> Result<FrameData> {
+        framedata::FrameData::from_json(&json)
+            .map(FrameData)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Exports the whole dataset as a JSON string
+    #[napi]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    /// Finds a move by character query and move identifier, returning `null` if there is none
+    #[napi]
+    pub fn find_move(&self, character: String, move_query: String) -> Result<Option<serde_json::Value>> {
+        let Ok(found) = self.0.find_move(&character, &move_query) else {
+            return Ok(None);
+        };
+        serde_json::to_value(found)
+            .map(Some)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Returns every move of a character, throwing for unknown characters
+    #[napi]
+    pub fn moves(&self, character: String) -> Result<serde_json::Value> {
+        let character_frame_data = self.0.find_character_frame_data_query(&character)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        serde_json::to_value(&character_frame_data.moves).map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Returns the ids of all loaded characters
+    #[napi]
+    pub fn characters(&self) -> Vec<String> {
+        self.0.character_frame_data.iter().map(|c| c.character_id.id.to_string()).collect()
+    }
+}
+
+/// Scrapes every supported character, see [`framedata::load_all`]
+#[napi]
+pub async fn load_all() -> FrameData {
+    FrameData(framedata::load_all().await.data)
+}
+
+/// Scrapes a single character by query, see [`framedata::load`]
+#[napi]
+pub async fn load(character: String) -> Result<FrameData> {
+    let character_id = character::get_character_by_regex(&character)
+        .ok_or_else(|| Error::from_reason(format!("Unknown character '{}'", character)))?;
+    Ok(FrameData(framedata::FrameData {
+        character_frame_data: vec![framedata::load(character_id).await.map_err(|e| Error::from_reason(e.to_string()))?],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = testing::ryu_jab().to_json();
+        let frame_data = FrameData::from_json(json).unwrap();
+        assert_eq!(frame_data.characters(), vec!["ryu"]);
+        let found = frame_data.find_move(String::from("ryu"), String::from("5lp")).unwrap().unwrap();
+        assert_eq!(found["startup"], "4");
+        assert!(frame_data.find_move(String::from("ryu"), String::from("9HK")).unwrap().is_none());
+        assert_eq!(frame_data.moves(String::from("ryu")).unwrap().as_array().map(Vec::len), Some(1));
+    }
+}
@@ -0,0 +1,259 @@
+//! Typed versions of [`Move`]'s frame data fields. The wiki writes values like `6`, `4(2)5` or
+//! `KD +38`; [`Move::parsed`] reads them once into numbers that can be compared and sorted.
+
+use crate::format::{leading_number, signed_number};
+use crate::framedata::{is_placeholder, Move};
+
+/// A frame count with whatever the wiki wrote after it, e.g. `4(2)5` is 4 frames with notes
+/// `(2)5`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frames {
+    pub frames: u32,
+    pub notes: Option<String>,
+}
+
+/// The frame a move's first active frame lands on
+pub type Startup = Frames;
+
+impl Frames {
+    /// Parses a frame count, `None` for placeholders and values that don't start with a number
+    pub fn parse(value: &str) -> Option<Frames> {
+        if is_placeholder(value) {
+            return None;
+        }
+        let (_, frames) = leading_number(value)?;
+        let rest = value.trim_start().trim_start_matches(['+', '-']).trim_start().trim_start_matches(|c: char| c.is_ascii_digit()).trim();
+        Some(Frames {
+            frames,
+            notes: (!rest.is_empty()).then(|| rest.to_string()),
+        })
+    }
+}
+
+/// Frame advantage after a move connects
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Advantage {
+    /// A plain advantage, e.g. `+4` or `-6`
+    Frames(i32),
+    /// The opponent is knocked down, with the attacker's advantage once they get up, e.g. `KD +38`
+    Knockdown(i32),
+    /// Anything else the wiki wrote, e.g. `Varies`
+    Unknown(String),
+}
+
+impl Advantage {
+    /// Parses an advantage value, `None` for placeholders
+    pub fn parse(value: &str) -> Option<Advantage> {
+        let value = value.trim();
+        if is_placeholder(value) {
+            return None;
+        }
+        let upper = value.to_ascii_uppercase();
+        let knockdown = ["HKD", "KD"].iter().find(|prefix| upper.starts_with(*prefix));
+        let advantage = match knockdown {
+            Some(prefix) => signed_number(&value[prefix.len()..]).map(Advantage::Knockdown),
+            None => signed_number(value).map(Advantage::Frames),
+        };
+        Some(advantage.unwrap_or_else(|| Advantage::Unknown(value.to_string())))
+    }
+
+    /// The advantage in frames, knockdowns included, `None` if unknown
+    pub fn frames(&self) -> Option<i32> {
+        match self {
+            Advantage::Frames(frames) | Advantage::Knockdown(frames) => Some(*frames),
+            Advantage::Unknown(_) => None,
+        }
+    }
+}
+
+/// What a move can be canceled into, read from the wiki's abbreviations in [`Move::cancel`] like
+/// `Chn`, `Sp` and `SA`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CancelType {
+    Chain,
+    Special,
+    Super,
+    TargetCombo,
+    DriveRush,
+}
+
+impl CancelType {
+    /// The cancel's readable name, e.g. `Target Combo`
+    pub fn name(&self) -> &'static str {
+        match self {
+            CancelType::Chain => "Chain",
+            CancelType::Special => "Special",
+            CancelType::Super => "Super",
+            CancelType::TargetCombo => "Target Combo",
+            CancelType::DriveRush => "Drive Rush",
+        }
+    }
+
+    /// Every cancel a [`Move::cancel`] value lists, see [`CancelOptions::cancel_types`]
+    pub fn parse_all(cancel: &str) -> Vec<CancelType> {
+        CancelOptions::parse(cancel).cancel_types()
+    }
+}
+
+/// Every cancel a move allows, parsed from [`Move::cancel`] while scraping into
+/// [`Move::cancel_options`]. Unlike [`CancelType::parse_all`], Super Art cancels keep their
+/// level, so `C SA3` allows chains and only a level 3 Super Art.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CancelOptions {
+    pub chain: bool,
+    pub special: bool,
+    /// Whether a level 1, 2 and 3 Super Art can be canceled into. The wiki's plain `SA` allows
+    /// all three.
+    pub super_arts: [bool; 3],
+    pub target_combo: bool,
+    pub drive_rush: bool,
+}
+
+impl CancelOptions {
+    /// Parses a [`Move::cancel`] value, e.g. `Chn Sp SA` or `sp su`
+    pub fn parse(cancel: &str) -> CancelOptions {
+        let mut options = CancelOptions::default();
+        for token in cancel.split(|c: char| c.is_whitespace() || c == ',' || c == '/') {
+            match token.to_ascii_lowercase().as_str() {
+                "chn" | "chain" | "c" => options.chain = true,
+                "sp" | "special" => options.special = true,
+                "su" | "sa" | "super" => options.super_arts = [true; 3],
+                "sa1" => options.super_arts[0] = true,
+                "sa2" => options.super_arts[1] = true,
+                "sa3" => options.super_arts[2] = true,
+                "tc" | "target" => options.target_combo = true,
+                "dr" => options.drive_rush = true,
+                _ => {}
+            }
+        }
+        options
+    }
+
+    /// Whether the move can be canceled into a Super Art of `level`, `1` to `3`
+    pub fn allows_super(&self, level: u8) -> bool {
+        (1..=3).contains(&level) && self.super_arts[level as usize - 1]
+    }
+
+    /// The Super Art levels the move can be canceled into, lowest first
+    pub fn super_levels(&self) -> Vec<u8> {
+        (1..=3).filter(|level| self.allows_super(*level)).collect()
+    }
+
+    /// Whether the move allows `cancel`, where [`CancelType::Super`] means a Super Art of any
+    /// level
+    pub fn contains(&self, cancel: CancelType) -> bool {
+        match cancel {
+            CancelType::Chain => self.chain,
+            CancelType::Special => self.special,
+            CancelType::Super => self.super_arts.contains(&true),
+            CancelType::TargetCombo => self.target_combo,
+            CancelType::DriveRush => self.drive_rush,
+        }
+    }
+
+    /// The kinds of cancel the move allows, chains first and Drive Rush last, with a Super Art of
+    /// any level as [`CancelType::Super`]
+    pub fn cancel_types(&self) -> Vec<CancelType> {
+        [CancelType::Chain, CancelType::Special, CancelType::Super, CancelType::TargetCombo, CancelType::DriveRush]
+            .into_iter()
+            .filter(|cancel| self.contains(*cancel))
+            .collect()
+    }
+
+    /// Whether the move can't be canceled at all
+    pub fn is_empty(&self) -> bool {
+        *self == CancelOptions::default()
+    }
+}
+
+/// A [`Move`] with its frame data fields parsed. Fields the wiki leaves empty are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedMove {
+    pub identifier: String,
+    pub name: String,
+    pub damage: Option<u32>,
+    pub startup: Option<Startup>,
+    pub active: Option<Frames>,
+    pub recovery: Option<Frames>,
+    pub total: Option<Frames>,
+    pub hitstun: Option<Frames>,
+    pub blockstun: Option<Frames>,
+    pub hit_advantage: Option<Advantage>,
+    pub block_advantage: Option<Advantage>,
+    pub punish_advantage: Option<Advantage>,
+    pub perfect_parry_advantage: Option<Advantage>,
+    pub after_dr_hit: Option<Advantage>,
+    pub after_dr_block: Option<Advantage>,
+}
+
+impl ParsedMove {
+    pub fn new(move_data: &Move) -> Self {
+        ParsedMove {
+            identifier: move_data.identifier.clone(),
+            name: move_data.name.clone(),
+            damage: Frames::parse(&move_data.damage).map(|damage| damage.frames),
+            startup: Frames::parse(&move_data.startup),
+            active: Frames::parse(&move_data.active),
+            recovery: Frames::parse(&move_data.recovery),
+            total: Frames::parse(&move_data.total),
+            hitstun: Frames::parse(&move_data.hitstun),
+            blockstun: Frames::parse(&move_data.blockstun),
+            hit_advantage: Advantage::parse(&move_data.hit_advantage),
+            block_advantage: Advantage::parse(&move_data.block_advantage),
+            punish_advantage: Advantage::parse(&move_data.punish_advantage),
+            perfect_parry_advantage: Advantage::parse(&move_data.perfect_parry_advantage),
+            after_dr_hit: Advantage::parse(&move_data.after_dr_hit),
+            after_dr_block: Advantage::parse(&move_data.after_dr_block),
+        }
+    }
+}
+
+impl Move {
+    /// This move's frame data fields parsed into numbers, see [`ParsedMove`]
+    pub fn parsed(&self) -> ParsedMove {
+        ParsedMove::new(self)
+    }
+
+    /// What this move can be canceled into, see [`Move::cancel_options`]
+    pub fn cancel_types(&self) -> Vec<CancelType> {
+        self.cancel_options.cancel_types()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wiki_values() {
+        let move_data = Move {
+            damage: String::from("800"),
+            startup: String::from("4(2)5"),
+            active: String::from("3"),
+            hit_advantage: String::from("KD +38"),
+            block_advantage: String::from("-6"),
+            punish_advantage: String::from("Varies"),
+            recovery: String::from("-"),
+            ..Move::default()
+        };
+        let parsed = move_data.parsed();
+        assert_eq!(parsed.damage, Some(800));
+        assert_eq!(parsed.startup, Some(Frames { frames: 4, notes: Some(String::from("(2)5")) }));
+        assert_eq!(parsed.active, Some(Frames { frames: 3, notes: None }));
+        assert_eq!(parsed.recovery, None);
+        assert_eq!(parsed.hit_advantage, Some(Advantage::Knockdown(38)));
+        assert_eq!(parsed.block_advantage.as_ref().and_then(Advantage::frames), Some(-6));
+        assert_eq!(parsed.punish_advantage, Some(Advantage::Unknown(String::from("Varies"))));
+        assert_eq!(CancelType::parse_all("TC, sa3/Chn"), vec![CancelType::Chain, CancelType::Super, CancelType::TargetCombo]);
+        let options = CancelOptions::parse("C SA3");
+        assert!(options.chain && !options.special && options.contains(CancelType::Super));
+        assert_eq!(options.super_levels(), vec![3]);
+        assert_eq!(CancelOptions::parse("sp su").super_levels(), vec![1, 2, 3]);
+        assert!(CancelOptions::parse("-").is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Hints of how often moves are asked about, so autocomplete and search can rank a character's
+//! 2MK, DP and supers above taunts and obscure follow-ups that match a query just as well.
+//! ```
+//! # let data = sf6rs::framedata::FrameData { character_frame_data: Vec::new() };
+//! use sf6rs::popularity::CuratedPopularity;
+//! for hit in data.text_index().search_ranked_with("kick", &CuratedPopularity) {
+//!     println!("{} {}", hit.character_id.name(), hit.move_data.identifier);
+//! }
+//! ```
+//! Bots with their own usage numbers can rank by those instead with [`UsagePopularity`] or any
+//! `Fn(&CharacterId, &Move) -> f32`.
+
+use std::collections::HashMap;
+
+use crate::character::{self, CharacterId};
+use crate::framedata::Move;
+use crate::tags::MoveCategory;
+
+/// How much a move's popularity can add to a match's score, small enough that popularity only
+/// decides between moves that match a query about equally well
+pub const POPULARITY_WEIGHT: f32 = 0.25;
+/// What a follow-up's popularity is multiplied by, e.g. the second hit of a target combo
+const FOLLOW_UP_FACTOR: f32 = 0.5;
+
+/// Supplies how commonly moves are asked about
+pub trait PopularityProvider {
+    /// How commonly `character_id`'s `move_data` is asked about, from `0.0` for never to `1.0`
+    /// for the most asked about moves
+    fn popularity(&self, character_id: &CharacterId, move_data: &Move) -> f32;
+}
+
+impl<F: Fn(&CharacterId, &Move) -> f32> PopularityProvider for F {
+    fn popularity(&self, character_id: &CharacterId, move_data: &Move) -> f32 {
+        self(character_id, move_data)
+    }
+}
+
+/// A move that is asked about more than its category alone suggests
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopularMove {
+    /// The character the hint is for, `None` if it applies to everyone
+    pub character_id: Option<&'static CharacterId>,
+    /// A move whose identifier contains this matches, so `623` covers every strength of a DP
+    pub identifier: &'static str,
+    pub popularity: f32,
+}
+
+impl PopularMove {
+    pub const fn shared(identifier: &'static str, popularity: f32) -> Self {
+        PopularMove {
+            character_id: None,
+            identifier,
+            popularity,
+        }
+    }
+
+    pub const fn character(character_id: &'static CharacterId, identifier: &'static str, popularity: f32) -> Self {
+        PopularMove {
+            character_id: Some(character_id),
+            identifier,
+            popularity,
+        }
+    }
+
+    fn matches(&self, character_id: &CharacterId, move_data: &Move) -> bool {
+        self.character_id.is_none_or(|id| id == character_id)
+            && move_data.identifier.to_ascii_uppercase().contains(self.identifier)
+    }
+}
+
+/// The moves the crate knows to be commonly asked about. Character hints take precedence over
+/// shared ones.
+pub static POPULAR_MOVES: [PopularMove; 17] = [
+    PopularMove::shared("2MK", 1.0),
+    PopularMove::shared("623", 0.95),
+    PopularMove::shared("2LP", 0.9),
+    PopularMove::shared("5LP", 0.85),
+    PopularMove::shared("2MP", 0.85),
+    PopularMove::shared("5MP", 0.85),
+    PopularMove::shared("2HP", 0.8),
+    PopularMove::shared("2HK", 0.8),
+    PopularMove::shared("HPHK", 0.9),
+    PopularMove::shared("MPMK", 0.8),
+    PopularMove::shared("LPLK", 0.8),
+    PopularMove::shared("SA3", 0.9),
+    PopularMove::character(&character::RYU, "236", 0.9),
+    PopularMove::character(&character::KEN, "236", 0.9),
+    PopularMove::character(&character::ZANGIEF, "360P", 1.0),
+    PopularMove::character(&character::GUILE, "[4]6P", 1.0),
+    PopularMove::character(&character::GUILE, "[2]8K", 0.95),
+];
+
+/// The crate's default hints: [`POPULAR_MOVES`], and otherwise a guess from the move's
+/// [`MoveCategory`] that puts supers and specials above normals and taunts last. Follow-ups
+/// like `5MP~HP` count for half.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CuratedPopularity;
+
+impl PopularityProvider for CuratedPopularity {
+    fn popularity(&self, character_id: &CharacterId, move_data: &Move) -> f32 {
+        let curated = POPULAR_MOVES.iter()
+            .filter(|hint| hint.matches(character_id, move_data))
+            .max_by_key(|hint| hint.character_id.is_some())
+            .map(|hint| hint.popularity);
+        let popularity = curated.unwrap_or_else(|| category_popularity(move_data.category()));
+        if move_data.identifier.contains(['~', '>']) {
+            popularity * FOLLOW_UP_FACTOR
+        } else {
+            popularity
+        }
+    }
+}
+
+fn category_popularity(category: MoveCategory) -> f32 {
+    match category {
+        MoveCategory::SuperArt => 0.7,
+        MoveCategory::Special | MoveCategory::DriveSystem => 0.6,
+        MoveCategory::Normal | MoveCategory::Throw => 0.5,
+        MoveCategory::OverDriveSpecial | MoveCategory::CommandNormal => 0.4,
+        MoveCategory::TargetCombo => 0.3,
+        MoveCategory::Taunt => 0.0,
+    }
+}
+
+/// Hints counted from usage, e.g. a bot's lookup log. The most used move of all has popularity
+/// `1.0` and the rest are relative to it; moves never used have `0.0`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsagePopularity {
+    /// Uses by character id and uppercase identifier
+    counts: HashMap<(String, String), u64>,
+    max: u64,
+}
+
+impl UsagePopularity {
+    pub fn new() -> Self {
+        UsagePopularity::default()
+    }
+
+    /// Counts one more use of `character_id`'s `identifier`
+    pub fn record(&mut self, character_id: &CharacterId, identifier: &str) {
+        self.record_many(character_id, identifier, 1);
+    }
+
+    /// Counts `uses` more uses of `character_id`'s `identifier`
+    pub fn record_many(&mut self, character_id: &CharacterId, identifier: &str, uses: u64) {
+        let count = self.counts.entry((character_id.id.to_string(), identifier.trim().to_ascii_uppercase())).or_default();
+        *count += uses;
+        self.max = self.max.max(*count);
+    }
+}
+
+impl PopularityProvider for UsagePopularity {
+    fn popularity(&self, character_id: &CharacterId, move_data: &Move) -> f32 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        let key = (character_id.id.to_string(), move_data.identifier.trim().to_ascii_uppercase());
+        self.counts.get(&key).map_or(0.0, |count| *count as f32 / self.max as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framedata::FrameData;
+    use crate::testing;
+
+    fn with(identifier: &str, name: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn ranks_popular_moves_first() {
+        let ryu = testing::character_frame_data(&character::RYU, vec![
+            with("5MP~HP", "Kick Combo"),
+            with("6HK", "Taunt Kick"),
+            with("2MK", "Crouching Medium Kick"),
+        ]);
+        let ranked = ryu.find_move_fuzzy_with("kick", &CuratedPopularity);
+        assert_eq!(ranked.iter().map(|m| m.move_data.identifier.as_str()).collect::<Vec<&str>>(), vec!["2MK", "5MP~HP", "6HK"]);
+
+        let data = FrameData { character_frame_data: vec![ryu] };
+        let mut usage = UsagePopularity::new();
+        usage.record_many(&character::RYU, "6hk", 3);
+        usage.record(&character::RYU, "2MK");
+        let hits = data.text_index().search_ranked_with("kick", &usage);
+        assert_eq!(hits[0].move_data.identifier, "6HK");
+        assert_eq!(usage.popularity(&character::RYU, &data.character_frame_data[0].moves[0]), 0.0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Python bindings built with PyO3, enabled with the `python` feature. Build and install the
+//! extension module with `maturin develop`, which also enables `python-extension` so the module
+//! links against the interpreter that imports it, then:
+//! ```python
+//! import sf6rs
+//! data = sf6rs.load_all()
+//! print(data.find_move("ryu", "5lp").startup)
+//! ```
+
+use pyo3::exceptions::{PyAttributeError, PyIOError, PyKeyError, PyRuntimeError};
+use pyo3::prelude::*;
+
+use crate::character;
+use crate::framedata::{self, FrameData, Move};
+
+/// Python view of [`FrameData`]
+#[pyclass(name = "FrameData", module = "sf6rs", frozen)]
+pub struct PyFrameData(FrameData);
+
+#[pymethods]
+impl PyFrameData {
+    /// Finds a move by character query and move identifier, raising `KeyError` if there is none
+    fn find_move(&self, character: &str, move_query: &str) -> PyResult<PyMove> {
+        self.0.find_move(character, move_query)
+            .map(|m| PyMove(m.clone()))
+            .map_err(|e| PyKeyError::new_err(e.to_string()))
+    }
+
+    /// Returns every move of a character, raising `KeyError` for unknown characters
+    fn moves(&self, character: &str) -> PyResult<Vec<PyMove>> {
+        self.0.find_character_frame_data_query(character)
+            .map(|c| c.moves.iter().cloned().map(PyMove).collect())
+            .map_err(|e| PyKeyError::new_err(e.to_string()))
+    }
+
+    /// Returns the ids of all loaded characters
+    fn characters(&self) -> Vec<String> {
+        self.0.character_frame_data.iter().map(|c| c.character_id.id.to_string()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.character_frame_data.len()
+    }
+}
+
+/// Python view of a [`Move`]. Every field of the Rust struct is readable as an attribute.
+#[pyclass(name = "Move", module = "sf6rs", frozen)]
+pub struct PyMove(Move);
+
+#[pymethods]
+impl PyMove {
+    fn __getattr__(&self, name: &str) -> PyResult<String> {
+        self.0.get(name)
+            .map(str::to_string)
+            .ok_or_else(|| PyAttributeError::new_err(format!("Move has no field '{}'", name)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move(identifier='{}', name='{}')", self.0.identifier, self.0.name)
+    }
+}
+
+fn runtime() -> PyResult<tokio::runtime::Runtime> {
+    tokio::runtime::Runtime::new().map_err(|e| PyRuntimeError::new_err(e.to_string()))
+}
+
+/// Scrapes every supported character, see [`framedata::load_all`]. Releases the GIL while loading.
+#[pyfunction]
+fn load_all(py: Python<'_>) -> PyResult<PyFrameData> {
+    let runtime = runtime()?;
+    Ok(PyFrameData(py.allow_threads(|| runtime.block_on(framedata::load_all()).data)))
+}
+
+/// Scrapes a single character by query, see [`framedata::load`]. Releases the GIL while loading.
+#[pyfunction]
+fn load(py: Python<'_>, character: &str) -> PyResult<PyFrameData> {
+    let character_id = character::get_character_by_regex(character)
+        .ok_or_else(|| PyKeyError::new_err(format!("Unknown character '{}'", character)))?;
+    let runtime = runtime()?;
+    let character_frame_data = py.allow_threads(|| runtime.block_on(framedata::load(character_id)))
+        .map_err(|e| PyIOError::new_err(e.to_string()))?;
+    Ok(PyFrameData(FrameData {
+        character_frame_data: vec![character_frame_data],
+    }))
+}
+
+#[pymodule]
+fn sf6rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyFrameData>()?;
+    m.add_class::<PyMove>()?;
+    m.add_function(wrap_pyfunction!(load_all, m)?)?;
+    m.add_function(wrap_pyfunction!(load, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn wraps_frame_data() {
+        let frame_data = PyFrameData(testing::ryu_jab());
+        assert_eq!(frame_data.characters(), vec!["ryu"]);
+        assert_eq!(frame_data.__len__(), 1);
+        let found = frame_data.find_move("ryu", "5lp").unwrap();
+        assert_eq!(found.__getattr__("startup").unwrap(), "4");
+        assert_eq!(found.__repr__(), "Move(identifier='5LP', name='Standing Light Punch')");
+        assert_eq!(frame_data.moves("ryu").unwrap().len(), 1);
+    }
+}
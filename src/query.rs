@@ -0,0 +1,238 @@
+//! Chained filters over every move of the cast, so cross-cast questions like "which normals are 7
+//! frames or faster and plus on block" don't need hand-rolled loops over the raw strings:
+//! ```
+//! use sf6rs::framedata::FrameData;
+//! use sf6rs::parsed::CancelType;
+//! use sf6rs::sort;
+//! use sf6rs::tags::MoveCategory;
+//!
+//! let data = FrameData { character_frame_data: Vec::new() };
+//! let buttons = data.query()
+//!     .category(MoveCategory::Normal)
+//!     .startup_at_most(7)
+//!     .cancelable_into(CancelType::Super)
+//!     .count();
+//! let fastest = data.query()
+//!     .order_by(sort::MoveComparator::ascending("startup").unwrap())
+//!     .into_iter()
+//!     .next();
+//! ```
+
+use crate::character::CharacterId;
+use crate::format::{signed_number, PUNISHABLE_THRESHOLD};
+use crate::framedata::{FrameData, Move};
+use crate::parsed::CancelType;
+use crate::sort::MoveComparator;
+use crate::tags::{MoveCategory, TagQuery};
+
+/// One condition of a [`MoveQuery`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    StartupAtMost(i32),
+    StartupAtLeast(i32),
+    BlockAtLeast(i32),
+    BlockAtMost(i32),
+    CancelableInto(CancelType),
+    CancelableIntoSuperArt(u8),
+    Category(MoveCategory),
+    Tags(TagQuery),
+}
+
+impl Filter {
+    fn matches(&self, move_data: &Move) -> bool {
+        let startup = || signed_number(&move_data.startup).filter(|startup| *startup > 0);
+        match self {
+            Filter::StartupAtMost(frames) => startup().is_some_and(|startup| startup <= *frames),
+            Filter::StartupAtLeast(frames) => startup().is_some_and(|startup| startup >= *frames),
+            Filter::BlockAtLeast(frames) => move_data.block_frames().is_some_and(|block| block >= *frames),
+            Filter::BlockAtMost(frames) => move_data.block_frames().is_some_and(|block| block <= *frames),
+            Filter::CancelableInto(cancel) => move_data.cancel_options.contains(*cancel),
+            Filter::CancelableIntoSuperArt(level) => move_data.cancel_options.allows_super(*level),
+            Filter::Category(category) => move_data.category() == *category,
+            Filter::Tags(query) => query.matches(move_data),
+        }
+    }
+}
+
+/// Moves across the cast matching every chained filter, see [`FrameData::query`]. Moves without
+/// a number in a filtered field never match. Results are in movelist order unless ordered with
+/// [`MoveQuery::order_by`].
+#[derive(Debug, Clone)]
+pub struct MoveQuery<'a> {
+    frame_data: &'a FrameData,
+    characters: Vec<String>,
+    filters: Vec<Filter>,
+    order: Vec<MoveComparator>,
+}
+
+impl<'a> MoveQuery<'a> {
+    pub fn new(frame_data: &'a FrameData) -> Self {
+        MoveQuery {
+            frame_data,
+            characters: Vec::new(),
+            filters: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Keeps only this character's moves. Calling it again adds another character.
+    pub fn character(mut self, character_id: &CharacterId) -> Self {
+        self.characters.push(character_id.id.to_string());
+        self
+    }
+
+    pub fn startup_at_most(mut self, frames: i32) -> Self {
+        self.filters.push(Filter::StartupAtMost(frames));
+        self
+    }
+
+    pub fn startup_at_least(mut self, frames: i32) -> Self {
+        self.filters.push(Filter::StartupAtLeast(frames));
+        self
+    }
+
+    /// Keeps moves at least `frames` on block, e.g. `-2` for moves at worst -2
+    pub fn block_at_least(mut self, frames: i32) -> Self {
+        self.filters.push(Filter::BlockAtLeast(frames));
+        self
+    }
+
+    /// Keeps moves at most `frames` on block
+    pub fn block_at_most(mut self, frames: i32) -> Self {
+        self.filters.push(Filter::BlockAtMost(frames));
+        self
+    }
+
+    /// Keeps moves with positive advantage on block
+    pub fn plus_on_block(self) -> Self {
+        self.block_at_least(1)
+    }
+
+    /// Keeps moves the fastest normals can't punish on block
+    pub fn safe_on_block(self) -> Self {
+        self.block_at_least(PUNISHABLE_THRESHOLD + 1)
+    }
+
+    /// Keeps moves the fastest normals can punish on block
+    pub fn punishable_on_block(self) -> Self {
+        self.block_at_most(PUNISHABLE_THRESHOLD)
+    }
+
+    /// Keeps moves that can be canceled into `cancel`, a Super Art of any level for
+    /// [`CancelType::Super`]
+    pub fn cancelable_into(mut self, cancel: CancelType) -> Self {
+        self.filters.push(Filter::CancelableInto(cancel));
+        self
+    }
+
+    /// Keeps moves that can be canceled into the Super Art of `level`, `1` to `3`
+    pub fn cancelable_into_super_art(mut self, level: u8) -> Self {
+        self.filters.push(Filter::CancelableIntoSuperArt(level));
+        self
+    }
+
+    pub fn category(mut self, category: MoveCategory) -> Self {
+        self.filters.push(Filter::Category(category));
+        self
+    }
+
+    /// Keeps moves matching a tag or [`TagQuery`]
+    pub fn tag(mut self, query: impl Into<TagQuery>) -> Self {
+        self.filters.push(Filter::Tags(query.into()));
+        self
+    }
+
+    /// Orders the results by `comparator`, e.g. `MoveComparator::ascending("startup")`. Calling it
+    /// again breaks ties with the next comparator; remaining ties keep movelist order.
+    pub fn order_by(mut self, comparator: MoveComparator) -> Self {
+        self.order.push(comparator);
+        self
+    }
+
+    /// The matching moves, character by character in movelist order unless ordered with
+    /// [`MoveQuery::order_by`]
+    pub fn iter(&self) -> impl Iterator<Item = (&'a CharacterId, &'a Move)> + '_ {
+        let mut moves = self.frame_data.character_frame_data.iter()
+            .filter(|c| self.characters.is_empty() || self.characters.iter().any(|id| *id == c.character_id.id))
+            .flat_map(|c| c.moves.iter().map(move |m| (&c.character_id, m)))
+            .filter(|(_, m)| self.filters.iter().all(|filter| filter.matches(m)))
+            .collect::<Vec<_>>();
+        if !self.order.is_empty() {
+            moves.sort_by(|(_, a), (_, b)| {
+                self.order.iter().fold(std::cmp::Ordering::Equal, |ordering, comparator| ordering.then_with(|| comparator.compare(a, b)))
+            });
+        }
+        moves.into_iter()
+    }
+
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<'a> IntoIterator for MoveQuery<'a> {
+    type Item = (&'a CharacterId, &'a Move);
+    type IntoIter = std::vec::IntoIter<(&'a CharacterId, &'a Move)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl FrameData {
+    /// Starts a [`MoveQuery`] over every loaded move
+    pub fn query(&self) -> MoveQuery<'_> {
+        MoveQuery::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::parsed::CancelOptions;
+    use crate::tags::Tag;
+    use crate::testing;
+
+    fn with(identifier: &str, startup: &str, block: &str, cancel: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            cancel: cancel.to_string(),
+            cancel_options: CancelOptions::parse(cancel),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn filters_across_the_cast() {
+        let data = FrameData {
+            character_frame_data: vec![
+                testing::character_frame_data(&character::RYU, vec![with("5LP", "4", "-1", "Chn Sp SA"), with("5HP", "10", "+1", "Sp SA"), with("623HP", "6", "-30", "SA3")]),
+                testing::character_frame_data(&character::KEN, vec![with("5MP", "6", "+2", "Sp SA"), with("2HK", "8", "-12", "-")]),
+            ],
+        };
+        let ids = |query: MoveQuery| query.into_iter().map(|(c, m)| format!("{} {}", c.id, m.identifier)).collect::<Vec<String>>();
+        assert_eq!(ids(data.query().startup_at_most(7).plus_on_block()), vec!["ken 5MP"]);
+        assert_eq!(ids(data.query().category(MoveCategory::Normal).cancelable_into(CancelType::Super)), vec!["ryu 5LP", "ryu 5HP", "ken 5MP"]);
+        assert_eq!(ids(data.query().cancelable_into_super_art(3).startup_at_least(6)), vec!["ryu 5HP", "ryu 623HP", "ken 5MP"]);
+        assert_eq!(ids(data.query().cancelable_into_super_art(1).startup_at_least(6)), vec!["ryu 5HP", "ken 5MP"]);
+        assert_eq!(ids(data.query().punishable_on_block().character(&character::KEN)), vec!["ken 2HK"]);
+        assert_eq!(data.query().safe_on_block().startup_at_least(6).count(), 2);
+        assert_eq!(data.query().tag(Tag::Invincible).count(), 0);
+    }
+
+    #[test]
+    fn orders_numerically() {
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![with("5HP", "10", "+1", "-"), with("2MK", "8", "-3", "-"), with("Taunt", "-", "-", "-"), with("5LP", "4", "-1", "-"), with("5MP", "8", "+2", "-")])],
+        };
+        let ids = |query: MoveQuery| query.into_iter().map(|(_, m)| m.identifier.clone()).collect::<Vec<String>>();
+        let startup = MoveComparator::ascending("startup").unwrap();
+        assert_eq!(ids(data.query().order_by(startup)), vec!["5LP", "2MK", "5MP", "5HP", "Taunt"]);
+        let block = MoveComparator::descending("block_advantage").unwrap();
+        assert_eq!(ids(data.query().order_by(startup).order_by(block)), vec!["5LP", "5MP", "2MK", "5HP", "Taunt"]);
+        assert_eq!(ids(data.query().order_by(block).startup_at_most(8)), vec!["5MP", "5LP", "2MK"]);
+    }
+}
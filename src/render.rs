@@ -0,0 +1,271 @@
+//! Renders frame data tables as PNG images, so a bot can post a character's whole movelist
+//! section as one image instead of an embed per move.
+
+use crate::character::CharacterId;
+use crate::format::{signed_number, PUNISHABLE_THRESHOLD};
+use crate::format::style::FormatStyle;
+use crate::framedata::media::png;
+use crate::framedata::columns::{self, ColumnCategory};
+use crate::framedata::{CharacterFrameData, Move};
+use crate::tags::MoveCategory;
+
+pub(crate) mod font;
+
+/// An RGBA color
+pub type Color = [u8; 4];
+
+/// Colors and sizing of a rendered table. Advantage cells are colored the way
+/// [`format::broadcast`](crate::format::broadcast) words them: plus, minus but safe, or
+/// punishable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableStyle {
+    pub background: Color,
+    /// The title and column header rows
+    pub header: Color,
+    /// Every other move row, so long rows stay easy to follow
+    pub alternate_row: Color,
+    pub text: Color,
+    pub header_text: Color,
+    /// Advantage above zero
+    pub plus: Color,
+    /// Advantage below zero that isn't punishable
+    pub minus: Color,
+    /// Advantage low enough to be punished by the fastest normals
+    pub punishable: Color,
+    /// How many times larger than its base size the image is drawn, kept between `1` and
+    /// [`TableStyle::MAX_SCALE`]
+    pub scale: u32,
+    /// Longest a cell can be, in characters, before it's cut short with `..`
+    pub max_cell_chars: usize,
+}
+
+impl TableStyle {
+    /// The largest [`TableStyle::scale`] drawn, so a typo can't ask for a gigapixel image
+    pub const MAX_SCALE: u32 = 8;
+
+    pub const DARK: TableStyle = TableStyle {
+        background: [0x1e, 0x1f, 0x22, 0xff],
+        header: [0x2b, 0x2d, 0x31, 0xff],
+        alternate_row: [0x25, 0x26, 0x2a, 0xff],
+        text: [0xdb, 0xde, 0xe1, 0xff],
+        header_text: [0xff, 0xff, 0xff, 0xff],
+        plus: [0x57, 0xf2, 0x87, 0xff],
+        minus: [0xfe, 0xe7, 0x5c, 0xff],
+        punishable: [0xed, 0x42, 0x45, 0xff],
+        scale: 2,
+        max_cell_chars: 24,
+    };
+
+    pub const LIGHT: TableStyle = TableStyle {
+        background: [0xff, 0xff, 0xff, 0xff],
+        header: [0xe3, 0xe5, 0xe8, 0xff],
+        alternate_row: [0xf2, 0xf3, 0xf5, 0xff],
+        text: [0x31, 0x33, 0x38, 0xff],
+        header_text: [0x06, 0x06, 0x07, 0xff],
+        plus: [0x1f, 0x8b, 0x4c, 0xff],
+        minus: [0xa8, 0x6f, 0x00, 0xff],
+        punishable: [0xd8, 0x3c, 0x3e, 0xff],
+        ..TableStyle::DARK
+    };
+
+    fn advantage_color(&self, value: &str) -> Color {
+        match signed_number(value) {
+            Some(frames) if frames <= PUNISHABLE_THRESHOLD => self.punishable,
+            Some(frames) if frames < 0 => self.minus,
+            Some(frames) if frames > 0 => self.plus,
+            _ => self.text,
+        }
+    }
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        TableStyle::DARK
+    }
+}
+
+/// The fields drawn, headed by their [`columns`] labels. Advantage columns are colored by
+/// [`TableStyle::advantage_color`].
+const FIELDS: [&str; 8] = ["identifier", "name", "startup", "active", "recovery", "hit_advantage", "block_advantage", "damage"];
+/// Space around each cell's text, in canvas units
+const PADDING: u32 = 3;
+
+/// Renders `moves` as a table titled `title`, e.g. `Ken Normals`, and encodes it as a PNG file
+pub fn moves_png(title: &str, moves: &[&Move], style: &TableStyle) -> Vec<u8> {
+    let columns = FIELDS.map(|field| columns::by_field(field).expect("rendered fields are move fields"));
+    let rows = moves.iter()
+        .map(|m| FIELDS.map(|field| truncate(FormatStyle::ENGLISH.value(m.get(field).unwrap_or_default()), style.max_cell_chars)))
+        .collect::<Vec<[String; 8]>>();
+
+    let mut widths = columns.map(|column| font::text_width(column.label));
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(font::text_width(cell));
+        }
+    }
+    let column_width = |text_width: u32| text_width + PADDING * 2;
+    let row_height = font::line_height() + PADDING * 2;
+    let table_width = widths.iter().map(|w| column_width(*w)).sum::<u32>()
+        .max(column_width(font::text_width(title)));
+
+    let mut canvas = Canvas::new(table_width, row_height * (rows.len() as u32 + 2), style.scale, style.background);
+    canvas.fill_rect(0, 0, table_width, row_height * 2, style.header);
+    canvas.text(PADDING, PADDING, title, style.header_text);
+    let mut x = 0;
+    for (column, width) in columns.iter().zip(widths) {
+        canvas.text(x + PADDING, row_height + PADDING, column.label, style.header_text);
+        x += column_width(width);
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        let y = row_height * (index as u32 + 2);
+        if index % 2 == 1 {
+            canvas.fill_rect(0, y, table_width, row_height, style.alternate_row);
+        }
+        let mut x = 0;
+        for (column, (cell, width)) in columns.iter().zip(row.iter().zip(widths)) {
+            let color = if column.category == ColumnCategory::Advantage { style.advantage_color(cell) } else { style.text };
+            canvas.text(x + PADDING, y + PADDING, cell, color);
+            x += column_width(width);
+        }
+    }
+    canvas.to_png()
+}
+
+impl CharacterFrameData {
+    /// Renders every move in `category` as one table image, e.g. all of a character's normals
+    /// for "show me Ken's normals"
+    pub fn render_section(&self, category: MoveCategory, style: &TableStyle) -> Vec<u8> {
+        let moves = self.moves.iter()
+            .filter(|m| m.category() == category)
+            .collect::<Vec<&Move>>();
+        moves_png(&section_title(&self.character_id, category), &moves, style)
+    }
+}
+
+/// e.g. `Ken Command Normals`
+fn section_title(character_id: &CharacterId, category: MoveCategory) -> String {
+    let words = category.name().split(' ')
+        .map(|word| format!("{}{}", word[..1].to_ascii_uppercase(), &word[1..]))
+        .collect::<Vec<String>>();
+    format!("{} {}s", character_id.name(), words.join(" "))
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        return value.to_string();
+    }
+    let mut truncated = value.chars().take(max_chars.saturating_sub(2)).collect::<String>();
+    truncated.push_str("..");
+    truncated
+}
+
+/// An RGBA image drawn on in canvas units, each `scale` image pixels large
+pub(crate) struct Canvas {
+    width: u32,
+    height: u32,
+    scale: u32,
+    rgba: Vec<u8>,
+}
+
+impl Canvas {
+    /// A canvas `width` by `height` canvas units large, `scale` clamped like
+    /// [`TableStyle::scale`]
+    pub(crate) fn new(width: u32, height: u32, scale: u32, background: Color) -> Self {
+        let scale = scale.clamp(1, TableStyle::MAX_SCALE);
+        let (width, height) = (width * scale, height * scale);
+        Canvas {
+            width,
+            height,
+            scale,
+            rgba: background.repeat((width * height) as usize),
+        }
+    }
+
+    pub(crate) fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.dot(px, py, color);
+            }
+        }
+    }
+
+    pub(crate) fn text(&mut self, x: u32, y: u32, text: &str, color: Color) {
+        font::draw(text, self.scale, x * self.scale, y * self.scale, |px, py, coverage| self.blend(px, py, color, coverage));
+    }
+
+    pub(crate) fn dot(&mut self, x: u32, y: u32, color: Color) {
+        for py in y * self.scale..(y + 1) * self.scale {
+            for px in x * self.scale..(x + 1) * self.scale {
+                if px < self.width && py < self.height {
+                    let offset = (py * self.width + px) as usize * 4;
+                    self.rgba[offset..offset + 4].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    /// Mixes `coverage` of `color` into the image pixel at `(x, y)`
+    fn blend(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+        if x < self.width && y < self.height {
+            let offset = (y * self.width + x) as usize * 4;
+            for (channel, value) in self.rgba[offset..offset + 4].iter_mut().zip(color) {
+                *channel = (*channel as f32 + (value as f32 - *channel as f32) * coverage).round() as u8;
+            }
+        }
+    }
+
+    pub(crate) fn to_png(&self) -> Vec<u8> {
+        png::encode_rgba(self.width, self.height, &self.rgba)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::media::sniff_dimensions;
+    use crate::testing;
+
+    #[test]
+    fn renders_sections() {
+        let normal = |identifier: &str, block: &str| Move {
+            identifier: identifier.to_string(),
+            name: String::from("Standing Light Punch"),
+            startup: String::from("4"),
+            block_advantage: block.to_string(),
+            ..Move::default()
+        };
+        let ken = testing::character_frame_data(&character::KEN, vec![normal("5LP", "-1"), normal("2HP", "-10"), Move { identifier: String::from("236P"), ..Move::default() }]);
+        assert_eq!(section_title(&ken.character_id, MoveCategory::SuperArt), "Ken Super Arts");
+
+        let style = TableStyle { scale: 1, ..TableStyle::default() };
+        let png = ken.render_section(MoveCategory::Normal, &style);
+        let row_height = font::line_height() + PADDING * 2;
+        let (width, height) = sniff_dimensions(&png).unwrap();
+        assert_eq!(height, row_height * 4);
+        let widths = ["Move", "Standing Light Punch", "Startup", "Active", "Recovery", "On Hit", "On Block", "Damage"];
+        assert_eq!(width, widths.iter().map(|w| font::text_width(w) + PADDING * 2).sum::<u32>());
+
+        let huge = TableStyle { scale: 1000, ..TableStyle::default() };
+        let (huge_width, _) = sniff_dimensions(&ken.render_section(MoveCategory::Normal, &huge)).unwrap();
+        assert_eq!(huge_width, width * TableStyle::MAX_SCALE);
+
+        assert_eq!(style.advantage_color("-10"), style.punishable);
+        assert_eq!(style.advantage_color("-1"), style.minus);
+        assert_eq!(style.advantage_color("KD +30"), style.text);
+        assert_eq!(truncate("Standing Light Punch", 10), "Standing..");
+    }
+
+    #[test]
+    fn rasterizes_text() {
+        let mut canvas = Canvas::new(40, 20, 2, [0, 0, 0, 0xff]);
+        canvas.text(2, 2, "Aé", [0xff, 0xff, 0xff, 0xff]);
+        let lit = canvas.rgba.chunks(4).filter(|pixel| pixel[0] > 0).count();
+        let partial = canvas.rgba.chunks(4).filter(|pixel| pixel[0] > 0 && pixel[0] < 0xff).count();
+        assert!(lit > 50 && partial > 0);
+        assert!(canvas.rgba.chunks(4).take(4).all(|pixel| pixel == [0, 0, 0, 0xff]));
+        assert_eq!(font::text_width("iiii"), font::text_width("MMMM"));
+        assert!(font::text_width("MMMM") > font::text_width("MM"));
+    }
+}
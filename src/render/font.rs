@@ -0,0 +1,48 @@
+//! Text rasterized from DejaVu Sans Mono with [`ab_glyph`], so labels stay smooth at every
+//! [`TableStyle::scale`](super::TableStyle::scale) and cover more than ASCII. The font and its
+//! license are in `font/`.
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+
+use crate::LazyLock;
+
+/// The font size at scale 1, in canvas units
+pub(crate) const SIZE: f32 = 10.0;
+
+static FONT: LazyLock<FontRef<'static>> = LazyLock::new(|| {
+    FontRef::try_from_slice(include_bytes!("font/DejaVuSansMono.ttf")).expect("the bundled font parses")
+});
+
+/// The height of a line of text at scale 1, ascenders to descenders
+pub(crate) fn line_height() -> u32 {
+    let font = FONT.as_scaled(PxScale::from(SIZE));
+    (font.ascent() - font.descent()).ceil() as u32
+}
+
+/// How wide `text` is at scale 1
+pub(crate) fn text_width(text: &str) -> u32 {
+    let font = FONT.as_scaled(PxScale::from(SIZE));
+    text.chars().map(|c| font.h_advance(font.glyph_id(c))).sum::<f32>().ceil() as u32
+}
+
+/// Rasterizes `text` at `scale` times [`SIZE`] with the top left of its first line at `(x, y)`,
+/// calling `plot` with every pixel it covers and how much of it, from `0` to `1`
+pub(crate) fn draw(text: &str, scale: u32, x: u32, y: u32, mut plot: impl FnMut(u32, u32, f32)) {
+    let font = FONT.as_scaled(PxScale::from(SIZE * scale as f32));
+    let mut caret = point(x as f32, y as f32 + font.ascent());
+    for c in text.chars() {
+        let mut glyph = font.scaled_glyph(c);
+        glyph.position = caret;
+        caret.x += font.h_advance(glyph.id);
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let (px, py) = (bounds.min.x + gx as f32, bounds.min.y + gy as f32);
+            if px >= 0.0 && py >= 0.0 && coverage > 0.0 {
+                plot(px as u32, py as u32, coverage.min(1.0));
+            }
+        });
+    }
+}
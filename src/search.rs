@@ -0,0 +1,262 @@
+//! An in-memory inverted index over move names and notes, for finding mechanics that are only
+//! mentioned in prose, e.g. every move whose notes say it crumples.
+//! ```
+//! # let data = sf6rs::framedata::FrameData { character_frame_data: Vec::new() };
+//! let index = data.text_index();
+//! for (character_id, move_data) in index.search("crumple") {
+//!     println!("{} {}", character_id.name(), move_data.identifier);
+//! }
+//! ```
+//! [`TextIndex::search_ranked`] scores matches instead, tolerating typos and favouring words in a
+//! move's name over its notes. With the `search` feature, [`tantivy_index`] provides the same
+//! search over a tantivy index that can be persisted to disk.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::character::CharacterId;
+use crate::framedata::{FrameData, Move};
+use crate::popularity::{PopularityProvider, POPULARITY_WEIGHT};
+
+#[cfg(feature = "search")]
+pub mod tantivy_index;
+
+/// How much more a word counts in a move's `name` than in its `notes`
+pub(crate) const NAME_BOOST: f32 = 2.0;
+pub(crate) const NOTES_BOOST: f32 = 1.0;
+/// Added when a query word is a move's `identifier`, so `5hk` ranks Juri's 5HK first
+pub(crate) const IDENTIFIER_BOOST: f32 = 3.0;
+/// How much a prefix or misspelt match counts next to an exact one
+pub(crate) const PREFIX_WEIGHT: f32 = 0.75;
+pub(crate) const TYPO_WEIGHT: f32 = 0.5;
+
+/// A move found by [`TextIndex::search_ranked`]
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub character_id: &'a CharacterId,
+    pub move_data: &'a Move,
+    /// Higher is better. Only meaningful relative to other hits of the same query.
+    pub score: f32,
+}
+
+/// Words of every move's `name` and `notes`, mapped to the moves they appear in. Build it once
+/// with [`FrameData::text_index`] and query it as often as needed.
+#[derive(Debug, Clone)]
+pub struct TextIndex<'a> {
+    moves: Vec<(&'a CharacterId, &'a Move)>,
+    /// Each word's moves, along with how strongly the word is tied to the move
+    words: BTreeMap<String, BTreeMap<usize, f32>>,
+}
+
+impl<'a> TextIndex<'a> {
+    pub fn new(frame_data: &'a FrameData) -> Self {
+        let mut index = TextIndex {
+            moves: Vec::new(),
+            words: BTreeMap::new(),
+        };
+        for character_frame_data in &frame_data.character_frame_data {
+            for move_data in &character_frame_data.moves {
+                let position = index.moves.len();
+                index.moves.push((&character_frame_data.character_id, move_data));
+                let name = words(&move_data.name).map(|word| (word, NAME_BOOST));
+                let notes = words(&move_data.notes).map(|word| (word, NOTES_BOOST));
+                for (word, boost) in name.chain(notes) {
+                    let weight = index.words.entry(word).or_default().entry(position).or_default();
+                    *weight = weight.max(boost);
+                }
+            }
+        }
+        index
+    }
+
+    /// Moves whose name or notes contain every word of `query`, in roster order. Each query word
+    /// also matches longer words it starts, so `crumple` finds `crumples` and `crumpled`.
+    pub fn search(&self, query: &str) -> Vec<(&'a CharacterId, &'a Move)> {
+        let mut matches: Option<BTreeSet<usize>> = None;
+        for word in words(query) {
+            let found = self.words.range(word.clone()..)
+                .take_while(|(indexed, _)| indexed.starts_with(&word))
+                .flat_map(|(_, positions)| positions.keys().copied())
+                .collect::<BTreeSet<usize>>();
+            matches = Some(match matches {
+                Some(matches) => matches.intersection(&found).copied().collect(),
+                None => found,
+            });
+        }
+        matches.unwrap_or_default().into_iter().map(|position| self.moves[position]).collect()
+    }
+
+    /// Moves matching every word of `query`, best first. Unlike [`TextIndex::search`], a query
+    /// word also matches indexed words one typo away (two for long words), so `hadokne` still
+    /// finds Hadoken. Exact words score above prefixes and typos, names above notes, and a query
+    /// word that is a move's `identifier` scores highest of all.
+    pub fn search_ranked(&self, query: &str) -> Vec<SearchHit<'a>> {
+        let mut scores: Option<BTreeMap<usize, f32>> = None;
+        for word in words(query) {
+            let mut found = BTreeMap::new();
+            for (indexed, positions) in &self.words {
+                let Some(weight) = word_weight(&word, indexed) else {
+                    continue;
+                };
+                for (position, boost) in positions {
+                    let score = found.entry(*position).or_insert(0.0f32);
+                    *score = score.max(weight * boost);
+                }
+            }
+            for (position, (_, move_data)) in self.moves.iter().enumerate() {
+                if move_data.identifier.eq_ignore_ascii_case(&word) {
+                    *found.entry(position).or_insert(0.0) += IDENTIFIER_BOOST;
+                }
+            }
+            scores = Some(match scores {
+                Some(scores) => scores.into_iter()
+                    .filter_map(|(position, score)| found.get(&position).map(|found| (position, score + found)))
+                    .collect(),
+                None => found,
+            });
+        }
+        let mut hits = scores.unwrap_or_default().into_iter()
+            .map(|(position, score)| SearchHit {
+                character_id: self.moves[position].0,
+                move_data: self.moves[position].1,
+                score,
+            })
+            .collect::<Vec<SearchHit>>();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+
+    /// Like [`TextIndex::search_ranked`], with each hit's score raised by up to
+    /// [`POPULARITY_WEIGHT`] by how commonly `popularity` says the move is asked about
+    pub fn search_ranked_with(&self, query: &str, popularity: &impl PopularityProvider) -> Vec<SearchHit<'a>> {
+        let mut hits = self.search_ranked(query);
+        for hit in &mut hits {
+            hit.score += POPULARITY_WEIGHT * popularity.popularity(hit.character_id, hit.move_data).clamp(0.0, 1.0);
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+}
+
+/// How well query word `word` matches `indexed`, `None` if it doesn't
+fn word_weight(word: &str, indexed: &str) -> Option<f32> {
+    if indexed == word {
+        Some(1.0)
+    } else if indexed.starts_with(word) {
+        Some(PREFIX_WEIGHT)
+    } else if edit_distance(word, indexed) <= max_typos(word) {
+        Some(TYPO_WEIGHT)
+    } else {
+        None
+    }
+}
+
+/// Short words get no typos at all, otherwise `dp` would match every two letter word
+pub(crate) fn max_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// The edit distance between `a` and `b`, counting swapped neighbouring letters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution.min(distances[i - 1][j] + 1).min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+impl FrameData {
+    /// Builds a [`TextIndex`] over every move's name and notes
+    pub fn text_index(&self) -> TextIndex<'_> {
+        TextIndex::new(self)
+    }
+
+    /// Finds moves whose name or notes mention every word of `query`. This builds a fresh
+    /// [`TextIndex`] on each call; keep one from [`FrameData::text_index`] for repeated queries.
+    pub fn search_text(&self, query: &str) -> Vec<(&CharacterId, &Move)> {
+        self.text_index().search(query)
+    }
+
+    /// Finds moves like [`TextIndex::search_ranked`], building a fresh [`TextIndex`] on each call
+    pub fn search_text_ranked(&self, query: &str) -> Vec<SearchHit<'_>> {
+        self.text_index().search_ranked(query)
+    }
+}
+
+pub(crate) fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    #[test]
+    fn finds_moves_by_notes() {
+        let with_notes = |identifier: &str, notes: &str| Move {
+            identifier: identifier.to_string(),
+            notes: notes.to_string(),
+            ..Move::default()
+        };
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::JURI, vec![
+                with_notes("5HK", "Crumples on Punish Counter"),
+                with_notes("2HK", "Hard knockdown on Punish Counter"),
+                with_notes("6MK", "Overhead"),
+            ])],
+        };
+        let identifiers = |query| data.search_text(query).iter().map(|(_, m)| m.identifier.clone()).collect::<Vec<String>>();
+        assert_eq!(identifiers("crumple"), vec!["5HK"]);
+        assert_eq!(identifiers("punish counter"), vec!["5HK", "2HK"]);
+        assert_eq!(identifiers("hard punish"), vec!["2HK"]);
+        assert!(identifiers("").is_empty());
+    }
+
+    #[test]
+    fn ranks_and_tolerates_typos() {
+        let named = |identifier: &str, name: &str, notes: &str| Move {
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+            notes: notes.to_string(),
+            ..Move::default()
+        };
+        let data = FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![
+                named("2MK", "Crouching Medium Kick", "Cancel into Hadoken"),
+                named("236P", "Hadoken", "Projectile"),
+                named("623P", "Shoryuken", "Invincible"),
+            ])],
+        };
+        let identifiers = |query| data.search_text_ranked(query).iter().map(|hit| hit.move_data.identifier.clone()).collect::<Vec<String>>();
+        assert_eq!(identifiers("hadoken"), vec!["236P", "2MK"]);
+        assert_eq!(identifiers("hadokne"), vec!["236P", "2MK"]);
+        assert_eq!(identifiers("shoryu"), vec!["623P"]);
+        assert_eq!(identifiers("623p"), vec!["623P"]);
+        assert_eq!(identifiers("medum kik"), Vec::<String>::new());
+        assert_eq!(identifiers("medum kic"), vec!["2MK"]);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("hadokne", "hadoken"), 1);
+    }
+}
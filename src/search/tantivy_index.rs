@@ -0,0 +1,298 @@
+//! A [tantivy](https://docs.rs/tantivy) index over every move, for deployments searching the whole
+//! cast often enough that rebuilding a [`TextIndex`](super::TextIndex) per process is too slow.
+//! The index can be kept in memory or persisted to a directory and reopened without the data:
+//! ```no_run
+//! # fn run(data: &sf6rs::framedata::FrameData) -> Result<(), sf6rs::search::tantivy_index::SearchError> {
+//! use std::path::Path;
+//! use sf6rs::search::tantivy_index::MoveSearchIndex;
+//!
+//! MoveSearchIndex::build_in_dir(data, Path::new("search-index"))?;
+//! let index = MoveSearchIndex::open(Path::new("search-index"))?;
+//! for hit in index.search("hadokne", 10)? {
+//!     println!("{} {} {}", hit.character_id, hit.identifier, hit.score);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value, STORED, STRING, TEXT};
+use tantivy::{doc, Index, IndexReader, TantivyDocument, Term};
+
+use crate::character::CharacterId;
+use crate::framedata::{FrameData, Move};
+use crate::search::{max_typos, words, IDENTIFIER_BOOST, NAME_BOOST, NOTES_BOOST, PREFIX_WEIGHT, TYPO_WEIGHT};
+
+/// Memory the index writer may use before flushing to disk, tantivy's minimum per thread
+const WRITER_MEMORY: usize = 15_000_000;
+
+/// Why the index couldn't be built, opened or searched
+#[derive(Debug)]
+pub enum SearchError {
+    Io(io::Error),
+    Index(tantivy::TantivyError),
+    /// The directory holds an index written with another schema, e.g. by an older version
+    SchemaMismatch,
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchError::Io(e) => write!(f, "Could not access the search index: {}", e),
+            SearchError::Index(e) => write!(f, "Search index error: {}", e),
+            SearchError::SchemaMismatch => write!(f, "The search index was written with a different schema"),
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+impl From<io::Error> for SearchError {
+    fn from(e: io::Error) -> Self {
+        SearchError::Io(e)
+    }
+}
+
+impl From<tantivy::TantivyError> for SearchError {
+    fn from(e: tantivy::TantivyError) -> Self {
+        SearchError::Index(e)
+    }
+}
+
+impl From<tantivy::directory::error::OpenDirectoryError> for SearchError {
+    fn from(e: tantivy::directory::error::OpenDirectoryError) -> Self {
+        SearchError::Index(e.into())
+    }
+}
+
+/// A move found by [`MoveSearchIndex::search`]. The index only stores ids, so hits are resolved
+/// against loaded data with [`IndexedHit::resolve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedHit {
+    /// The character's [`CharacterId::id`]
+    pub character_id: String,
+    pub identifier: String,
+    /// Higher is better. Only meaningful relative to other hits of the same query.
+    pub score: f32,
+}
+
+impl IndexedHit {
+    /// The hit's character and move in `frame_data`, `None` if it isn't loaded there
+    pub fn resolve<'a>(&self, frame_data: &'a FrameData) -> Option<(&'a CharacterId, &'a Move)> {
+        let character = frame_data.character_frame_data.iter().find(|c| c.character_id.id == self.character_id)?;
+        let move_data = character.moves.iter().find(|m| m.identifier == self.identifier)?;
+        Some((&character.character_id, move_data))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    character_id: Field,
+    identifier: Field,
+    identifier_text: Field,
+    name: Field,
+    notes: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            character_id: builder.add_text_field("character_id", STRING | STORED),
+            identifier: builder.add_text_field("identifier", STRING | STORED),
+            identifier_text: builder.add_text_field("identifier_text", TEXT),
+            name: builder.add_text_field("name", TEXT),
+            notes: builder.add_text_field("notes", TEXT),
+        };
+        (builder.build(), fields)
+    }
+}
+
+/// Every move's identifier, name and notes in a tantivy index, searched with the same boosts and
+/// typo tolerance as [`TextIndex::search_ranked`](super::TextIndex::search_ranked)
+#[derive(Clone)]
+pub struct MoveSearchIndex {
+    index: Index,
+    reader: IndexReader,
+    fields: Fields,
+}
+
+impl std::fmt::Debug for MoveSearchIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MoveSearchIndex").field("documents", &self.len()).finish()
+    }
+}
+
+impl MoveSearchIndex {
+    /// Indexes `frame_data` in memory
+    pub fn build_in_ram(frame_data: &FrameData) -> Result<Self, SearchError> {
+        let (schema, fields) = Fields::schema();
+        MoveSearchIndex::fill(Index::create_in_ram(schema), fields, frame_data)
+    }
+
+    /// Indexes `frame_data` into `dir`, replacing any moves indexed there before, so it can be
+    /// reopened with [`MoveSearchIndex::open`]
+    pub fn build_in_dir(frame_data: &FrameData, dir: &Path) -> Result<Self, SearchError> {
+        fs::create_dir_all(dir)?;
+        let (schema, fields) = Fields::schema();
+        let index = Index::open_or_create(MmapDirectory::open(dir)?, schema.clone())?;
+        if index.schema() != schema {
+            return Err(SearchError::SchemaMismatch);
+        }
+        MoveSearchIndex::fill(index, fields, frame_data)
+    }
+
+    /// Opens an index written by [`MoveSearchIndex::build_in_dir`]
+    pub fn open(dir: &Path) -> Result<Self, SearchError> {
+        let (schema, fields) = Fields::schema();
+        let index = Index::open_in_dir(dir)?;
+        if index.schema() != schema {
+            return Err(SearchError::SchemaMismatch);
+        }
+        let reader = index.reader()?;
+        Ok(MoveSearchIndex { index, reader, fields })
+    }
+
+    fn fill(index: Index, fields: Fields, frame_data: &FrameData) -> Result<Self, SearchError> {
+        let mut writer = index.writer_with_num_threads::<TantivyDocument>(1, WRITER_MEMORY)?;
+        writer.delete_all_documents()?;
+        for character in &frame_data.character_frame_data {
+            for move_data in &character.moves {
+                writer.add_document(doc!(
+                    fields.character_id => character.character_id.id.as_ref(),
+                    fields.identifier => move_data.identifier.as_str(),
+                    fields.identifier_text => move_data.identifier.as_str(),
+                    fields.name => move_data.name.as_str(),
+                    fields.notes => move_data.notes.as_str(),
+                ))?;
+            }
+        }
+        writer.commit()?;
+        let reader = index.reader()?;
+        reader.reload()?;
+        Ok(MoveSearchIndex { index, reader, fields })
+    }
+
+    /// How many moves are indexed
+    pub fn len(&self) -> usize {
+        self.reader.searcher().num_docs() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The underlying tantivy index, e.g. to run custom queries
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Up to `limit` moves matching every word of `query`, best first. Each word matches exactly,
+    /// as a prefix, or one typo away (two for long words), with identifiers boosted above names
+    /// and names above notes.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<IndexedHit>, SearchError> {
+        let clauses = words(query)
+            .map(|word| (Occur::Must, self.word_query(&word)))
+            .collect::<Vec<_>>();
+        if clauses.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let searcher = self.reader.searcher();
+        let mut hits = Vec::new();
+        for (score, address) in searcher.search(&BooleanQuery::new(clauses), &TopDocs::with_limit(limit))? {
+            let document = searcher.doc::<TantivyDocument>(address)?;
+            let text = |field| document.get_first(field).and_then(|value| value.as_str()).unwrap_or_default().to_string();
+            hits.push(IndexedHit {
+                character_id: text(self.fields.character_id),
+                identifier: text(self.fields.identifier),
+                score,
+            });
+        }
+        Ok(hits)
+    }
+
+    /// Matches `word` in any field, weighted like [`TextIndex::search_ranked`](super::TextIndex::search_ranked)
+    fn word_query(&self, word: &str) -> Box<dyn Query> {
+        let typos = max_typos(word) as u8;
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for (field, boost) in [(self.fields.identifier_text, IDENTIFIER_BOOST), (self.fields.name, NAME_BOOST), (self.fields.notes, NOTES_BOOST)] {
+            let term = Term::from_field_text(field, word);
+            let exact = TermQuery::new(term.clone(), IndexRecordOption::WithFreqs);
+            clauses.push((Occur::Should, Box::new(BoostQuery::new(Box::new(exact), boost))));
+            let prefix = FuzzyTermQuery::new_prefix(term.clone(), 0, true);
+            clauses.push((Occur::Should, Box::new(BoostQuery::new(Box::new(prefix), boost * PREFIX_WEIGHT))));
+            if typos > 0 {
+                let typo = FuzzyTermQuery::new(term, typos, true);
+                clauses.push((Occur::Should, Box::new(BoostQuery::new(Box::new(typo), boost * TYPO_WEIGHT))));
+            }
+        }
+        Box::new(BooleanQuery::new(clauses))
+    }
+}
+
+impl FrameData {
+    /// Builds a [`MoveSearchIndex`] over every move in memory
+    pub fn search_index(&self) -> Result<MoveSearchIndex, SearchError> {
+        MoveSearchIndex::build_in_ram(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing::TempDir;
+    use crate::testing;
+
+    fn data() -> FrameData {
+        let named = |identifier: &str, name: &str, notes: &str| Move {
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+            notes: notes.to_string(),
+            ..Move::default()
+        };
+        FrameData {
+            character_frame_data: vec![testing::character_frame_data(&character::RYU, vec![
+                named("2MK", "Crouching Medium Kick", "Cancel into Hadoken"),
+                named("236P", "Hadoken", "Projectile"),
+                named("623P", "Shoryuken", "Invincible"),
+            ])],
+        }
+    }
+
+    #[test]
+    fn ranks_and_tolerates_typos() {
+        let data = data();
+        let index = data.search_index().unwrap();
+        assert_eq!(index.len(), 3);
+        let identifiers = |query| index.search(query, 10).unwrap().into_iter().map(|hit| hit.identifier).collect::<Vec<String>>();
+        assert_eq!(identifiers("hadoken"), vec!["236P", "2MK"]);
+        assert_eq!(identifiers("hadokne"), vec!["236P", "2MK"]);
+        assert_eq!(identifiers("shoryu"), vec!["623P"]);
+        assert_eq!(identifiers("623p"), vec!["623P"]);
+        assert_eq!(identifiers("medum kic"), vec!["2MK"]);
+        assert!(identifiers("").is_empty());
+        let hit = &index.search("shoryuken", 1).unwrap()[0];
+        assert_eq!(hit.resolve(&data).map(|(c, m)| (c.id.as_ref(), m.name.as_str())), Some(("ryu", "Shoryuken")));
+    }
+
+    #[test]
+    fn persists_to_a_directory() {
+        let dir = TempDir::new("sf6rs-search-index-test");
+        MoveSearchIndex::build_in_dir(&data(), &dir).unwrap();
+        let reopened = MoveSearchIndex::open(&dir).unwrap();
+        assert_eq!(reopened.search("projectile", 10).unwrap()[0].identifier, "236P");
+
+        let rebuilt = MoveSearchIndex::build_in_dir(&FrameData { character_frame_data: Vec::new() }, &dir).unwrap();
+        assert!(rebuilt.is_empty());
+        assert!(MoveSearchIndex::open(&dir).unwrap().search("projectile", 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! Comparators that order wiki-formatted values by the number they start with rather than as
+//! text, so `3`, `4(5)`, `12-14` and `-` sort the way a reader expects instead of `12-14` landing
+//! before `3`. Use [`compare_values`] for single columns, [`by_field`] to sort moves and a
+//! [`MoveComparator`] to order a [`MoveQuery`](crate::query::MoveQuery):
+//! ```
+//! use sf6rs::framedata::Move;
+//! use sf6rs::sort;
+//!
+//! let mut moves = vec![Move::default(); 3];
+//! moves.sort_by(sort::by_field("startup").unwrap());
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::framedata::{columns, is_placeholder, Move};
+use crate::parsed::Advantage;
+
+/// Orders two wiki values: numbers ascending, with knockdown advantages like `KD +38` counted as
+/// their number and ties broken by the text after it, so `4` comes before `4(5)`. Values without a
+/// number follow in text order, and placeholders like `-` come last.
+pub fn compare_values(a: &str, b: &str) -> Ordering {
+    sort_key(a).cmp(&sort_key(b))
+}
+
+/// [`compare_values`] with numbers descending and placeholders still last
+pub fn compare_values_descending(a: &str, b: &str) -> Ordering {
+    match (sort_key(a), sort_key(b)) {
+        (SortKey::Number(x, xs), SortKey::Number(y, ys)) => y.cmp(&x).then_with(|| xs.cmp(&ys)),
+        (x, y) => x.cmp(&y),
+    }
+}
+
+/// A comparator ordering moves by one of their fields with [`compare_values`], `None` if `field`
+/// isn't a [`Move`] field name like `startup`
+pub fn by_field(field: &str) -> Option<impl Fn(&Move, &Move) -> Ordering + '_> {
+    columns::by_field(field)?;
+    Some(move |a: &Move, b: &Move| compare_values(a.get(field).unwrap_or_default(), b.get(field).unwrap_or_default()))
+}
+
+/// Like [`by_field`], largest first
+pub fn by_field_descending(field: &str) -> Option<impl Fn(&Move, &Move) -> Ordering + '_> {
+    columns::by_field(field)?;
+    Some(move |a: &Move, b: &Move| compare_values_descending(a.get(field).unwrap_or_default(), b.get(field).unwrap_or_default()))
+}
+
+/// Orders moves by one of their fields, see [`MoveQuery::order_by`](crate::query::MoveQuery::order_by)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveComparator {
+    field: &'static str,
+    descending: bool,
+}
+
+impl MoveComparator {
+    /// Orders by `field` with [`compare_values`], `None` if `field` isn't a [`Move`] field name
+    pub fn ascending(field: &str) -> Option<Self> {
+        let column = columns::by_field(field)?;
+        Some(MoveComparator { field: column.field, descending: false })
+    }
+
+    /// Orders by `field` with [`compare_values_descending`], `None` if `field` isn't a [`Move`]
+    /// field name
+    pub fn descending(field: &str) -> Option<Self> {
+        let column = columns::by_field(field)?;
+        Some(MoveComparator { field: column.field, descending: true })
+    }
+
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    pub fn compare(&self, a: &Move, b: &Move) -> Ordering {
+        let (a, b) = (a.get(self.field).unwrap_or_default(), b.get(self.field).unwrap_or_default());
+        if self.descending {
+            compare_values_descending(a, b)
+        } else {
+            compare_values(a, b)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Number(i32, String),
+    Text(String),
+    Placeholder,
+}
+
+fn sort_key(value: &str) -> SortKey {
+    let value = value.trim();
+    if is_placeholder(value) {
+        return SortKey::Placeholder;
+    }
+    match Advantage::parse(value).as_ref().and_then(Advantage::frames) {
+        Some(number) => SortKey::Number(number, value.to_ascii_lowercase()),
+        None => SortKey::Text(value.to_ascii_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_numerically() {
+        let mut values = vec!["-", "12-14", "4(5)", "Varies", "3", "4", "KD +38", "-6"];
+        values.sort_by(|a, b| compare_values(a, b));
+        assert_eq!(values, vec!["-6", "3", "4", "4(5)", "12-14", "KD +38", "Varies", "-"]);
+        values.sort_by(|a, b| compare_values_descending(a, b));
+        assert_eq!(values, vec!["KD +38", "12-14", "4", "4(5)", "3", "-6", "Varies", "-"]);
+        assert!(by_field("not_a_field").is_none());
+        assert!(MoveComparator::descending("not_a_field").is_none());
+    }
+}
@@ -0,0 +1,3 @@
+//! Universal game mechanics that aren't tied to any one character's frame data
+
+pub mod defense;
@@ -0,0 +1,136 @@
+//! Defensive system numbers, so trainer apps can reference them instead of hardcoding values
+//! that change with patches. Each value records where it was taken from.
+
+/// The supercombo.gg page the universal mechanics below are sourced from
+pub const SOURCE: &str = "https://wiki.supercombo.gg/w/Street_Fighter_6/Game_Data";
+
+/// A frame window or timing for a universal mechanic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mechanic {
+    pub name: &'static str,
+    /// The first frame of the window, counted from the start of the relevant action
+    pub start: u8,
+    /// The last frame of the window, inclusive
+    pub end: u8,
+    pub source: &'static str,
+}
+
+impl Mechanic {
+    /// How many frames the window lasts
+    pub const fn frames(&self) -> u8 {
+        self.end - self.start + 1
+    }
+}
+
+/// Startup of the universal `LPLK` throw
+pub const THROW_STARTUP: Mechanic = Mechanic {
+    name: "Throw startup",
+    start: 5,
+    end: 5,
+    source: SOURCE,
+};
+
+/// Frames after being grabbed during which `LPLK` techs the throw
+pub const THROW_TECH_WINDOW: Mechanic = Mechanic {
+    name: "Throw tech window",
+    start: 1,
+    end: 7,
+    source: SOURCE,
+};
+
+/// Frames at the start of a Drive Parry that turn a parried hit into a Perfect Parry
+pub const PERFECT_PARRY_WINDOW: Mechanic = Mechanic {
+    name: "Perfect Parry window",
+    start: 1,
+    end: 2,
+    source: SOURCE,
+};
+
+/// Startup of Drive Impact, the window a reaction Drive Impact has to beat
+pub const DRIVE_IMPACT_STARTUP: Mechanic = Mechanic {
+    name: "Drive Impact startup",
+    start: 26,
+    end: 26,
+    source: SOURCE,
+};
+
+/// Frames of the opponent's Drive Impact during which a Drive Impact of your own still beats it
+/// by armor breaking it first. Derived from [`DRIVE_IMPACT_STARTUP`]: an answer started by the
+/// time the opponent's hits is still absorbed by its armor.
+pub const DRIVE_IMPACT_REVERSAL_WINDOW: Mechanic = Mechanic {
+    name: "Drive Impact reversal window",
+    start: 1,
+    end: DRIVE_IMPACT_STARTUP.start,
+    source: SOURCE,
+};
+
+/// Startup of Drive Reversal, usable from blockstun or hitstun
+pub const DRIVE_REVERSAL_STARTUP: Mechanic = Mechanic {
+    name: "Drive Reversal startup",
+    start: 20,
+    end: 20,
+    source: SOURCE,
+};
+
+/// How a knocked down character can choose to get up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WakeupOption {
+    /// Getting up in place by not pressing anything
+    QuickRise,
+    /// Rolling backwards by holding back, only possible after a normal knockdown
+    BackRise,
+}
+
+impl WakeupOption {
+    pub const ALL: [WakeupOption; 2] = [WakeupOption::QuickRise, WakeupOption::BackRise];
+
+    /// Frames added to a knockdown's advantage compared to [`WakeupOption::QuickRise`]. The wiki's
+    /// `KD +x` values hold for both: SF6 rises take the same time in either direction, and back
+    /// rise only changes the distance.
+    pub const fn extra_frames(&self) -> u8 {
+        match self {
+            WakeupOption::QuickRise => 0,
+            WakeupOption::BackRise => 0,
+        }
+    }
+
+    /// Where the option's timing is sourced from
+    pub const fn source(&self) -> &'static str {
+        SOURCE
+    }
+
+    /// Whether this option is available after a hard knockdown
+    pub const fn after_hard_knockdown(&self) -> bool {
+        matches!(self, WakeupOption::QuickRise)
+    }
+}
+
+/// Every [`Mechanic`] in this module
+pub const ALL: [Mechanic; 6] = [
+    THROW_STARTUP, THROW_TECH_WINDOW, PERFECT_PARRY_WINDOW, DRIVE_IMPACT_STARTUP,
+    DRIVE_IMPACT_REVERSAL_WINDOW, DRIVE_REVERSAL_STARTUP,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_windows() {
+        assert_eq!(THROW_TECH_WINDOW.frames(), 7);
+        assert_eq!(PERFECT_PARRY_WINDOW.frames(), 2);
+        assert_eq!(DRIVE_IMPACT_REVERSAL_WINDOW.end, DRIVE_IMPACT_STARTUP.start);
+        assert!(ALL.iter().all(|mechanic| mechanic.start <= mechanic.end && mechanic.source == SOURCE));
+        for (i, mechanic) in ALL.iter().enumerate() {
+            assert!(ALL[i + 1..].iter().all(|other| other.name != mechanic.name));
+        }
+    }
+
+    #[test]
+    fn describes_wakeup_options() {
+        assert!(WakeupOption::ALL.iter().all(|option| option.extra_frames() == 0));
+        assert!(WakeupOption::QuickRise.after_hard_knockdown());
+        assert!(!WakeupOption::BackRise.after_hard_knockdown());
+    }
+}
@@ -0,0 +1,421 @@
+//! Effect tags derived from a move's notes and columns, so questions like "which Juri moves cause
+//! a hard knockdown" don't require reading notes strings.
+
+use crate::character::CharacterId;
+use crate::format;
+use crate::framedata::{is_placeholder, CharacterFrameData, FrameData, Move};
+
+/// An effect or property a move has
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tag {
+    Knockdown,
+    HardKnockdown,
+    Crumple,
+    WallSplat,
+    WallBounce,
+    Launcher,
+    Overhead,
+    Low,
+    Throw,
+    Projectile,
+    Armor,
+    Invincible,
+    Airborne,
+    /// Freezes the screen on startup or hit (supers, Drive Impact), pausing the opponent's timeline
+    Superfreeze,
+    /// Plays a cinematic on hit, during which neither timeline advances
+    Cinematic,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 15] = [
+        Tag::Knockdown, Tag::HardKnockdown, Tag::Crumple, Tag::WallSplat, Tag::WallBounce,
+        Tag::Launcher, Tag::Overhead, Tag::Low, Tag::Throw, Tag::Projectile, Tag::Armor,
+        Tag::Invincible, Tag::Airborne, Tag::Superfreeze, Tag::Cinematic,
+    ];
+
+    fn applies_to(&self, move_data: &Move) -> bool {
+        let notes = move_data.notes.to_ascii_lowercase();
+        let guard = move_data.guard.trim();
+        match self {
+            Tag::Knockdown => move_data.hit_advantage.trim_start().to_ascii_uppercase().starts_with("KD")
+                || notes.contains("knockdown"),
+            Tag::HardKnockdown => notes.contains("hard knockdown") || notes.contains("hkd"),
+            Tag::Crumple => notes.contains("crumple"),
+            Tag::WallSplat => notes.contains("wall splat") || notes.contains("wallsplat"),
+            Tag::WallBounce => notes.contains("wall bounce") || notes.contains("wallbounce"),
+            Tag::Launcher => notes.contains("launch") || notes.contains("juggle state"),
+            Tag::Overhead => guard.eq_ignore_ascii_case("H") || notes.contains("overhead"),
+            Tag::Low => guard.eq_ignore_ascii_case("L"),
+            Tag::Throw => guard.eq_ignore_ascii_case("T") || guard.eq_ignore_ascii_case("Throw"),
+            Tag::Projectile => !is_placeholder(&move_data.projectile_speed) || notes.contains("projectile"),
+            Tag::Armor => !is_placeholder(&move_data.armor),
+            Tag::Invincible => !is_placeholder(&move_data.invuln),
+            Tag::Airborne => !is_placeholder(&move_data.airborne),
+            Tag::Superfreeze => move_data.is_super()
+                || move_data.name.to_ascii_lowercase().contains("drive impact")
+                || notes.contains("superfreeze") || notes.contains("super freeze") || notes.contains("screen freeze"),
+            Tag::Cinematic => notes.contains("cinematic") || move_data.super_art_level() == Some(3),
+        }
+    }
+}
+
+/// The kind of move, the way the wiki's movelist sections group them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveCategory {
+    /// Single-button attacks, standing, crouching and jumping
+    Normal,
+    /// Single-button attacks with a direction, like 6HP
+    CommandNormal,
+    /// Chains of normals performed in a set order, like 5MP~HP
+    TargetCombo,
+    /// Motion or charge attacks
+    Special,
+    /// A special performed with two buttons for Drive gauge, like 236PP
+    OverDriveSpecial,
+    SuperArt,
+    Throw,
+    /// Drive Impact, Drive Parry, Drive Reversal and Drive Rush
+    DriveSystem,
+    Taunt,
+}
+
+impl MoveCategory {
+    pub const ALL: [MoveCategory; 9] = [
+        MoveCategory::Normal, MoveCategory::CommandNormal, MoveCategory::TargetCombo,
+        MoveCategory::Special, MoveCategory::OverDriveSpecial, MoveCategory::SuperArt,
+        MoveCategory::Throw, MoveCategory::DriveSystem, MoveCategory::Taunt,
+    ];
+
+    /// The category's lowercase name, e.g. `command normal`
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoveCategory::Normal => "normal",
+            MoveCategory::CommandNormal => "command normal",
+            MoveCategory::TargetCombo => "target combo",
+            MoveCategory::Special => "special",
+            MoveCategory::OverDriveSpecial => "overdrive special",
+            MoveCategory::SuperArt => "super art",
+            MoveCategory::Throw => "throw",
+            MoveCategory::DriveSystem => "drive system",
+            MoveCategory::Taunt => "taunt",
+        }
+    }
+
+    /// Finds a category by [`MoveCategory::name`], ignoring case, spaces, underscores and a
+    /// trailing `s`. `super` is accepted for [`MoveCategory::SuperArt`].
+    pub fn from_name(name: &str) -> Option<MoveCategory> {
+        let name = name.trim().replace([' ', '_', '-'], "");
+        let name = name.strip_suffix(['s', 'S']).unwrap_or(&name);
+        if name.eq_ignore_ascii_case("super") {
+            return Some(MoveCategory::SuperArt);
+        }
+        MoveCategory::ALL.into_iter().find(|c| c.name().replace(' ', "").eq_ignore_ascii_case(name))
+    }
+
+    /// The category a wiki movelist section heading like `Command Normals` or `Special Moves`
+    /// stands for
+    pub fn from_heading(heading: &str) -> Option<MoveCategory> {
+        let heading = heading.to_ascii_lowercase();
+        let category = if heading.contains("command normal") || heading.contains("unique attack") {
+            MoveCategory::CommandNormal
+        } else if heading.contains("target combo") {
+            MoveCategory::TargetCombo
+        } else if heading.contains("normal") {
+            MoveCategory::Normal
+        } else if heading.contains("overdrive") || heading.starts_with("od ") {
+            MoveCategory::OverDriveSpecial
+        } else if heading.contains("special") {
+            MoveCategory::Special
+        } else if heading.contains("super") || heading.contains("critical art") {
+            MoveCategory::SuperArt
+        } else if heading.contains("throw") {
+            MoveCategory::Throw
+        } else if heading.contains("drive") {
+            MoveCategory::DriveSystem
+        } else if heading.contains("taunt") {
+            MoveCategory::Taunt
+        } else {
+            return None;
+        };
+        Some(category)
+    }
+
+    /// Whether moves in this category are performed with a single button press or a chain of
+    /// them, i.e. [`MoveCategory::Normal`], [`MoveCategory::CommandNormal`] or
+    /// [`MoveCategory::TargetCombo`]
+    pub fn is_normal(&self) -> bool {
+        matches!(self, MoveCategory::Normal | MoveCategory::CommandNormal | MoveCategory::TargetCombo)
+    }
+
+    /// Narrows the category of a whole movelist section down for one of its moves, for sections
+    /// that mix kinds, like OD versions listed among specials
+    pub(crate) fn refine(self, move_data: &Move) -> MoveCategory {
+        match self {
+            MoveCategory::Normal if is_command_normal(&move_data.identifier) => MoveCategory::CommandNormal,
+            MoveCategory::Special if is_overdrive(move_data) => MoveCategory::OverDriveSpecial,
+            category => category,
+        }
+    }
+}
+
+/// A normal performed with a direction other than neutral, down or up, like `6HP` or `3HK`
+fn is_command_normal(identifier: &str) -> bool {
+    format::is_normal(identifier) && identifier.trim().starts_with(['1', '3', '4', '6'])
+}
+
+fn is_target_combo(identifier: &str) -> bool {
+    let mut parts = identifier.split(['~', '>']).map(str::trim).peekable();
+    parts.peek().is_some_and(|first| format::is_normal(first)) && parts.count() > 1
+}
+
+fn is_overdrive(move_data: &Move) -> bool {
+    let input = move_data.input.trim().to_ascii_uppercase();
+    move_data.name.trim_start().starts_with("OD ")
+        || (input.ends_with("PP") && !input.ends_with("PPP"))
+        || (input.ends_with("KK") && !input.ends_with("KKK"))
+}
+
+/// A move most of the cast has a version of, so the same kind of move can be compared across
+/// characters, e.g. every Dragon Punch's startup and recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveArchetype {
+    /// An invincible rising attack like Shoryuken
+    DragonPunch,
+    /// A forward projectile like Hadoken
+    Fireball,
+    /// A spinning kick like Tatsumaki Senpu-kyaku
+    HurricaneKick,
+    DriveImpact,
+    DriveReversal,
+}
+
+impl MoveArchetype {
+    pub const ALL: [MoveArchetype; 5] = [
+        MoveArchetype::DragonPunch, MoveArchetype::Fireball, MoveArchetype::HurricaneKick,
+        MoveArchetype::DriveImpact, MoveArchetype::DriveReversal,
+    ];
+
+    /// The archetype's display name, e.g. `Dragon Punch`
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoveArchetype::DragonPunch => "Dragon Punch",
+            MoveArchetype::Fireball => "Fireball",
+            MoveArchetype::HurricaneKick => "Hurricane Kick",
+            MoveArchetype::DriveImpact => "Drive Impact",
+            MoveArchetype::DriveReversal => "Drive Reversal",
+        }
+    }
+
+    /// Finds an archetype by [`MoveArchetype::name`], ignoring case and spaces
+    pub fn from_name(name: &str) -> Option<MoveArchetype> {
+        let name = name.replace(' ', "");
+        MoveArchetype::ALL.into_iter().find(|a| a.name().replace(' ', "").eq_ignore_ascii_case(&name))
+    }
+
+    fn applies_to(&self, move_data: &Move) -> bool {
+        let name = move_data.name.to_ascii_lowercase();
+        let motion = move_data.input.trim_start_matches(|c: char| !c.is_ascii_digit()).replace(' ', "");
+        let punch = motion.contains('P');
+        match self {
+            MoveArchetype::DragonPunch => name.contains("shoryu") || name.contains("dragon punch")
+                || (motion.starts_with("623") && punch && move_data.is_reversal()),
+            MoveArchetype::Fireball => name.contains("hadoken") || name.contains("fireball")
+                || (motion.starts_with("236") && !motion.starts_with("236236") && punch && move_data.has_tag(Tag::Projectile)),
+            MoveArchetype::HurricaneKick => name.contains("tatsumaki") || name.contains("hurricane kick"),
+            MoveArchetype::DriveImpact => name.contains("drive impact"),
+            MoveArchetype::DriveReversal => name.contains("drive reversal"),
+        }
+    }
+}
+
+/// A combination of tags to match moves against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagQuery {
+    Has(Tag),
+    /// Every sub-query must match
+    All(Vec<TagQuery>),
+    /// At least one sub-query must match
+    Any(Vec<TagQuery>),
+    Not(Box<TagQuery>),
+}
+
+impl TagQuery {
+    /// Matches moves that have every one of `tags`
+    pub fn all(tags: impl IntoIterator<Item = Tag>) -> Self {
+        TagQuery::All(tags.into_iter().map(TagQuery::Has).collect())
+    }
+
+    /// Matches moves that have at least one of `tags`
+    pub fn any(tags: impl IntoIterator<Item = Tag>) -> Self {
+        TagQuery::Any(tags.into_iter().map(TagQuery::Has).collect())
+    }
+
+    pub fn matches(&self, move_data: &Move) -> bool {
+        match self {
+            TagQuery::Has(tag) => tag.applies_to(move_data),
+            TagQuery::All(queries) => queries.iter().all(|q| q.matches(move_data)),
+            TagQuery::Any(queries) => queries.iter().any(|q| q.matches(move_data)),
+            TagQuery::Not(query) => !query.matches(move_data),
+        }
+    }
+}
+
+impl From<Tag> for TagQuery {
+    fn from(tag: Tag) -> Self {
+        TagQuery::Has(tag)
+    }
+}
+
+impl Move {
+    /// Every [`Tag`] that applies to this move
+    pub fn tags(&self) -> Vec<Tag> {
+        Tag::ALL.into_iter().filter(|tag| tag.applies_to(self)).collect()
+    }
+
+    pub fn has_tag(&self, tag: Tag) -> bool {
+        tag.applies_to(self)
+    }
+
+    /// Which [`MoveCategory`] this move belongs to: the movelist section the wiki lists it under
+    /// when known, otherwise a guess from its identifier, input and name
+    pub fn category(&self) -> MoveCategory {
+        if let Some(category) = self.category {
+            return category;
+        }
+        let name = self.name.to_ascii_lowercase();
+        let category = if self.has_tag(Tag::Throw) {
+            MoveCategory::Throw
+        } else if self.is_super() {
+            MoveCategory::SuperArt
+        } else if name.contains("taunt") {
+            MoveCategory::Taunt
+        } else if name.starts_with("drive ") {
+            MoveCategory::DriveSystem
+        } else if is_target_combo(&self.identifier) {
+            MoveCategory::TargetCombo
+        } else if format::is_normal(&self.identifier) {
+            MoveCategory::Normal
+        } else {
+            MoveCategory::Special
+        };
+        category.refine(self)
+    }
+
+    /// Which shared [`MoveArchetype`] this move is a version of, if any
+    pub fn archetype(&self) -> Option<MoveArchetype> {
+        MoveArchetype::ALL.into_iter().find(|archetype| archetype.applies_to(self))
+    }
+}
+
+impl CharacterFrameData {
+    /// This character's moves matching a tag or [`TagQuery`]
+    pub fn moves_with_tag(&self, query: impl Into<TagQuery>) -> Vec<&Move> {
+        let query = query.into();
+        self.moves.iter().filter(|m| query.matches(m)).collect()
+    }
+}
+
+impl FrameData {
+    /// Every move across the roster matching a tag or [`TagQuery`], e.g.
+    /// `data.moves_with_tag(TagQuery::all([Tag::Low, Tag::Knockdown]))`
+    pub fn moves_with_tag(&self, query: impl Into<TagQuery>) -> Vec<(&CharacterId, &Move)> {
+        let query = query.into();
+        self.character_frame_data.iter()
+            .flat_map(|c| c.moves.iter().map(move |m| (&c.character_id, m)))
+            .filter(|(_, m)| query.matches(m))
+            .collect()
+    }
+
+    /// Every move across the roster that is a version of `archetype`, e.g. every Dragon Punch for
+    /// a side-by-side comparison
+    pub fn moves_with_archetype(&self, archetype: MoveArchetype) -> Vec<(&CharacterId, &Move)> {
+        self.character_frame_data.iter()
+            .flat_map(|c| c.moves.iter().map(move |m| (&c.character_id, m)))
+            .filter(|(_, m)| m.archetype() == Some(archetype))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_tags() {
+        let sweep = Move {
+            guard: String::from("L"),
+            hit_advantage: String::from("KD +30"),
+            notes: String::from("Hard knockdown on Punish Counter"),
+            ..Move::default()
+        };
+        assert_eq!(sweep.tags(), vec![Tag::Knockdown, Tag::HardKnockdown, Tag::Low]);
+        assert!(TagQuery::all([Tag::Low, Tag::HardKnockdown]).matches(&sweep));
+        assert!(TagQuery::any([Tag::Overhead, Tag::Low]).matches(&sweep));
+        assert!(!TagQuery::Not(Box::new(Tag::Low.into())).matches(&sweep));
+    }
+
+    #[test]
+    fn flags_freezes() {
+        let level_three = Move {
+            identifier: String::from("SA3"),
+            input: String::from("236236K"),
+            ..Move::default()
+        };
+        assert!(level_three.has_tag(Tag::Superfreeze) && level_three.has_tag(Tag::Cinematic));
+        assert!(!Move::default().has_tag(Tag::Superfreeze));
+        let critical_art = Move { identifier: String::from("214214K (CA)"), ..Move::default() };
+        assert!(critical_art.has_tag(Tag::Cinematic));
+        let special = Move { identifier: String::from("CAMMY SPIKE"), input: String::from("623K"), ..Move::default() };
+        assert!(!special.has_tag(Tag::Cinematic));
+        let level_two = Move { identifier: String::from("SA2"), ..Move::default() };
+        assert!(level_two.has_tag(Tag::Superfreeze) && !level_two.has_tag(Tag::Cinematic));
+    }
+
+    #[test]
+    fn guesses_categories() {
+        let with = |identifier: &str, input: &str| Move {
+            identifier: identifier.to_string(),
+            input: input.to_string(),
+            ..Move::default()
+        };
+        assert_eq!(with("6HP", "6HP").category(), MoveCategory::CommandNormal);
+        assert_eq!(with("5MP~HP", "5MP~HP").category(), MoveCategory::TargetCombo);
+        assert_eq!(with("236KK", "236KK").category(), MoveCategory::OverDriveSpecial);
+        assert_eq!(with("SA2", "214214P").category(), MoveCategory::SuperArt);
+        let scraped = Move { category: Some(MoveCategory::Taunt), ..with("5LP", "5LP") };
+        assert_eq!(scraped.category(), MoveCategory::Taunt);
+
+        assert_eq!(MoveCategory::from_name("command_normals"), Some(MoveCategory::CommandNormal));
+        assert_eq!(MoveCategory::from_name("supers"), Some(MoveCategory::SuperArt));
+        assert_eq!(MoveCategory::from_heading("Target Combos"), Some(MoveCategory::TargetCombo));
+        assert_eq!(MoveCategory::from_heading("Frame Data"), None);
+    }
+
+    #[test]
+    fn identifies_archetypes() {
+        let named = Move {
+            name: String::from("Heavy Shoryuken"),
+            input: String::from("623HP"),
+            ..Move::default()
+        };
+        let unnamed = Move {
+            name: String::from("Rising Jaguar"),
+            input: String::from("623HP"),
+            invuln: String::from("1-7 Full"),
+            ..Move::default()
+        };
+        let fireball = Move {
+            name: String::from("Sonic Boom"),
+            input: String::from("[4]6LP"),
+            ..Move::default()
+        };
+        assert_eq!(named.archetype(), Some(MoveArchetype::DragonPunch));
+        assert_eq!(unnamed.archetype(), Some(MoveArchetype::DragonPunch));
+        assert_eq!(fireball.archetype(), None);
+        assert_eq!(MoveArchetype::from_name("dragonpunch"), Some(MoveArchetype::DragonPunch));
+    }
+}
@@ -0,0 +1,363 @@
+//! Helpers for fuzzing the scraper against malformed wiki markup, enabled with the `test-support`
+//! feature. Pages are generated from a seed so any failure can be replayed:
+//! ```
+//! use sf6rs::character;
+//! use sf6rs::testing;
+//!
+//! for seed in 0..64 {
+//!     let html = testing::fuzz_page(seed);
+//!     testing::assert_parser_invariants(&character::RYU, &html);
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::character::{self, CharacterId};
+use crate::framedata::archive::{HtmlArchive, PageKind};
+use crate::framedata::{columns, parse_character_pages, CharacterFrameData, DataStatus, FrameData, Move, DEFAULT_IMAGE};
+use crate::parsed::CancelOptions;
+
+/// Hand-written pages covering the layouts the parser has to cope with, as `(file name, html)`.
+/// They reproduce the wiki's markup around frame data tables but are not saved copies of it, so
+/// they can't catch changes to the real page structure; pages saved with
+/// [`load_archived`](crate::framedata::load_archived) can, see [`archived_corpus`]. The frame data
+/// pages are named `<character id>.data.html` and gif pages `<character id>.gifs.html`, matching
+/// [`HtmlArchive`]'s layout.
+pub const CORPUS: [(&str, &str); 3] = [
+    ("ryu.data.html", include_str!("testing/corpus/ryu.data.html")),
+    ("jamie.data.html", include_str!("testing/corpus/jamie.data.html")),
+    ("ryu.gifs.html", include_str!("testing/corpus/ryu.gifs.html")),
+];
+
+/// Reads every frame data page stored in `archive`, so pages saved with
+/// [`load_archived`](crate::framedata::load_archived) can be run through
+/// [`assert_parser_invariants`] and used as seeds for [`mangle`]
+pub fn archived_corpus(archive: &HtmlArchive) -> std::io::Result<Vec<(&'static CharacterId, String)>> {
+    let mut pages = Vec::new();
+    for character_id in crate::character::CHARACTERS.iter() {
+        if let Some(html) = archive.read(character_id, PageKind::FrameData)? {
+            pages.push((*character_id, html));
+        }
+    }
+    Ok(pages)
+}
+
+/// Renders moves as a frame data page in the wiki's markup. Every field is written verbatim, so
+/// values should not contain markup; `image_link` and `image_alt` are not rendered.
+pub fn frame_data_page(moves: &[Move]) -> String {
+    let mut html = String::from("<html><body><div><div>\n");
+    for move_data in moves {
+        html.push_str(&format!(
+            "<section class=\"section-collapsible\"><h5><span>{}</span></h5><table class=\"wikitable\">\
+            <tr><th><div><p><span>{}</span>{}</p><div>{}</div></div></th></tr><tr>{}</tr><tr>",
+            move_data.identifier,
+            move_data.input,
+            move_data.modern_input.as_ref().map(|input| format!("<span class=\"modern-input\">{}</span>", input)).unwrap_or_default(),
+            move_data.name,
+            columns::wiki_columns().filter_map(|column| column.wiki_header).map(|header| format!("<th>{}</th>", header)).collect::<String>(),
+        ));
+        for column in columns::wiki_columns() {
+            html.push_str(&format!("<td>{}</td>", move_data.get(column.field).unwrap_or_default()));
+        }
+        html.push_str("</tr></table></section>\n");
+    }
+    html.push_str("</div></div></body></html>");
+    html
+}
+
+/// Renders `(move name, gif path)` pairs as a gif page in ultimateframedata.com's markup
+pub fn gif_page(gifs: &[(&str, &str)]) -> String {
+    let mut html = String::from("<html><body>\n");
+    for (name, path) in gifs {
+        html.push_str(&format!(
+            "<div class=\"movecontainer\"><div class=\"movename\">{}</div><div class=\"hitbox\"><a><img src=\"{}\"></a></div></div>\n",
+            name, path,
+        ));
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// A character with `moves` and neither gifs nor stats, for tests that build data by hand rather
+/// than parsing a page
+pub fn character_frame_data(character_id: &CharacterId, moves: Vec<Move>) -> CharacterFrameData {
+    CharacterFrameData {
+        character_id: character_id.clone(),
+        moves,
+        gifs: Vec::new(),
+        stats: None,
+    }
+}
+
+/// Ryu with only a Standing Light Punch (`5LP`) of startup `4`, the data the language binding
+/// tests query through their own APIs
+pub fn ryu_jab() -> FrameData {
+    let jab = Move { identifier: String::from("5LP"), name: String::from("Standing Light Punch"), startup: String::from("4"), ..Move::default() };
+    FrameData {
+        character_frame_data: vec![character_frame_data(&character::RYU, vec![jab])],
+    }
+}
+
+const INPUTS: [&str; 12] = ["5LP", "5MP", "5HP", "2LK", "2MK", "2HK", "236P", "623HP", "214K", "j.HP", "6HK", "[4]6P"];
+const VALUES: [&str; 14] = ["-", "", "300", "1000", "+4", "-6", "12", "4(8)", "KD +38", "LH", "Chn Sp SA", "1-10", "20% Starter", "N/A"];
+
+/// Generates a move with random plausible values. Values never contain markup, so rendering it
+/// with [`frame_data_page`] and parsing it back yields the same fields.
+pub fn random_move(rng: &mut impl Rng) -> Move {
+    let input = INPUTS.choose(rng).unwrap().to_string();
+    let name = format!("Move {}", rng.gen_range(0..1000));
+    let mut values = columns::wiki_columns().map(|_| VALUES.choose(rng).unwrap().to_string());
+    let mut next = || values.next().unwrap();
+    let mut move_data = Move {
+        identifier: input.clone(),
+        input,
+        modern_input: None,
+        name,
+        category: None,
+        image_link: DEFAULT_IMAGE.to_string(),
+        image_alt: String::new(),
+        image_info: None,
+        damage: next(),
+        chip_damage: next(),
+        damage_scaling: next(),
+        guard: next(),
+        cancel: next(),
+        cancel_options: CancelOptions::default(),
+        hitconfirm_window: next(),
+        startup: next(),
+        active: next(),
+        recovery: next(),
+        total: next(),
+        hitstun: next(),
+        blockstun: next(),
+        drive_damage_block: next(),
+        drive_damage_hit: next(),
+        drive_gain: next(),
+        super_gain_hit: next(),
+        super_gain_block: next(),
+        projectile_speed: next(),
+        invuln: next(),
+        armor: next(),
+        airborne: next(),
+        juggle_start: next(),
+        juggle_increase: next(),
+        juggle_limit: next(),
+        perfect_parry_advantage: next(),
+        after_dr_hit: next(),
+        after_dr_block: next(),
+        dr_cancel_hit: next(),
+        dr_cancel_block: next(),
+        punish_advantage: next(),
+        hit_advantage: next(),
+        block_advantage: next(),
+        notes: next(),
+    };
+    move_data.cancel_options = CancelOptions::parse(&move_data.cancel);
+    move_data
+}
+
+/// Fragments of markup the wiki has been seen to produce, or that a broken edit could
+const JUNK: [&str; 10] = [
+    "<span>", "</span>", "</td>", "<td>", "</tr>", "<table class=\"wikitable\">", "</section>",
+    "<h5><span>??</span></h5>", "&amp;", "<!-- -->",
+];
+
+/// Applies a few random corruptions to `html`: dropping or duplicating spans of the page, inserting
+/// stray tags, and truncating it
+pub fn mangle(html: &str, rng: &mut impl Rng) -> String {
+    let mut html = html.to_string();
+    for _ in 0..rng.gen_range(1..=4) {
+        let start = random_boundary(&html, rng);
+        let end = start + random_boundary(&html[start..], rng);
+        match rng.gen_range(0..4) {
+            0 => html.replace_range(start..end, ""),
+            1 => {
+                let span = html[start..end].to_string();
+                html.insert_str(end, &span);
+            }
+            2 => html.insert_str(start, JUNK.choose(rng).unwrap()),
+            _ => html.truncate(end),
+        }
+    }
+    html
+}
+
+fn random_boundary(text: &str, rng: &mut impl Rng) -> usize {
+    let mut index = rng.gen_range(0..=text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Generates a frame data page from `seed`: a page of random moves, or one of the [`CORPUS`]
+/// pages, usually [`mangle`]d
+pub fn fuzz_page(seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let html = if rng.gen_bool(0.5) {
+        let moves = (0..rng.gen_range(0..8)).map(|_| random_move(&mut rng)).collect::<Vec<Move>>();
+        frame_data_page(&moves)
+    } else {
+        let pages = CORPUS.iter()
+            .filter(|(name, _)| name.ends_with(".data.html"))
+            .map(|(_, html)| *html)
+            .collect::<Vec<&str>>();
+        pages.choose(&mut rng).unwrap().to_string()
+    };
+    if rng.gen_bool(0.8) {
+        mangle(&html, &mut rng)
+    } else {
+        html
+    }
+}
+
+/// A parser invariant that did not hold for a page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantViolation(pub String);
+
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parser invariant violated: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvariantViolation {}
+
+/// Parses `html` as `character_id`'s frame data page and checks the properties every parse must
+/// have however broken the markup is: no more moves than move sections, every move has a name and
+/// an absolute image url, and the completeness scores stay in range.
+pub fn check_parser_invariants(character_id: &CharacterId, html: &str) -> Result<CharacterFrameData, InvariantViolation> {
+    let character_frame_data = parse_character_pages(character_id, html, None);
+    let sections = html.matches("<h5").count();
+    if character_frame_data.moves.len() > sections {
+        return Err(InvariantViolation(format!("parsed {} moves from {} sections", character_frame_data.moves.len(), sections)));
+    }
+    for move_data in &character_frame_data.moves {
+        if !move_data.image_link.starts_with("https://") {
+            return Err(InvariantViolation(format!("{} has image link {:?}", move_data.identifier, move_data.image_link)));
+        }
+        if move_data.image_alt.is_empty() {
+            return Err(InvariantViolation(format!("{} has no alt text", move_data.identifier)));
+        }
+        if !(0.0..=1.0).contains(&move_data.completeness()) {
+            return Err(InvariantViolation(format!("{} has completeness {}", move_data.identifier, move_data.completeness())));
+        }
+    }
+    if !(0.0..=1.0).contains(&character_frame_data.completeness()) {
+        return Err(InvariantViolation(format!("character completeness {}", character_frame_data.completeness())));
+    }
+    if (character_frame_data.status() == DataStatus::Empty) != character_frame_data.moves.is_empty() {
+        return Err(InvariantViolation(format!("status {:?} for {} moves", character_frame_data.status(), character_frame_data.moves.len())));
+    }
+    Ok(character_frame_data)
+}
+
+/// Like [`check_parser_invariants`], panicking with the violation instead
+pub fn assert_parser_invariants(character_id: &CharacterId, html: &str) -> CharacterFrameData {
+    check_parser_invariants(character_id, html).unwrap_or_else(|violation| panic!("{}", violation))
+}
+
+/// Asserts that `moves` survive being rendered with [`frame_data_page`] and parsed back
+pub fn assert_round_trip(character_id: &CharacterId, moves: &[Move]) {
+    let parsed = assert_parser_invariants(character_id, &frame_data_page(moves)).moves;
+    assert_eq!(parsed.len(), moves.len(), "round trip changed the number of moves");
+    for (expected, actual) in moves.iter().zip(&parsed) {
+        for name in Move::FIELDS.iter().filter(|name| **name != "image_alt") {
+            assert_eq!(expected.get(name), actual.get(name), "round trip changed {} of {}", name, expected.identifier);
+        }
+    }
+}
+
+/// A path under the system temp dir unique to this process and call, so concurrent test runs
+/// never share files. Whatever is created there is removed when it is dropped.
+#[derive(Debug)]
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// A path named after `prefix` that doesn't exist yet
+    pub fn new(prefix: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT.fetch_add(1, Ordering::Relaxed);
+        TempDir(std::env::temp_dir().join(format!("{}-{}-{}", prefix, std::process::id(), id)))
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0).or_else(|_| std::fs::remove_file(&self.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::parse_gifs;
+    use scraper::Html;
+
+    #[test]
+    fn corpus_holds_invariants() {
+        let ryu = assert_parser_invariants(&character::RYU, CORPUS[0].1);
+        assert_eq!(ryu.moves.len(), 4);
+        assert!(ryu.moves[0].image_link.contains("hitbox"));
+        assert_eq!(ryu.moves[0].image_info.as_ref().and_then(|info| info.width), Some(350));
+        assert_eq!(ryu.moves[3].notes, "-");
+        let jamie = assert_parser_invariants(&character::JAMIE, CORPUS[1].1);
+        assert!(matches!(jamie.status(), DataStatus::PartialData { .. }));
+        let gifs = parse_gifs(&Html::parse_document(CORPUS[2].1));
+        assert_eq!(gifs.len(), 2);
+        assert_eq!(gifs[1].name, "Crouching Medium Kick");
+    }
+
+    /// Runs the parser over real pages saved with `load_archived` into the directory named by
+    /// `SF6RS_ARCHIVE`, if set
+    #[test]
+    fn archived_pages_hold_invariants() {
+        let Some(dir) = std::env::var_os("SF6RS_ARCHIVE") else {
+            return;
+        };
+        for (character_id, html) in archived_corpus(&HtmlArchive::new(dir)).unwrap() {
+            let character_frame_data = assert_parser_invariants(character_id, &html);
+            assert!(!character_frame_data.moves.is_empty(), "no moves parsed for {}", character_id.id);
+        }
+    }
+
+    #[test]
+    fn random_moves_round_trip() {
+        for seed in 0..64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let moves = (0..rng.gen_range(0..6)).map(|_| random_move(&mut rng)).collect::<Vec<Move>>();
+            assert_round_trip(&character::RYU, &moves);
+        }
+    }
+
+    #[test]
+    fn fuzzed_pages_hold_invariants() {
+        for seed in 0..256 {
+            if let Err(violation) = check_parser_invariants(&character::RYU, &fuzz_page(seed)) {
+                panic!("seed {}: {}", seed, violation);
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! String helpers shared by the lookups and scrapers.
+
+/// Lowercases `value` and drops everything but letters and digits, so `Shoryuken!`, `shoryu-ken`
+/// and `SHORYUKEN` compare equal
+pub(crate) fn normalize(value: &str) -> String {
+    value.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The file name at the end of a wiki image url or path, without any query or fragment and with a
+/// thumbnail's size prefix removed, e.g. `SF6_Ryu_5lp.png` for
+/// `/images/thumb/1/12/SF6_Ryu_5lp.png/350px-SF6_Ryu_5lp.png`
+pub(crate) fn wiki_file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let file = path.rsplit('/').next().unwrap_or(path);
+    file.split_once("px-")
+        .filter(|(size, _)| !size.is_empty() && size.bytes().all(|b| b.is_ascii_digit()))
+        .map_or(file, |(_, file)| file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_text() {
+        assert_eq!(normalize("Shoryu-ken!"), "shoryuken");
+        assert_eq!(normalize(" 2 MK "), "2mk");
+        assert_eq!(normalize("Él"), "él");
+    }
+
+    #[test]
+    fn reads_wiki_file_names() {
+        assert_eq!(wiki_file_name("/images/thumb/1/12/SF6_Ryu_5lp.png/350px-SF6_Ryu_5lp.png"), "SF6_Ryu_5lp.png");
+        assert_eq!(wiki_file_name("https://wiki.supercombo.gg/images/1/12/SF6_Ryu_5lp.gif?version=2"), "SF6_Ryu_5lp.gif");
+        assert_eq!(wiki_file_name("SF6_Ryu_2px-thing.png"), "SF6_Ryu_2px-thing.png");
+        assert_eq!(wiki_file_name("px-SF6_Ryu.png"), "px-SF6_Ryu.png");
+    }
+}
@@ -0,0 +1,132 @@
+//! Lightweight view models for GUI frameworks (Dioxus, egui, iced...). Every field is either a
+//! display-ready string or an already parsed number, so widgets can bind to them directly and
+//! diff them cheaply through `PartialEq`.
+
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, Move};
+
+/// A display-ready, flattened view of a [`Move`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveView {
+    pub character: String,
+    pub identifier: String,
+    pub name: String,
+    pub input: String,
+    pub image_url: String,
+    pub image_alt: String,
+    pub damage: String,
+    pub damage_value: Option<i32>,
+    pub guard: String,
+    pub cancel: String,
+    pub startup: String,
+    pub startup_frames: Option<i32>,
+    pub active: String,
+    pub recovery: String,
+    pub recovery_frames: Option<i32>,
+    pub on_hit: String,
+    pub on_hit_frames: Option<i32>,
+    pub on_block: String,
+    pub on_block_frames: Option<i32>,
+    pub invuln: String,
+    pub armor: String,
+    pub notes: String,
+}
+
+impl MoveView {
+    pub fn new(character: &CharacterId, move_data: &Move) -> Self {
+        MoveView {
+            character: character.name(),
+            identifier: display_text(&move_data.identifier),
+            name: display_text(&move_data.name),
+            input: display_text(&move_data.input),
+            image_url: move_data.image_link.clone(),
+            image_alt: move_data.image_alt.clone(),
+            damage: display_text(&move_data.damage),
+            damage_value: signed_number(&move_data.damage),
+            guard: display_text(&move_data.guard),
+            cancel: display_text(&move_data.cancel),
+            startup: display_text(&move_data.startup),
+            startup_frames: signed_number(&move_data.startup),
+            active: display_text(&move_data.active),
+            recovery: display_text(&move_data.recovery),
+            recovery_frames: signed_number(&move_data.recovery),
+            on_hit: display_text(&move_data.hit_advantage),
+            on_hit_frames: signed_number(&move_data.hit_advantage),
+            on_block: display_text(&move_data.block_advantage),
+            on_block_frames: signed_number(&move_data.block_advantage),
+            invuln: display_text(&move_data.invuln),
+            armor: display_text(&move_data.armor),
+            notes: display_text(&move_data.notes),
+        }
+    }
+}
+
+/// A display-ready view of a character and all of their moves
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterView {
+    pub id: String,
+    pub name: String,
+    pub moves: Vec<MoveView>,
+}
+
+impl CharacterView {
+    pub fn new(character_frame_data: &CharacterFrameData) -> Self {
+        let character_id = &character_frame_data.character_id;
+        CharacterView {
+            id: character_id.id.to_string(),
+            name: character_id.name(),
+            moves: character_frame_data.moves.iter()
+                .map(|m| MoveView::new(character_id, m))
+                .collect(),
+        }
+    }
+}
+
+impl From<&CharacterFrameData> for CharacterView {
+    fn from(character_frame_data: &CharacterFrameData) -> Self {
+        CharacterView::new(character_frame_data)
+    }
+}
+
+/// Turns scraped inner html into plain text: tags are dropped, common entities decoded, and the
+/// wiki's `-` placeholder becomes an empty string.
+fn display_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    let text = text.trim();
+    if text == "-" {
+        String::new()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleans_display_text() {
+        assert_eq!(display_text("-"), "");
+        assert_eq!(display_text(" Hit &amp; Run "), "Hit & Run");
+        assert_eq!(display_text("<b>KD</b> +38"), "KD +38");
+    }
+}
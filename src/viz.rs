@@ -0,0 +1,318 @@
+//! Charts of frame data for data-driven character breakdowns and tier discussions, e.g. every
+//! Ken move's startup against its block advantage, or the whole roster compared on a few
+//! [`Metric`]s in a [`Heatmap`]:
+//! ```
+//! use sf6rs::framedata::{CharacterFrameData, Move};
+//! use sf6rs::{character, viz};
+//!
+//! let ken = CharacterFrameData { character_id: character::KEN.clone(), moves: vec![Move::default()], gifs: Vec::new(), stats: None };
+//! let plot = viz::scatter(&ken, "startup", "block_advantage").unwrap();
+//! let svg = plot.to_svg();
+//! ```
+
+use std::fmt::Write;
+
+use crate::character::CharacterId;
+use crate::framedata::{columns, CharacterFrameData, FrameData, Move};
+use crate::parsed::Advantage;
+#[cfg(feature = "render")]
+use crate::render::{font, Canvas, Color, TableStyle};
+
+pub mod heatmap;
+
+pub use heatmap::{heatmap, Heatmap, Metric};
+
+/// Size of an SVG plot
+pub const SVG_WIDTH: u32 = 640;
+pub const SVG_HEIGHT: u32 = 480;
+/// Size of a PNG plot in canvas units, each [`TableStyle::scale`] image pixels large
+#[cfg(feature = "render")]
+pub const PNG_WIDTH: u32 = 320;
+#[cfg(feature = "render")]
+pub const PNG_HEIGHT: u32 = 240;
+
+/// The colors points are drawn in, one per character, in the order characters are plotted
+const PALETTE: [[u8; 3]; 8] = [
+    [0x58, 0x65, 0xf2], [0xed, 0x42, 0x45], [0x23, 0xa5, 0x59], [0xf0, 0xb2, 0x32],
+    [0xeb, 0x45, 0x9e], [0x00, 0xa8, 0xfc], [0x9b, 0x59, 0xb6], [0xe6, 0x7e, 0x22],
+];
+
+/// What to plot: one character's moves or every loaded character's
+#[derive(Debug, Clone, Copy)]
+pub enum PlotSource<'a> {
+    Character(&'a CharacterFrameData),
+    Roster(&'a FrameData),
+}
+
+impl<'a> From<&'a CharacterFrameData> for PlotSource<'a> {
+    fn from(character_frame_data: &'a CharacterFrameData) -> Self {
+        PlotSource::Character(character_frame_data)
+    }
+}
+
+impl<'a> From<&'a FrameData> for PlotSource<'a> {
+    fn from(frame_data: &'a FrameData) -> Self {
+        PlotSource::Roster(frame_data)
+    }
+}
+
+impl<'a> PlotSource<'a> {
+    fn characters(&self) -> &'a [CharacterFrameData] {
+        match self {
+            PlotSource::Character(character_frame_data) => std::slice::from_ref(*character_frame_data),
+            PlotSource::Roster(frame_data) => &frame_data.character_frame_data,
+        }
+    }
+}
+
+/// One move of a [`ScatterPlot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScatterPoint {
+    pub character: CharacterId,
+    pub identifier: String,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Moves plotted by two of their fields, see [`scatter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScatterPlot {
+    pub x_field: String,
+    pub y_field: String,
+    /// The plotted characters, in the order of [`PALETTE`] colors
+    pub characters: Vec<CharacterId>,
+    pub points: Vec<ScatterPoint>,
+}
+
+/// Plots the moves of a character or the whole roster by two numeric fields, like `startup` and
+/// `block_advantage`. Moves without a number in either field are left out; knockdown advantages
+/// like `KD +38` count as their number. `None` if either field isn't a [`Move`] field name.
+pub fn scatter<'a>(source: impl Into<PlotSource<'a>>, x_field: &str, y_field: &str) -> Option<ScatterPlot> {
+    columns::by_field(x_field)?;
+    columns::by_field(y_field)?;
+    let characters = source.into().characters();
+    let points = characters.iter()
+        .flat_map(|c| c.moves.iter().filter_map(|m| {
+            Some(ScatterPoint {
+                character: c.character_id.clone(),
+                identifier: m.identifier.clone(),
+                x: value(m, x_field)?,
+                y: value(m, y_field)?,
+            })
+        }))
+        .collect();
+    Some(ScatterPlot {
+        x_field: x_field.to_string(),
+        y_field: y_field.to_string(),
+        characters: characters.iter().map(|c| c.character_id.clone()).collect(),
+        points,
+    })
+}
+
+fn value(move_data: &Move, field: &str) -> Option<i32> {
+    Advantage::parse(move_data.get(field)?).as_ref().and_then(Advantage::frames)
+}
+
+/// The range of values along one side of a plot, always including zero and widened to whole ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Axis {
+    min: i32,
+    max: i32,
+    step: i32,
+}
+
+impl Axis {
+    fn fit(values: impl Iterator<Item = i32>) -> Axis {
+        let (min, max) = values.fold((0, 0), |(min, max), value| (min.min(value), max.max(value)));
+        let step = tick_step(max - min);
+        let min = min.div_euclid(step) * step;
+        let max = (max + step - 1).div_euclid(step) * step;
+        Axis {
+            min,
+            max: if max == min { min + step } else { max },
+            step,
+        }
+    }
+
+    fn ticks(&self) -> impl Iterator<Item = i32> {
+        (self.min..=self.max).step_by(self.step as usize)
+    }
+
+    /// Where `value` falls between the axis' ends, from `0.0` to `1.0`
+    fn fraction(&self, value: i32) -> f64 {
+        (value - self.min) as f64 / (self.max - self.min) as f64
+    }
+}
+
+/// The smallest of 1, 2 and 5 times a power of ten that splits `span` into at most ten ticks
+fn tick_step(span: i32) -> i32 {
+    let mut magnitude = 1;
+    loop {
+        for step in [magnitude, magnitude * 2, magnitude * 5] {
+            if span <= step * 10 {
+                return step;
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+/// The area of an image a plot's points are drawn in
+struct PlotArea {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl PlotArea {
+    fn x(&self, axis: &Axis, value: i32) -> f64 {
+        self.left + axis.fraction(value) * self.width
+    }
+
+    fn y(&self, axis: &Axis, value: i32) -> f64 {
+        self.top + (1.0 - axis.fraction(value)) * self.height
+    }
+}
+
+impl ScatterPlot {
+    fn axes(&self) -> (Axis, Axis) {
+        (Axis::fit(self.points.iter().map(|p| p.x)), Axis::fit(self.points.iter().map(|p| p.y)))
+    }
+
+    fn color(&self, character: &CharacterId) -> [u8; 3] {
+        let index = self.characters.iter().position(|c| c == character).unwrap_or_default();
+        PALETTE[index % PALETTE.len()]
+    }
+
+    /// Draws the plot as an SVG image, with each point's move in its tooltip
+    pub fn to_svg(&self) -> String {
+        let (x_axis, y_axis) = self.axes();
+        let area = PlotArea { left: 56.0, top: 24.0, width: SVG_WIDTH as f64 - 80.0, height: SVG_HEIGHT as f64 - 80.0 };
+        let bottom = area.top + area.height;
+        let right = area.left + area.width;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\
+            <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>",
+            SVG_WIDTH, SVG_HEIGHT,
+        );
+        for tick in x_axis.ticks() {
+            let x = area.x(&x_axis, tick);
+            let stroke = if tick == 0 { "#80848e" } else { "#e3e5e8" };
+            let _ = write!(svg, "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"{3}\"/>", x, area.top, bottom, stroke);
+            let _ = write!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#313338\">{}</text>", x, bottom + 16.0, tick);
+        }
+        for tick in y_axis.ticks() {
+            let y = area.y(&y_axis, tick);
+            let stroke = if tick == 0 { "#80848e" } else { "#e3e5e8" };
+            let _ = write!(svg, "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{2:.1}\" y2=\"{1:.1}\" stroke=\"{3}\"/>", area.left, y, right, stroke);
+            let _ = write!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" fill=\"#313338\">{}</text>", area.left - 6.0, y + 4.0, tick);
+        }
+        let _ = write!(svg, "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\" fill=\"#313338\">{}</text>", area.left + area.width / 2.0, SVG_HEIGHT - 12, escape(&self.x_field));
+        let _ = write!(svg, "<text transform=\"translate(16 {:.1}) rotate(-90)\" text-anchor=\"middle\" fill=\"#313338\">{}</text>", area.top + area.height / 2.0, escape(&self.y_field));
+
+        for point in &self.points {
+            let [r, g, b] = self.color(&point.character);
+            let _ = write!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"0.8\"><title>{} {}: {}, {}</title></circle>",
+                area.x(&x_axis, point.x), area.y(&y_axis, point.y), r, g, b,
+                escape(&point.character.name()), escape(&point.identifier), point.x, point.y,
+            );
+        }
+        if self.characters.len() > 1 {
+            for (index, character) in self.characters.iter().enumerate() {
+                let [r, g, b] = self.color(character);
+                let y = area.top + 8.0 + index as f64 * 16.0;
+                let _ = write!(svg, "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"#{:02x}{:02x}{:02x}\"/>", right - 96.0, y, r, g, b);
+                let _ = write!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"#313338\">{}</text>", right - 88.0, y + 4.0, escape(&character.name()));
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Draws the plot as a PNG image in `style`'s colors and scale
+    #[cfg(feature = "render")]
+    pub fn to_png(&self, style: &TableStyle) -> Vec<u8> {
+        let (x_axis, y_axis) = self.axes();
+        let area = PlotArea { left: 32.0, top: 8.0, width: PNG_WIDTH as f64 - 40.0, height: PNG_HEIGHT as f64 - 36.0 };
+        let (top, bottom) = (area.top as u32, (area.top + area.height) as u32);
+        let (left, right) = (area.left as u32, (area.left + area.width) as u32);
+        let mut canvas = Canvas::new(PNG_WIDTH, PNG_HEIGHT, style.scale, style.background);
+        for tick in x_axis.ticks() {
+            let x = area.x(&x_axis, tick) as u32;
+            canvas.fill_rect(x, top, 1, bottom - top, if tick == 0 { style.text } else { style.header });
+            let label = tick.to_string();
+            canvas.text(x.saturating_sub(font::text_width(&label) / 2), bottom + 3, &label, style.text);
+        }
+        for tick in y_axis.ticks() {
+            let y = area.y(&y_axis, tick) as u32;
+            canvas.fill_rect(left, y, right - left, 1, if tick == 0 { style.text } else { style.header });
+            let label = tick.to_string();
+            canvas.text(left.saturating_sub(font::text_width(&label) + 3), y.saturating_sub(font::line_height() / 2), &label, style.text);
+        }
+        let label = format!("{} / {}", self.x_field, self.y_field);
+        canvas.text(PNG_WIDTH.saturating_sub(font::text_width(&label)) / 2, PNG_HEIGHT - font::line_height() - 2, &label, style.header_text);
+
+        for point in &self.points {
+            let (x, y) = (area.x(&x_axis, point.x) as u32, area.y(&y_axis, point.y) as u32);
+            canvas.fill_rect(x.saturating_sub(1), y.saturating_sub(1), 3, 3, opaque(self.color(&point.character)));
+        }
+        if self.characters.len() > 1 {
+            for (index, character) in self.characters.iter().enumerate() {
+                let y = top + 2 + index as u32 * (font::line_height() + 2);
+                canvas.fill_rect(right - 64, y + 2, 3, 3, opaque(self.color(character)));
+                canvas.text(right - 58, y, &character.name(), style.text);
+            }
+        }
+        canvas.to_png()
+    }
+}
+
+#[cfg(feature = "render")]
+fn opaque([r, g, b]: [u8; 3]) -> Color {
+    [r, g, b, 0xff]
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::testing;
+
+    fn with(identifier: &str, startup: &str, block: &str) -> Move {
+        Move {
+            identifier: identifier.to_string(),
+            startup: startup.to_string(),
+            block_advantage: block.to_string(),
+            ..Move::default()
+        }
+    }
+
+    #[test]
+    fn plots_moves() {
+        let ken = testing::character_frame_data(&character::KEN, vec![with("5LP", "4", "-1"), with("5MP~HP", "6(9)", "KD +30"), with("2HK", "8", "-"), with("623HP", "6", "-31")]);
+        let plot = scatter(&ken, "startup", "block_advantage").unwrap();
+        assert_eq!(plot.points.iter().map(|p| (p.x, p.y)).collect::<Vec<(i32, i32)>>(), vec![(4, -1), (6, 30), (6, -31)]);
+        assert!(scatter(&ken, "startup", "frames").is_none());
+
+        let (x_axis, y_axis) = plot.axes();
+        assert_eq!(x_axis, Axis { min: 0, max: 6, step: 1 });
+        assert_eq!(y_axis, Axis { min: -40, max: 30, step: 10 });
+        assert_eq!(tick_step(100), 10);
+
+        let svg = plot.to_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert!(svg.contains("<title>Ken 5MP~HP: 6, 30</title>"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        #[cfg(feature = "render")]
+        assert_eq!(crate::framedata::media::sniff_dimensions(&plot.to_png(&TableStyle::DARK)), Some((PNG_WIDTH * 2, PNG_HEIGHT * 2)));
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use crate::character::CharacterId;
+use crate::format::signed_number;
+use crate::framedata::{CharacterFrameData, FrameData};
+use crate::viz::escape;
+
+/// Stats fields where fewer frames are better
+const LOWER_IS_BETTER_STATS: [&str; 5] = ["forward_dash", "back_dash", "jump_startup", "jump_active", "jump_recovery"];
+
+/// Size of each value cell of an SVG heatmap
+const CELL_WIDTH: u32 = 120;
+const CELL_HEIGHT: u32 = 28;
+/// Width of the character name column
+const NAME_WIDTH: u32 = 110;
+const HEADER_HEIGHT: u32 = 36;
+
+/// A way of comparing characters, one column of a [`Heatmap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Metric {
+    /// Startup of the character's fastest normal, lower is better
+    FastestNormal,
+    /// How many of the character's moves are plus on block
+    PlusOnBlock,
+    /// How many invincible reversals the character has
+    Reversals,
+    /// A [`CharacterStats`](crate::framedata::CharacterStats) field, like `forward_walk_speed`
+    Stat(String),
+}
+
+impl Metric {
+    /// The column header, e.g. `Fastest Normal` or `forward_walk_speed`
+    pub fn label(&self) -> &str {
+        match self {
+            Metric::FastestNormal => "Fastest Normal",
+            Metric::PlusOnBlock => "Plus on Block",
+            Metric::Reversals => "Reversals",
+            Metric::Stat(field) => field,
+        }
+    }
+
+    /// Whether a smaller value ranks a character higher
+    pub fn lower_is_better(&self) -> bool {
+        match self {
+            Metric::FastestNormal => true,
+            Metric::Stat(field) => LOWER_IS_BETTER_STATS.contains(&field.as_str()),
+            _ => false,
+        }
+    }
+
+    /// The metric's value for a character, `None` if the data to work it out is missing
+    pub fn value(&self, character_frame_data: &CharacterFrameData) -> Option<f64> {
+        let moves = &character_frame_data.moves;
+        let value = match self {
+            Metric::FastestNormal => moves.iter()
+                .filter(|m| m.category().is_normal())
+                .filter_map(|m| signed_number(&m.startup).filter(|startup| *startup > 0))
+                .min()? as f64,
+            Metric::PlusOnBlock => moves.iter().filter(|m| m.block_frames().is_some_and(|frames| frames > 0)).count() as f64,
+            Metric::Reversals => moves.iter().filter(|m| m.is_reversal()).count() as f64,
+            Metric::Stat(field) => character_frame_data.stats.as_ref().and_then(|s| s.number(field))?,
+        };
+        Some(value)
+    }
+}
+
+/// Characters compared on several metrics at once, see [`heatmap`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heatmap {
+    pub metrics: Vec<Metric>,
+    pub characters: Vec<CharacterId>,
+    /// One row per character, one value per metric
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+/// Compares every loaded character on each of `metrics`
+pub fn heatmap(frame_data: &FrameData, metrics: &[Metric]) -> Heatmap {
+    let characters = &frame_data.character_frame_data;
+    Heatmap {
+        metrics: metrics.to_vec(),
+        characters: characters.iter().map(|c| c.character_id.clone()).collect(),
+        values: characters.iter().map(|c| metrics.iter().map(|m| m.value(c)).collect()).collect(),
+    }
+}
+
+impl Heatmap {
+    /// How a character's value of a metric ranks against the rest of the roster, from `0.0` for
+    /// the worst to `1.0` for the best. `None` for missing values.
+    pub fn score(&self, character: usize, metric: usize) -> Option<f64> {
+        let value = self.values.get(character)?.get(metric).copied().flatten()?;
+        let column = self.values.iter().filter_map(|row| row.get(metric).copied().flatten());
+        let (min, max) = column.fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(v), max.max(v)));
+        if max == min {
+            return Some(1.0);
+        }
+        let score = (value - min) / (max - min);
+        Some(if self.metrics[metric].lower_is_better() { 1.0 - score } else { score })
+    }
+
+    /// Draws the heatmap as an SVG image, a row per character and a column per metric, with
+    /// each cell shaded from red for the worst value to green for the best
+    pub fn to_svg(&self) -> String {
+        let width = NAME_WIDTH + CELL_WIDTH * self.metrics.len() as u32;
+        let height = HEADER_HEIGHT + CELL_HEIGHT * self.characters.len() as u32;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\
+            <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>",
+            width, height,
+        );
+        for (column, metric) in self.metrics.iter().enumerate() {
+            let x = NAME_WIDTH + CELL_WIDTH * column as u32 + CELL_WIDTH / 2;
+            let _ = write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-weight=\"bold\" fill=\"#313338\">{}</text>", x, HEADER_HEIGHT - 12, escape(metric.label()));
+        }
+        for (row, character) in self.characters.iter().enumerate() {
+            let y = HEADER_HEIGHT + CELL_HEIGHT * row as u32;
+            let _ = write!(svg, "<text x=\"8\" y=\"{}\" fill=\"#313338\">{}</text>", y + CELL_HEIGHT / 2 + 4, escape(&character.name()));
+            for column in 0..self.metrics.len() {
+                let x = NAME_WIDTH + CELL_WIDTH * column as u32;
+                let (fill, text) = match (self.score(row, column), self.values[row][column]) {
+                    (Some(score), Some(value)) => (shade(score), value.to_string()),
+                    _ => (String::from("#e3e5e8"), String::from("-")),
+                };
+                let _ = write!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#ffffff\"/>", x, y, CELL_WIDTH, CELL_HEIGHT, fill);
+                let _ = write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#060607\">{}</text>", x + CELL_WIDTH / 2, y + CELL_HEIGHT / 2 + 4, text);
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// A color from red at `0.0` through yellow to green at `1.0`
+fn shade(score: f64) -> String {
+    const RED: [f64; 3] = [237.0, 66.0, 69.0];
+    const YELLOW: [f64; 3] = [254.0, 231.0, 92.0];
+    const GREEN: [f64; 3] = [87.0, 242.0, 135.0];
+    let (from, to, t) = if score < 0.5 { (RED, YELLOW, score * 2.0) } else { (YELLOW, GREEN, score * 2.0 - 1.0) };
+    let [r, g, b] = [0, 1, 2].map(|i| (from[i] + (to[i] - from[i]) * t).round() as u8);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character;
+    use crate::framedata::{CharacterStats, Move};
+    use crate::testing;
+
+    fn character(character_id: &CharacterId, startup: &str, block: &str, walk_speed: &str) -> CharacterFrameData {
+        let mut character_frame_data = testing::character_frame_data(character_id, vec![Move { identifier: String::from("5LP"), startup: startup.to_string(), block_advantage: block.to_string(), ..Move::default() }]);
+        character_frame_data.stats = Some(CharacterStats { forward_walk_speed: walk_speed.to_string(), ..CharacterStats::default() });
+        character_frame_data
+    }
+
+    #[test]
+    fn compares_the_roster() {
+        let data = FrameData {
+            character_frame_data: vec![
+                character(&character::RYU, "4", "-1", "0.032"),
+                character(&character::KEN, "5", "+2", "0.038"),
+                character(&character::JP, "6", "-", "-"),
+            ],
+        };
+        let metrics = [Metric::FastestNormal, Metric::PlusOnBlock, Metric::Stat(String::from("forward_walk_speed"))];
+        let map = heatmap(&data, &metrics);
+        assert_eq!(map.values[0], vec![Some(4.0), Some(0.0), Some(0.032)]);
+        assert_eq!(map.values[2][2], None);
+        assert_eq!((map.score(0, 0), map.score(1, 0), map.score(2, 0)), (Some(1.0), Some(0.5), Some(0.0)));
+        assert_eq!(map.score(1, 2), Some(1.0));
+        assert_eq!(map.score(2, 2), None);
+
+        let svg = map.to_svg();
+        assert!(svg.contains(">forward_walk_speed</text>"));
+        assert!(svg.contains(&format!("fill=\"{}\"", shade(1.0))));
+        assert_eq!(shade(0.0), "#ed4245");
+        assert_eq!(shade(0.5), "#fee75c");
+    }
+}